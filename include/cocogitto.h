@@ -0,0 +1,51 @@
+/*
+ * C bindings for cocogitto, built with the `ffi` feature (see docs/Packaging.md).
+ *
+ * Every function returns 0 on success and -1 on failure, in which case
+ * cog_last_error() describes what went wrong. Strings written to `out` are
+ * owned by the caller and must be released with cog_string_free().
+ */
+
+#ifndef COCOGITTO_H
+#define COCOGITTO_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Check `message` against the commit types configured in the repository at
+ * `repo_path`, or the default ones when `repo_path` is NULL.
+ *
+ * Returns 0 when the message is valid and 1 when it is not, in which case
+ * `out` receives the diagnostic as JSON.
+ */
+int cog_verify_message(const char *repo_path, const char *message, char **out);
+
+/*
+ * Write the version `cog bump --auto` would create in the repository at
+ * `repo_path` to `out`, tag prefix included.
+ */
+int cog_next_version(const char *repo_path, char **out);
+
+/*
+ * Write the changelog of the repository at `repo_path` for the revision range
+ * `range` to `out`, rendered with the configured template.
+ * A NULL `range` renders everything since the latest tag.
+ */
+int cog_render_changelog(const char *repo_path, const char *range, char **out);
+
+/*
+ * The error of the last failed call on this thread, or NULL.
+ * The string is owned by the library and valid until the next call on this thread.
+ */
+const char *cog_last_error(void);
+
+/* Release a string returned by this library. */
+void cog_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* COCOGITTO_H */
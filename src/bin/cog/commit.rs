@@ -1,29 +1,22 @@
 use std::fmt::Write;
 
-use cocogitto::COMMITS_METADATA;
+use cocogitto::CocoGitto;
 
 use anyhow::{bail, Result};
-use clap::builder::PossibleValuesParser;
 use conventional_commit_parser::commit::Separator;
 use itertools::Itertools;
 
-pub fn commit_types() -> PossibleValuesParser {
-    let types = COMMITS_METADATA
-        .iter()
-        .map(|(commit_type, _)| -> &str { commit_type.as_ref() });
-
-    types.into()
-}
-
 pub fn edit_message(
+    cocogitto: &CocoGitto,
     typ: &str,
     message: &str,
     scope: Option<&str>,
     breaking: bool,
 ) -> Result<(Option<String>, Option<String>, bool)> {
-    let template = prepare_edit_template(typ, message, scope, breaking);
+    let body = cocogitto.commit_template();
+    let template = prepare_edit_template(typ, message, scope, breaking, body.as_deref());
 
-    let edited = edit::edit(&template)?;
+    let edited = cocogitto.edit_message(&template)?;
 
     if edited.lines().all(|line| {
         let trimmed = line.trim_start();
@@ -83,7 +76,14 @@ fn prepare_header(typ: &str, message: &str, scope: Option<&str>) -> String {
     header
 }
 
-fn prepare_edit_template(typ: &str, message: &str, scope: Option<&str>, breaking: bool) -> String {
+// The body is seeded with the git `commit.template` content when there is one
+fn prepare_edit_template(
+    typ: &str,
+    message: &str,
+    scope: Option<&str>,
+    breaking: bool,
+    body: Option<&str>,
+) -> String {
     let mut template: String = EDIT_TEMPLATE.into();
     let header = prepare_header(typ, message, scope);
 
@@ -91,10 +91,11 @@ fn prepare_edit_template(typ: &str, message: &str, scope: Option<&str>, breaking
         template.push_str("# WARNING: This will be marked as a breaking change!\n");
     }
 
+    let body = body.map(str::trim_end).unwrap_or_default();
     write!(
         &mut template,
-        "{}\n\n# Message body\n\n\n# Message footer\n# For example, foo: bar\n\n\n",
-        header
+        "{}\n\n# Message body\n{}\n\n# Message footer\n# For example, foo: bar\n\n\n",
+        header, body
     )
     .unwrap();
 
@@ -0,0 +1,168 @@
+use cocogitto::CocoGitto;
+
+use clap_complete::Shell;
+
+use crate::COMMIT_TYPES;
+
+// How many commits are read to suggest scopes
+const SCOPE_HISTORY: usize = 500;
+
+/// The candidates printed by `cog __complete <kind> <prefix>`, one per line
+pub fn candidates(kind: &str, prefix: &str) -> Vec<String> {
+    let candidates = match kind {
+        "types" => COMMIT_TYPES
+            .keys()
+            .map(|commit_type| commit_type.as_ref().to_string())
+            .collect(),
+        "scopes" => CocoGitto::get()
+            .and_then(|cocogitto| cocogitto.recent_scopes(SCOPE_HISTORY))
+            .unwrap_or_default(),
+        // Range arguments complete the tag after `..`
+        "tags" => {
+            let (start, prefix) = match prefix.rsplit_once("..") {
+                Some((start, end)) => (format!("{}..", start), end),
+                None => (String::new(), prefix),
+            };
+
+            return CocoGitto::get()
+                .and_then(|cocogitto| cocogitto.version_tags())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|tag| tag.starts_with(prefix))
+                .map(|tag| format!("{}{}", start, tag))
+                .collect();
+        }
+        _ => vec![],
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate: &String| candidate.starts_with(prefix))
+        .collect()
+}
+
+/// Shell code appended to the generated completions, asking `cog __complete`
+/// for the commit types, scopes and tags of the current repository
+pub fn dynamic_script(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH),
+        Shell::Zsh => Some(ZSH),
+        Shell::Fish => Some(FISH),
+        _ => None,
+    }
+}
+
+const BASH: &str = r#"
+_cog_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local kind="" positional=0 word
+
+    # Positional arguments before the cursor, the subcommand excluded
+    for word in "${COMP_WORDS[@]:2:COMP_CWORD-2}"; do
+        [[ "${word}" != -* ]] && positional=$((positional + 1))
+    done
+
+    case "${COMP_WORDS[1]}" in
+        commit)
+            case "${positional}" in
+                0) kind=types ;;
+                2) kind=scopes ;;
+            esac
+            ;;
+        log)
+            case "${prev}" in
+                -t|--type) kind=types ;;
+                -s|--scope) kind=scopes ;;
+                -a|--author|-n|--max-count|--format) ;;
+                *) [[ "${cur}" != -* ]] && kind=tags ;;
+            esac
+            ;;
+        changelog)
+            case "${prev}" in
+                -a|--at) kind=tags ;;
+                -t|--template|-r|--remote|-o|--owner|--repository) ;;
+                *) [[ "${cur}" != -* ]] && kind=tags ;;
+            esac
+            ;;
+        stats|release-notes)
+            [[ "${cur}" != -* ]] && kind=tags
+            ;;
+    esac
+
+    if [[ -n "${kind}" ]]; then
+        COMPREPLY=($(cog __complete "${kind}" "${cur}" 2>/dev/null))
+        return 0
+    fi
+
+    _cog "$@"
+}
+
+complete -F _cog_dynamic -o bashdefault -o default cog
+"#;
+
+const ZSH: &str = r#"
+_cog_dynamic() {
+    local kind="" positional=0 word
+    local cur="${words[CURRENT]}" prev="${words[CURRENT-1]}"
+
+    # Positional arguments before the cursor, the subcommand excluded
+    for word in "${(@)words[3,CURRENT-1]}"; do
+        [[ "${word}" != -* ]] && positional=$((positional + 1))
+    done
+
+    case "${words[2]}" in
+        commit)
+            case "${positional}" in
+                0) kind=types ;;
+                2) kind=scopes ;;
+            esac
+            ;;
+        log)
+            case "${prev}" in
+                -t|--type) kind=types ;;
+                -s|--scope) kind=scopes ;;
+                -a|--author|-n|--max-count|--format) ;;
+                *) [[ "${cur}" != -* ]] && kind=tags ;;
+            esac
+            ;;
+        changelog)
+            case "${prev}" in
+                -a|--at) kind=tags ;;
+                -t|--template|-r|--remote|-o|--owner|--repository) ;;
+                *) [[ "${cur}" != -* ]] && kind=tags ;;
+            esac
+            ;;
+        stats|release-notes)
+            [[ "${cur}" != -* ]] && kind=tags
+            ;;
+    esac
+
+    if [[ -n "${kind}" ]]; then
+        local -a candidates
+        candidates=(${(f)"$(cog __complete "${kind}" "${cur}" 2>/dev/null)"})
+        compadd -Q -U -a candidates
+    else
+        _cog "$@"
+    fi
+}
+
+compdef _cog_dynamic cog
+"#;
+
+const FISH: &str = r#"
+function __cog_positional_count
+    set -l tokens (commandline -opc)
+    set -l count 0
+    for token in $tokens[3..-1]
+        string match -q -- '-*' $token; or set count (math $count + 1)
+    end
+    test $count -eq $argv[1]
+end
+
+complete -c cog -n "__fish_seen_subcommand_from commit; and __cog_positional_count 0" -f -a "(cog __complete types (commandline -ct))"
+complete -c cog -n "__fish_seen_subcommand_from commit; and __cog_positional_count 2" -f -a "(cog __complete scopes (commandline -ct))"
+complete -c cog -n "__fish_seen_subcommand_from log" -s t -l type -r -f -a "(cog __complete types (commandline -ct))"
+complete -c cog -n "__fish_seen_subcommand_from log" -s s -l scope -r -f -a "(cog __complete scopes (commandline -ct))"
+complete -c cog -n "__fish_seen_subcommand_from changelog" -s a -l at -r -f -a "(cog __complete tags (commandline -ct))"
+complete -c cog -n "__fish_seen_subcommand_from log changelog stats release-notes" -f -a "(cog __complete tags (commandline -ct))"
+"#;
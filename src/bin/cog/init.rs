@@ -0,0 +1,85 @@
+use std::io::{self, BufRead, Write};
+
+use cocogitto::settings::import::ImportSource;
+use cocogitto::settings::preset::Preset;
+use cocogitto::InitOptions;
+
+use anyhow::{bail, Result};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use itertools::Itertools;
+
+pub fn presets() -> impl TypedValueParser<Value = Preset> {
+    PossibleValuesParser::new(["rust", "node", "python"]).map(|preset| {
+        preset
+            .parse::<Preset>()
+            .expect("preset is a possible value")
+    })
+}
+
+pub fn import_sources() -> impl TypedValueParser<Value = ImportSource> {
+    PossibleValuesParser::new(["git-cliff", "standard-version", "semantic-release"]).map(|source| {
+        source
+            .parse::<ImportSource>()
+            .expect("source is a possible value")
+    })
+}
+
+/// Ask for the `cog init` options on stdin, answers default to the given preset
+pub fn prompt_options(preset: Option<Preset>, from: Option<ImportSource>) -> Result<InitOptions> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    let presets = Preset::ALL.iter().join("/");
+    let default = preset.map(|preset| preset.to_string()).unwrap_or_default();
+    let preset = match ask(&mut input, &format!("Preset ({})", presets), &default)?.as_str() {
+        "" => None,
+        preset => match preset.parse::<Preset>() {
+            Ok(preset) => Some(preset),
+            Err(err) => bail!(err),
+        },
+    };
+
+    let default = preset
+        .and_then(|preset| preset.settings().tag_prefix)
+        .unwrap_or_default();
+    // An imported configuration already sets the tag prefix
+    let tag_prefix = match from {
+        Some(_) => None,
+        None => Some(ask(&mut input, "Tag prefix", &default)?),
+    };
+
+    let remote = ask(&mut input, "Changelog remote, e.g. github.com", "")?;
+    let (remote, owner, repository) = if remote.is_empty() {
+        (None, None, None)
+    } else {
+        let owner = ask(&mut input, "Repository owner", "")?;
+        let repository = ask(&mut input, "Repository name", "")?;
+        (Some(remote), Some(owner), Some(repository))
+    };
+
+    let install_hooks = ask(&mut input, "Install the cog git hooks (y/n)", "n")? == "y";
+    let changelog = ask(&mut input, "Create an empty changelog (y/n)", "y")? == "y";
+
+    Ok(InitOptions {
+        preset,
+        from,
+        tag_prefix,
+        remote,
+        owner,
+        repository,
+        install_hooks,
+        changelog,
+    })
+}
+
+// Prompts are written to stderr, an empty answer selects the default
+fn ask(input: &mut impl BufRead, question: &str, default: &str) -> Result<String> {
+    eprint!("{} [{}]: ", question, default);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    let answer = answer.trim();
+
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
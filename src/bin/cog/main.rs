@@ -1,28 +1,73 @@
 mod commit;
+mod completion;
+mod init;
+mod progress;
+mod serve;
 
 use std::path::PathBuf;
 
 use cocogitto::conventional::changelog::template::{RemoteContext, Template};
 use cocogitto::conventional::commit as conv_commit;
+use cocogitto::conventional::diagnostic::diagnose;
+use cocogitto::conventional::lint::Linter;
 use cocogitto::conventional::version::VersionIncrement;
-use cocogitto::git::hook::HookKind;
+use cocogitto::git::hook::{HookFramework, HookKind};
+use cocogitto::git::identity::Identity;
 use cocogitto::git::revspec::RevspecPattern;
+use cocogitto::hook;
 use cocogitto::log::filter::{CommitFilter, CommitFilters};
+use cocogitto::log::format::{LogFormat, LogOptions};
 use cocogitto::log::output::Output;
-use cocogitto::{CocoGitto, SETTINGS};
-
-use anyhow::{Context, Result};
-use clap::builder::PossibleValuesParser;
+use cocogitto::report::{github, CheckReportFormat, OutputFormat};
+use cocogitto::reporter;
+use cocogitto::settings::import::ImportSource;
+use cocogitto::settings::preset::Preset;
+use cocogitto::settings::{Settings, Severity};
+use cocogitto::{CocoGitto, CommitsMetadata, InitOptions};
+use progress::ProgressReporter;
+
+use anyhow::{bail, Context, Result};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
 use clap::{ArgAction, ArgGroup, Args, CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    // Read on first use, once the `-C, --repository-path` option moved to the repository
+    static ref SETTINGS: Settings = CocoGitto::get()
+        .map(|cocogitto| cocogitto.settings().clone())
+        .unwrap_or_default();
+    static ref COMMIT_TYPES: CommitsMetadata = SETTINGS.commit_types();
+}
 
-fn hook_profiles() -> PossibleValuesParser {
-    let profiles = SETTINGS
-        .bump_profiles
-        .keys()
-        .map(|profile| -> &str { profile });
+fn log_formats() -> impl TypedValueParser<Value = LogFormat> {
+    PossibleValuesParser::new(["full", "oneline", "json"]).map(|format| {
+        format
+            .parse::<LogFormat>()
+            .expect("format is a possible value")
+    })
+}
+
+fn check_report_formats() -> impl TypedValueParser<Value = CheckReportFormat> {
+    PossibleValuesParser::new(["junit", "codequality"]).map(|format| {
+        format
+            .parse::<CheckReportFormat>()
+            .expect("format is a possible value")
+    })
+}
 
-    profiles.into()
+fn output_formats() -> impl TypedValueParser<Value = OutputFormat> {
+    PossibleValuesParser::new(["text", "github", "json"]).map(|format| {
+        format
+            .parse::<OutputFormat>()
+            .expect("format is a possible value")
+    })
+}
+
+fn identity(value: &str) -> Result<Identity, String> {
+    value.parse()
 }
 
 /// A command line tool for the conventional commits and semver specifications
@@ -33,7 +78,7 @@ fn hook_profiles() -> PossibleValuesParser {
     author = "Paul D. <paul.delafosse@protonmail.com>"
 )]
 struct Cli {
-    /// The level of verbosity: -v for ERROR, -vv for WARNING, -vvv for INFO
+    /// Show more details: -v for debug messages, -vv for trace messages
     #[arg(long, short = 'v', action = ArgAction::Count)]
     verbose: u8,
 
@@ -41,6 +86,15 @@ struct Cli {
     #[arg(long, short = 'q')]
     quiet: bool,
 
+    /// Run as if cog was started in the given repository instead of the current directory
+    #[arg(short = 'C', long, global = true, value_name = "PATH")]
+    repository_path: Option<PathBuf>,
+
+    /// Report results for a CI service, detected from the environment when not set,
+    /// or as JSON documents on stdout
+    #[arg(long, global = true, value_parser = output_formats())]
+    output_format: Option<OutputFormat>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -56,6 +110,19 @@ enum Command {
         /// Ignore merge commits messages
         #[arg(short, long)]
         ignore_merge_commits: bool,
+
+        /// Print a report of every checked commit for CI services
+        #[arg(long, value_parser = check_report_formats())]
+        output: Option<CheckReportFormat>,
+
+        /// Check the message of the commit being authored instead of the history, along with
+        /// the scope of the staged files, e.g. in a commit-msg hook
+        #[arg(long, conflicts_with_all = ["from_latest_tag", "output"])]
+        staged: bool,
+
+        /// The message checked by --staged, .git/COMMIT_EDITMSG by default
+        #[arg(long, requires = "staged", value_name = "PATH")]
+        file: Option<PathBuf>,
     },
 
     /// Create a new conventional commit
@@ -66,6 +133,36 @@ enum Command {
         /// Edit non conventional commits, starting from the latest tag to HEAD
         #[arg(short = 'l', long)]
         from_latest_tag: bool,
+
+        /// Only edit the commits in the given spec range, e.g. main..HEAD
+        #[arg(conflicts_with = "from_latest_tag")]
+        pattern: Option<String>,
+
+        /// Sign the rebased commits, their previous signature is invalidated
+        #[arg(short, long)]
+        sign: bool,
+
+        /// Do not sign the rebased commits, overrides the commit.gpgSign git config
+        #[arg(long, conflicts_with = "sign")]
+        no_gpg_sign: bool,
+    },
+
+    /// Rewrite non conventional commit messages with the `import_history` rules
+    ImportHistory {
+        /// Only rewrite the commits in the given spec range, e.g. main..HEAD
+        pattern: Option<String>,
+
+        /// Print the rewritten messages without rewriting history
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Sign the rebased commits, their previous signature is invalidated
+        #[arg(short, long)]
+        sign: bool,
+
+        /// Do not sign the rebased commits, overrides the commit.gpgSign git config
+        #[arg(long, conflicts_with = "sign")]
+        no_gpg_sign: bool,
     },
 
     /// Like git log but for conventional commits
@@ -89,12 +186,38 @@ enum Command {
         /// Omit error on the commit log
         #[arg(short = 'e', long)]
         no_error: bool,
+
+        /// Only log commits in the given spec range
+        pattern: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "full", value_parser = log_formats())]
+        format: LogFormat,
+
+        /// Limit the number of commits to output
+        #[arg(short = 'n', long)]
+        max_count: Option<usize>,
+    },
+
+    /// Count commits by type, scope and author
+    Stats {
+        /// Only count commits in the given spec range
+        pattern: Option<String>,
+
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Verify a single commit message
     Verify {
         /// The commit message
-        message: String,
+        #[arg(required_unless_present = "file")]
+        message: Option<String>,
+
+        /// Read the commit message from a file, as passed by the `commit-msg` git hook
+        #[arg(short, long, conflicts_with = "message")]
+        file: Option<PathBuf>,
 
         /// Ignore merge commit messages
         #[arg(short, long)]
@@ -129,6 +252,25 @@ enum Command {
         /// Name of the repository used during template generation
         #[arg(long, requires_all = ["owner", "remote"])]
         repository: Option<String>,
+
+        /// Only include the commits touching the given paths, as glob patterns relative to
+        /// the repository root, e.g. 'src/parser/**'. Can be repeated.
+        #[arg(long = "path", value_name = "GLOB")]
+        paths: Vec<String>,
+    },
+
+    /// Print the notes of a released version, without its heading
+    ReleaseNotes {
+        /// The released version tag
+        tag: String,
+
+        /// Read the notes from the changelog file instead of rendering them from commits
+        #[arg(long, conflicts_with = "template")]
+        from_changelog: bool,
+
+        /// Render the notes with the given template, see `cog changelog --template`
+        #[arg(long, short)]
+        template: Option<String>,
     },
 
     /// Commit changelog from latest tag to HEAD and create new tag
@@ -154,17 +296,109 @@ enum Command {
         #[arg(short, long, group = "bump-spec")]
         patch: bool,
 
+        /// Graduate a 0.x version to 1.0.0, which automatic bumps never do
+        #[arg(long, group = "bump-spec")]
+        stabilize: bool,
+
+        /// With --stabilize, release 1.0.0 even if breaking changes were made since the latest version
+        #[arg(long, requires = "stabilize")]
+        acknowledge_breaking_changes: bool,
+
         /// Set the pre-release version
         #[arg(long)]
         pre: Option<String>,
 
         /// Specify the bump profile hooks to run
-        #[arg(short = 'H', long, value_parser = hook_profiles())]
+        #[arg(short = 'H', long)]
         hook_profile: Option<String>,
 
+        /// Bump with the settings overridden by the given `[profile.<name>]` table
+        #[arg(long)]
+        profile: Option<String>,
+
         /// Dry-run: print the target version. No action taken
         #[arg(short, long)]
         dry_run: bool,
+
+        /// With --dry-run, also print the hook commands that would run
+        #[arg(long, requires = "dry_run")]
+        show_hooks: bool,
+
+        /// Do not sign the release commit and tag, overrides the git config
+        #[arg(long)]
+        no_gpg_sign: bool,
+
+        /// Author and commit the release as "Name <email>", overrides the `release_author` setting
+        #[arg(long, value_parser = identity)]
+        author: Option<Identity>,
+
+        /// Add a Signed-off-by trailer to the release commit
+        #[arg(long)]
+        signoff: bool,
+
+        /// Create the release commit even when the bump changed no file
+        #[arg(long)]
+        allow_empty: bool,
+
+        /// Move the release tag if it already exists instead of refusing to bump
+        #[arg(long)]
+        force_tag: bool,
+
+        /// Bump even from a branch `branch_whitelist` or `branch_denylist` forbid
+        #[arg(long)]
+        ignore_branch_protection: bool,
+
+        /// Write the changelog to this file instead of the `changelog.path` setting
+        #[arg(long, value_name = "PATH")]
+        changelog_path: Option<PathBuf>,
+    },
+
+    /// Publish the current version to the registries of the `publish.providers` setting
+    Publish {
+        /// Check the publication without publishing, providers unable to do so are skipped
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+
+    /// Print the current version, read from the latest tag
+    GetVersion {
+        /// Print the version an automatic bump would create instead, nothing is bumped
+        #[arg(long)]
+        next: bool,
+
+        /// Print a version between releases instead, e.g. `1.2.3-dev.5+gabcdef1` five commits
+        /// after the 1.2.3 tag
+        #[arg(long, conflicts_with = "next")]
+        snapshot: bool,
+
+        /// Print the version without the configured tag prefix
+        #[arg(long)]
+        no_prefix: bool,
+    },
+
+    /// Inspect and validate the settings merged from every configuration source
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// List, inspect and create version tags
+    #[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
+    Tag {
+        #[command(subcommand)]
+        command: Option<TagCommand>,
+
+        /// Tag the past commits changing the version declared in a manifest
+        #[arg(long)]
+        backfill: bool,
+
+        /// Manifest to read the versions from, defaults to the `version_source` setting
+        #[arg(long, value_name = "PATH", requires = "backfill")]
+        manifest: Option<PathBuf>,
+
+        /// Print the tags that would be created, nothing is tagged
+        #[arg(short, long, requires = "backfill")]
+        dry_run: bool,
     },
 
     /// Install cog config files
@@ -172,6 +406,18 @@ enum Command {
         /// Path to initialized dir
         #[arg(default_value = ".")]
         path: PathBuf,
+
+        /// Write the settings of a project ecosystem instead of the default ones
+        #[arg(short, long, value_parser = init::presets())]
+        template: Option<Preset>,
+
+        /// Convert the configuration of another release tool found in the initialized directory
+        #[arg(long, value_parser = init::import_sources())]
+        from: Option<ImportSource>,
+
+        /// Ask for the tag prefix, changelog remote, git hooks and changelog to set up
+        #[arg(short, long)]
+        interactive: bool,
     },
 
     /// Add git hooks to the repository
@@ -181,6 +427,19 @@ enum Command {
         hook_type: String,
     },
 
+    /// Generate a hook definition for a third party hook manager
+    GenerateHookConfig {
+        /// Hook manager to generate the configuration for
+        #[arg(value_parser = ["pre-commit", "husky"])]
+        framework: String,
+    },
+
+    /// Answer editor integrations over stdin/stdout, one JSON-RPC request per line.
+    ///
+    /// Methods are `verify` and `preview` taking a `message`, `types`, `scopes` with an
+    /// optional `limit`, and `exit`.
+    Serve,
+
     /// Generate shell completions
     GenerateCompletions {
         /// Shell to generate completions for
@@ -193,10 +452,59 @@ enum Command {
     GenerateManpage { cmd: String },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the merged settings
+    Show {
+        /// Print the settings as JSON instead of TOML
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check glob patterns, hooks and changelog template, unknown keys fail to load
+    Validate,
+
+    /// Print a single setting, nested keys are separated by dots: `changelog.path`
+    Get { key: String },
+
+    /// Print the JSON schema of the settings, for editor completion and validation
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum TagCommand {
+    /// Print the version tags, oldest first
+    List {
+        /// Only print the tags with this prefix, an empty prefix printing the tags without one
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+
+    /// Print the latest version tag
+    Latest,
+
+    /// Tag HEAD with a version greater than the current one, the tag prefix is added if missing
+    Create {
+        version: String,
+
+        /// Create an annotated tag with this message
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Do not sign the tag, overrides the git config
+        #[arg(long)]
+        no_gpg_sign: bool,
+
+        /// Move the tag if it already exists instead of refusing to create it
+        #[arg(long)]
+        force_tag: bool,
+    },
+}
+
 #[derive(Args)]
 struct CommitArgs {
     /// Conventional commit type
-    #[arg(name = "type", value_name = "TYPE", value_parser = commit::commit_types())]
+    #[arg(name = "type", value_name = "TYPE")]
     typ: String,
 
     /// Commit description
@@ -216,12 +524,59 @@ struct CommitArgs {
     /// Sign this commit
     #[arg(short, long)]
     sign: bool,
+
+    /// Do not sign this commit, overrides the commit.gpgSign git config
+    #[arg(long, conflicts_with = "sign")]
+    no_gpg_sign: bool,
+
+    /// Override the commit author, written "Name <email>"
+    #[arg(long, value_parser = identity)]
+    author: Option<Identity>,
+
+    /// Add a Signed-off-by trailer with the commit author identity
+    #[arg(long)]
+    signoff: bool,
+
+    /// Commit even when no change is staged
+    #[arg(long)]
+    allow_empty: bool,
+
+    /// Commit even from a branch the branch settings forbid, with `commit.check_branch` enabled
+    #[arg(long)]
+    ignore_branch_protection: bool,
 }
 
 fn main() -> Result<()> {
+    // `cog __complete <kind> [prefix]`, called by the completion scripts, is not a clap
+    // subcommand so that it stays out of the generated completions
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, kind, rest @ ..] = args.as_slice() {
+        if command == "__complete" {
+            let prefix = rest.first().map(String::as_str).unwrap_or_default();
+            for candidate in completion::candidates(kind, prefix) {
+                println!("{}", candidate);
+            }
+            return Ok(());
+        }
+    }
+
     let cli = Cli::parse();
 
+    // Settings, changelog and hooks paths are resolved from the working directory
+    if let Some(repository) = &cli.repository_path {
+        std::env::set_current_dir(repository)
+            .with_context(|| format!("failed to open repository {:?}", repository))?;
+    }
+
+    if let Err(err) = check_settings_values(&cli.command) {
+        err.exit();
+    }
+
     init_logs(cli.verbose, cli.quiet);
+    reporter::set_reporter(ProgressReporter::new(cli.quiet));
+    let output_format = cli.output_format.unwrap_or_else(OutputFormat::detect);
+    // Hooks must not write into the JSON document printed to stdout
+    hook::redirect_stdout_to_stderr(output_format == OutputFormat::Json);
 
     match cli.command {
         Command::Bump {
@@ -230,11 +585,53 @@ fn main() -> Result<()> {
             major,
             minor,
             patch,
+            stabilize,
+            acknowledge_breaking_changes,
             pre,
             hook_profile,
+            profile,
             dry_run,
+            show_hooks,
+            no_gpg_sign,
+            author,
+            signoff,
+            allow_empty,
+            force_tag,
+            ignore_branch_protection,
+            changelog_path,
         } => {
             let mut cocogitto = CocoGitto::get()?;
+            if let Some(profile) = profile {
+                cocogitto = cocogitto.with_profile(&profile)?;
+            }
+
+            if let Some(author) = author {
+                cocogitto = cocogitto.with_author(author);
+            }
+
+            if signoff {
+                cocogitto = cocogitto.with_sign_off();
+            }
+
+            if allow_empty {
+                cocogitto = cocogitto.with_allow_empty();
+            }
+
+            if force_tag {
+                cocogitto = cocogitto.with_force_tag();
+            }
+
+            if ignore_branch_protection {
+                cocogitto = cocogitto.with_ignore_branch_protection();
+            }
+
+            if let Some(path) = changelog_path {
+                cocogitto = cocogitto.with_changelog_path(path);
+            }
+
+            if acknowledge_breaking_changes {
+                cocogitto = cocogitto.with_acknowledged_breaking_changes();
+            }
 
             let increment = match version {
                 Some(version) => VersionIncrement::Manual(version),
@@ -242,35 +639,180 @@ fn main() -> Result<()> {
                 None if major => VersionIncrement::Major,
                 None if minor => VersionIncrement::Minor,
                 None if patch => VersionIncrement::Patch,
+                None if stabilize => VersionIncrement::Stabilize,
                 _ => unreachable!(),
             };
 
-            cocogitto.create_version(increment, pre.as_deref(), hook_profile.as_deref(), dry_run)?
+            let sign = no_gpg_sign.then_some(false);
+            if show_hooks {
+                let hooks = cocogitto.render_bump_hooks(
+                    increment.clone(),
+                    pre.as_deref(),
+                    hook_profile.as_deref(),
+                )?;
+
+                let outcome = cocogitto.create_version(
+                    increment,
+                    pre.as_deref(),
+                    hook_profile.as_deref(),
+                    true,
+                    sign,
+                )?;
+                if output_format == OutputFormat::Json {
+                    let hooks: Vec<_> = hooks
+                        .iter()
+                        .map(|(hook_type, command)| {
+                            serde_json::json!({
+                                "type": hook_type.to_string(),
+                                "command": command,
+                            })
+                        })
+                        .collect();
+
+                    let mut report = serde_json::to_value(&outcome)?;
+                    report["hooks"] = hooks.into();
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    println!("{}", outcome.new_tag);
+                    for (hook_type, command) in hooks {
+                        println!("{}: {}", hook_type, command);
+                    }
+                }
+            } else {
+                let outcome = cocogitto.create_version(
+                    increment,
+                    pre.as_deref(),
+                    hook_profile.as_deref(),
+                    dry_run,
+                    sign,
+                )?;
+
+                match output_format {
+                    OutputFormat::Json => {
+                        println!("{}", serde_json::to_string_pretty(&outcome)?)
+                    }
+                    OutputFormat::Github => github::write_bump_outputs(&outcome)?,
+                    OutputFormat::Text if dry_run => print!("{}", outcome.new_tag),
+                    OutputFormat::Text => {}
+                }
+            }
         }
         Command::Verify {
             message,
+            file,
             ignore_merge_commits,
         } => {
+            let message = match file {
+                Some(path) => std::fs::read_to_string(&path)
+                    .with_context(|| format!("failed to read commit message from {:?}", path))?,
+                None => message.expect("commit message is required"),
+            };
             let ignore_merge_commits = ignore_merge_commits || SETTINGS.ignore_merge_commits;
             let author = CocoGitto::get()
                 .map(|cogito| cogito.get_committer().unwrap())
                 .ok();
+            let linter = Linter::new(&SETTINGS)?;
+
+            if let Err(err) = conv_commit::verify(
+                author,
+                &message,
+                ignore_merge_commits,
+                &COMMIT_TYPES,
+                &linter,
+            ) {
+                if let Some(diagnostic) = diagnose(&message, &COMMIT_TYPES) {
+                    match output_format {
+                        OutputFormat::Json => {
+                            println!("{}", serde_json::to_string_pretty(&diagnostic)?)
+                        }
+                        _ => eprintln!("{}\n", diagnostic.snippet(&message)),
+                    }
+                }
 
-            conv_commit::verify(author, &message, ignore_merge_commits)?;
+                return Err(err.into());
+            }
         }
         Command::Check {
             from_latest_tag,
             ignore_merge_commits,
+            output,
+            staged,
+            file,
         } => {
             let cocogitto = CocoGitto::get()?;
-            let from_latest_tag = from_latest_tag || SETTINGS.from_latest_tag;
-            let ignore_merge_commits = ignore_merge_commits || SETTINGS.ignore_merge_commits;
-            cocogitto.check(from_latest_tag, ignore_merge_commits)?;
+            let settings = cocogitto.settings();
+            let from_latest_tag = from_latest_tag || settings.from_latest_tag;
+            let ignore_merge_commits = ignore_merge_commits || settings.ignore_merge_commits;
+
+            if staged {
+                cocogitto.check_staged(file.as_deref(), ignore_merge_commits)?;
+                return Ok(());
+            }
+
+            let (commits, report) =
+                cocogitto.check_commits(from_latest_tag, ignore_merge_commits)?;
+            if let Some(format) = output {
+                println!("{}", format.render(&commits).trim_end());
+            } else if output_format == OutputFormat::Json {
+                let count = |severity| {
+                    commits
+                        .iter()
+                        .filter(|commit| commit.severity == Some(severity))
+                        .count()
+                };
+                let report = serde_json::json!({
+                    "commits": commits,
+                    "errors": count(Severity::Error),
+                    "warnings": count(Severity::Warning),
+                });
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else if output_format == OutputFormat::Github {
+                print!("{}", github::annotations(&report));
+            }
+
+            CocoGitto::check_report(report)?;
         }
-        Command::Edit { from_latest_tag } => {
+        Command::Edit {
+            from_latest_tag,
+            pattern,
+            sign,
+            no_gpg_sign,
+        } => {
             let cocogitto = CocoGitto::get()?;
-            let from_latest_tag = from_latest_tag || SETTINGS.from_latest_tag;
-            cocogitto.check_and_edit(from_latest_tag)?;
+            let from_latest_tag = from_latest_tag || cocogitto.settings().from_latest_tag;
+            let range = match pattern {
+                Some(pattern) => Some(RevspecPattern::from(pattern.as_str())),
+                None if from_latest_tag => Some(RevspecPattern::default()),
+                None => None,
+            };
+            let sign = match (sign, no_gpg_sign) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            cocogitto.check_and_edit(range.as_ref(), sign)?;
+        }
+        Command::ImportHistory {
+            pattern,
+            dry_run,
+            sign,
+            no_gpg_sign,
+        } => {
+            let cocogitto = CocoGitto::get()?;
+            let range = pattern.as_deref().map(RevspecPattern::from);
+            let sign = match (sign, no_gpg_sign) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            let rewritten = cocogitto.import_history(range.as_ref(), dry_run, sign)?;
+            if dry_run {
+                for commit in rewritten {
+                    let summary = commit.original.lines().next().unwrap_or_default();
+                    let new_summary = commit.message.lines().next().unwrap_or_default();
+                    println!("{} {}\n\t-> {}", &commit.oid[0..7], summary, new_summary);
+                }
+            }
         }
         Command::Log {
             breaking_change,
@@ -278,16 +820,26 @@ fn main() -> Result<()> {
             author,
             scope,
             no_error,
+            pattern,
+            format,
+            max_count,
         } => {
             let cocogitto = CocoGitto::get()?;
 
             let repo_tag_name = cocogitto.get_repo_tag_name();
             let repo_tag_name = repo_tag_name.as_deref().unwrap_or("cog log");
 
-            let mut output = Output::builder()
-                .with_pager_from_env("PAGER")
-                .with_file_name(repo_tag_name)
-                .build()?;
+            // JSON lines are meant for pipes, not for a pager
+            let (format, mut output) = match output_format {
+                OutputFormat::Json => (LogFormat::Json, Output::stdout()),
+                _ => (
+                    format,
+                    Output::builder()
+                        .with_pager_from_env("PAGER")
+                        .with_file_name(repo_tag_name)
+                        .build()?,
+                ),
+            };
 
             let mut filters = vec![];
             if let Some(commit_types) = typ {
@@ -315,13 +867,140 @@ fn main() -> Result<()> {
             }
 
             let filters = CommitFilters(filters);
+            let options = LogOptions {
+                pattern: pattern.as_deref().map(RevspecPattern::from),
+                format,
+                max_count,
+            };
 
-            let content = cocogitto.get_log(filters)?;
-            output
-                .handle()?
-                .write_all(content.as_bytes())
+            cocogitto
+                .write_log(output.handle()?, &filters, &options)
                 .context("failed to write log into the pager")?;
         }
+        Command::Publish { dry_run } => {
+            let cocogitto = CocoGitto::get()?;
+            let outputs = cocogitto.publish(dry_run)?;
+            if output_format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&outputs)?);
+            }
+        }
+        Command::GetVersion {
+            next,
+            snapshot,
+            no_prefix,
+        } => {
+            let cocogitto = CocoGitto::get()?;
+            let version = if next {
+                cocogitto.next_auto_version()?
+            } else if snapshot {
+                cocogitto.snapshot_version()?
+            } else {
+                cocogitto.current_version()?
+            };
+
+            if output_format == OutputFormat::Json {
+                let version = serde_json::json!({
+                    "version": version.to_string(),
+                    "tag": cocogitto.prefixed_version(&version),
+                });
+                println!("{}", serde_json::to_string_pretty(&version)?);
+            } else if no_prefix {
+                println!("{}", version);
+            } else {
+                println!("{}", cocogitto.prefixed_version(&version));
+            }
+        }
+        Command::Stats { pattern, json } => {
+            let cocogitto = CocoGitto::get()?;
+            let pattern = pattern.as_deref().map(RevspecPattern::from);
+            let stats = cocogitto.stats(pattern.as_ref())?;
+
+            if json || output_format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+            } else {
+                print!("{}", stats);
+            }
+        }
+        Command::Config { command } => {
+            // The schema describes any settings, it does not need a repository
+            let settings = || CocoGitto::get().map(|cocogitto| cocogitto.settings().clone());
+
+            match command {
+                ConfigCommand::Show { json } if json || output_format == OutputFormat::Json => {
+                    println!("{}", serde_json::to_string_pretty(&settings()?)?)
+                }
+                ConfigCommand::Show { .. } => {
+                    print!("{}", toml::to_string(&toml::Value::try_from(settings()?)?)?)
+                }
+                ConfigCommand::Validate => {
+                    let problems = settings()?.validate();
+                    if !problems.is_empty() {
+                        for problem in &problems {
+                            eprintln!("{}", problem);
+                        }
+
+                        bail!("found {} invalid setting(s)", problems.len());
+                    }
+
+                    println!("No errors found");
+                }
+                ConfigCommand::Get { key } => match settings()?.value_at(&key) {
+                    Some(serde_json::Value::String(value)) => println!("{}", value),
+                    Some(value) => println!("{}", value),
+                    None => bail!("unknown setting '{}'", key),
+                },
+                ConfigCommand::Schema => {
+                    println!("{}", serde_json::to_string_pretty(&Settings::schema())?)
+                }
+            }
+        }
+        Command::Tag {
+            command,
+            backfill,
+            manifest,
+            dry_run,
+        } => {
+            let cocogitto = CocoGitto::get()?;
+            match command {
+                None if !backfill => bail!("expected a subcommand or --backfill"),
+                None => {
+                    let tags = cocogitto.backfill_tags(manifest.as_deref(), dry_run)?;
+                    if output_format == OutputFormat::Json {
+                        println!("{}", serde_json::to_string_pretty(&tags)?);
+                    } else {
+                        for backfilled in tags {
+                            println!("{} {}", backfilled.tag, backfilled.oid);
+                        }
+                    }
+                }
+                Some(TagCommand::List { prefix }) => {
+                    let tags = cocogitto.list_tags(prefix.as_deref())?;
+                    if output_format == OutputFormat::Json {
+                        println!("{}", serde_json::to_string_pretty(&tags)?);
+                    } else {
+                        for tag in tags {
+                            println!("{}", tag);
+                        }
+                    }
+                }
+                Some(TagCommand::Latest) => println!("{}", cocogitto.latest_tag()?),
+                Some(TagCommand::Create {
+                    version,
+                    message,
+                    no_gpg_sign,
+                    force_tag,
+                }) => {
+                    let sign = no_gpg_sign.then_some(false);
+                    let tag = cocogitto.create_version_tag(
+                        &version,
+                        message.as_deref(),
+                        sign,
+                        force_tag,
+                    )?;
+                    println!("{}", tag);
+                }
+            }
+        }
         Command::Changelog {
             pattern,
             at,
@@ -329,31 +1008,73 @@ fn main() -> Result<()> {
             remote,
             owner,
             repository,
+            paths,
         } => {
             let cocogitto = CocoGitto::get()?;
 
+            let settings = cocogitto.settings();
             let context = RemoteContext::try_new(remote, repository, owner)
-                .or_else(|| SETTINGS.get_template_context());
-            let template = template.as_ref().or(SETTINGS.changelog.template.as_ref());
+                .or_else(|| settings.get_template_context());
+            let template = template.as_ref().or(settings.changelog.template.as_ref());
             let template = if let Some(template) = template {
                 Template::from_arg(template, context)?
             } else {
                 Template::default()
             };
+            let template = template.with_repository(cocogitto.repository_context()?);
 
             let pattern = pattern.as_deref().map(RevspecPattern::from);
 
-            let result = match at {
-                Some(at) => cocogitto.get_changelog_at_tag(&at, template)?,
-                None => {
-                    let changelog = cocogitto.get_changelog(pattern.unwrap_or_default(), true)?;
-                    changelog.into_markdown(template)?
-                }
+            let mut release = match at {
+                Some(at) => cocogitto
+                    .get_changelog(RevspecPattern::from(format!("..{}", at).as_str()), false)?,
+                None => cocogitto.get_changelog(pattern.unwrap_or_default(), true)?,
+            };
+
+            if !paths.is_empty() {
+                cocogitto.retain_paths(&mut release, &paths)?;
+            }
+
+            if output_format == OutputFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&release)?);
+                return Ok(());
+            }
+
+            println!("{}", release.into_markdown(template)?);
+        }
+        Command::ReleaseNotes {
+            tag,
+            from_changelog,
+            template,
+        } => {
+            let cocogitto = CocoGitto::get()?;
+            let settings = cocogitto.settings();
+            let template = match template {
+                Some(template) => Template::from_arg(&template, settings.get_template_context())?,
+                None => settings.get_changelog_template()?,
             };
-            println!("{}", result);
+            let template = template.with_repository(cocogitto.repository_context()?);
+
+            let notes = cocogitto.get_release_notes(&tag, template, from_changelog)?;
+            println!("{}", notes);
         }
-        Command::Init { path } => {
-            cocogitto::init(&path)?;
+        Command::Init {
+            path,
+            template,
+            from,
+            interactive,
+        } => {
+            let options = if interactive {
+                init::prompt_options(template, from)?
+            } else {
+                InitOptions {
+                    preset: template,
+                    from,
+                    ..Default::default()
+                }
+            };
+
+            cocogitto::init_with_options(&path, &options)?;
         }
         Command::InstallHook { hook_type } => {
             let cocogitto = CocoGitto::get()?;
@@ -364,8 +1085,20 @@ fn main() -> Result<()> {
                 _ => unreachable!(),
             }
         }
+        Command::GenerateHookConfig { framework } => {
+            let framework = match framework.as_str() {
+                "pre-commit" => HookFramework::PreCommit,
+                "husky" => HookFramework::Husky,
+                _ => unreachable!(),
+            };
+            print!("{}", framework.config());
+        }
+        Command::Serve => serve::serve()?,
         Command::GenerateCompletions { shell } => {
             clap_complete::generate(shell, &mut Cli::command(), "cog", &mut std::io::stdout());
+            if let Some(script) = completion::dynamic_script(shell) {
+                print!("{}", script);
+            }
         }
         Command::GenerateManpage { cmd } => {
             let cog_cmd = Cli::command();
@@ -386,14 +1119,47 @@ fn main() -> Result<()> {
             breaking_change,
             edit,
             sign,
+            no_gpg_sign,
+            author,
+            signoff,
+            allow_empty,
+            ignore_branch_protection,
         }) => {
-            let cocogitto = CocoGitto::get()?;
+            let mut cocogitto = CocoGitto::get()?;
+            if let Some(author) = author {
+                cocogitto = cocogitto.with_author(author);
+            }
+
+            if signoff {
+                cocogitto = cocogitto.with_sign_off();
+            }
+
+            if allow_empty {
+                cocogitto = cocogitto.with_allow_empty();
+            }
+
+            if ignore_branch_protection {
+                cocogitto = cocogitto.with_ignore_branch_protection();
+            }
+
             let (body, footer, breaking) = if edit {
-                commit::edit_message(&typ, &message, scope.as_deref(), breaking_change)?
+                commit::edit_message(
+                    &cocogitto,
+                    &typ,
+                    &message,
+                    scope.as_deref(),
+                    breaking_change,
+                )?
             } else {
                 (None, None, breaking_change)
             };
 
+            let sign = match (sign, no_gpg_sign) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+
             cocogitto.conventional_commit(&typ, scope, message, body, footer, breaking, sign)?;
         }
     }
@@ -401,8 +1167,57 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+// The values allowed by the repository settings, checked once in the repository
+fn check_settings_values(command: &Command) -> Result<(), clap::Error> {
+    match command {
+        Command::Bump {
+            hook_profile,
+            profile,
+            ..
+        } => {
+            if let Some(hook_profile) = hook_profile {
+                let profiles = SETTINGS.bump_profiles.keys().map(String::as_str);
+                check_possible_value("--hook-profile <HOOK_PROFILE>", hook_profile, profiles)?;
+            }
+
+            if let Some(profile) = profile {
+                let profiles = SETTINGS.profile.keys().map(String::as_str);
+                check_possible_value("--profile <PROFILE>", profile, profiles)?;
+            }
+        }
+        Command::Commit(args) => {
+            let types = COMMIT_TYPES.keys().map(AsRef::as_ref);
+            check_possible_value("<TYPE>", &args.typ, types)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// Reject a value like a clap possible values parser would
+fn check_possible_value<'a>(
+    arg: &str,
+    value: &str,
+    possible_values: impl Iterator<Item = &'a str>,
+) -> Result<(), clap::Error> {
+    let possible_values: Vec<_> = possible_values.sorted().collect();
+    if possible_values.contains(&value) {
+        return Ok(());
+    }
+
+    let message = format!(
+        "invalid value '{}' for '{}'\n  [possible values: {}]",
+        value,
+        arg,
+        possible_values.join(", ")
+    );
+    Err(Cli::command().error(ErrorKind::InvalidValue, message))
+}
+
 fn init_logs(verbose: u8, quiet: bool) {
-    let verbosity = if verbose == 0 { 2 } else { verbose - 1 };
+    // Errors, warnings and info messages are shown by default
+    let verbosity = 2 + verbose;
     stderrlog::new()
         .module(module_path!())
         .modules(vec!["cocogitto"])
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use cocogitto::reporter::{self, Level, LogReporter, Reporter, Task};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Logs messages and draws a progress bar on stderr for each running task.
+/// Bars are hidden when stderr is not a terminal.
+pub struct ProgressReporter {
+    quiet: bool,
+    bars: Mutex<HashMap<Task, ProgressBar>>,
+}
+
+impl ProgressReporter {
+    pub fn new(quiet: bool) -> Self {
+        ProgressReporter {
+            quiet,
+            bars: Mutex::default(),
+        }
+    }
+
+    fn bar(task: Task, total: Option<u64>) -> ProgressBar {
+        let (bar, template) = match total {
+            Some(total) => (
+                ProgressBar::new(total),
+                "{msg} [{bar:40}] {pos}/{len} ({eta})",
+            ),
+            None => (ProgressBar::new_spinner(), "{spinner} {msg} {pos}"),
+        };
+
+        let style = ProgressStyle::with_template(template)
+            .expect("progress template is valid")
+            .progress_chars("=> ");
+        bar.set_style(style);
+        bar.set_message(task.to_string());
+        bar
+    }
+}
+
+impl Reporter for ProgressReporter {
+    fn report(&self, level: Level, message: &str) {
+        let bars = self.bars.lock().unwrap_or_else(|err| err.into_inner());
+        // Keep messages above the bars instead of drawing them over
+        match bars.values().next() {
+            Some(bar) => bar.suspend(|| LogReporter.report(level, message)),
+            None => LogReporter.report(level, message),
+        }
+    }
+
+    fn progress(&self, task: Task, done: u64, total: Option<u64>) {
+        if self.quiet {
+            return;
+        }
+
+        let mut bars = self.bars.lock().unwrap_or_else(|err| err.into_inner());
+        bars.entry(task)
+            .or_insert_with(|| Self::bar(task, total))
+            .set_position(done);
+    }
+
+    fn finish(&self, task: Task) {
+        let mut bars = self.bars.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(bar) = bars.remove(&task) {
+            bar.finish_and_clear();
+        }
+    }
+
+    fn colors(&self) -> bool {
+        reporter::detect_colors()
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::{self, BufRead, Write};
+
+use cocogitto::conventional::diagnostic::diagnose;
+use cocogitto::CocoGitto;
+
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::COMMIT_TYPES;
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+// Scopes suggested when the request does not set a limit
+const DEFAULT_SCOPE_HISTORY: usize = 500;
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl ToString) -> Self {
+        RpcError {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Answer JSON-RPC requests read from stdin, one per line, until stdin is closed
+/// or an `exit` request is received. Responses are written to stdout, one per line.
+pub fn serve() -> Result<()> {
+    let cocogitto = CocoGitto::get().ok();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout().lock();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (id, result) = match serde_json::from_str::<Request>(&line) {
+            Ok(request) if request.method == "exit" => {
+                write_response(&mut stdout, request.id, Ok(Value::Null))?;
+                break;
+            }
+            Ok(request) => {
+                let result = handle(cocogitto.as_ref(), &request.method, &request.params);
+                (request.id, result)
+            }
+            Err(err) => (Value::Null, Err(RpcError::new(PARSE_ERROR, err))),
+        };
+
+        write_response(&mut stdout, id, result)?;
+    }
+
+    Ok(())
+}
+
+fn handle(cocogitto: Option<&CocoGitto>, method: &str, params: &Value) -> Result<Value, RpcError> {
+    let repository =
+        || cocogitto.ok_or_else(|| RpcError::new(INTERNAL_ERROR, "not in a git repository"));
+
+    match method {
+        "verify" => {
+            let message = string_param(params, "message")?;
+            Ok(json!(diagnose(message, &COMMIT_TYPES)))
+        }
+        "types" => {
+            let mut types: Vec<&str> = COMMIT_TYPES.keys().map(|typ| typ.as_ref()).collect();
+            types.sort_unstable();
+            Ok(json!(types))
+        }
+        "scopes" => {
+            let limit = params
+                .get("limit")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_SCOPE_HISTORY, |limit| limit as usize);
+            let scopes = repository()?
+                .recent_scopes(limit)
+                .map_err(|err| RpcError::new(INTERNAL_ERROR, err))?;
+            Ok(json!(scopes))
+        }
+        "preview" => {
+            let message = string_param(params, "message")?;
+            let entry = repository()?
+                .preview_changelog_entry(message)
+                .map_err(|err| RpcError::new(INVALID_PARAMS, err.to_string().trim()))?;
+            Ok(json!(entry))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method '{}'", method),
+        )),
+    }
+}
+
+fn string_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("missing string param '{}'", name)))
+}
+
+fn write_response(out: &mut impl Write, id: Value, result: Result<Value, RpcError>) -> Result<()> {
+    let response = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": err.code, "message": err.message },
+        }),
+    };
+
+    writeln!(out, "{}", response)?;
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::reporter::warn;
+use chrono::NaiveDateTime;
+use conventional_commit_parser::commit::{CommitType, ConventionalCommit, Footer, Separator};
+use git2::Commit as Git2Commit;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::conventional::commit::Commit;
+use crate::conventional::error::ConventionalCommitError;
+use crate::CommitsMetadata;
+
+const CACHE_DIR: &str = "cog-cache";
+const CACHE_FILE: &str = "commits.json";
+
+/// Conventional commits parsed in previous runs, keyed by commit oid and persisted in the git
+/// directory. Only compliant commits are cached: non compliant ones are parsed again to report
+/// their errors. The whole cache is discarded when the allowed commit types change.
+#[derive(Debug)]
+pub(crate) struct CommitCache {
+    path: PathBuf,
+    commit_types: String,
+    commits: RefCell<HashMap<String, CachedCommit>>,
+    dirty: Cell<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CacheFile {
+    commit_types: String,
+    commits: HashMap<String, CachedCommit>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct CachedCommit {
+    commit_type: String,
+    scope: Option<String>,
+    summary: String,
+    body: Option<String>,
+    footers: Vec<CachedFooter>,
+    breaking_change: bool,
+    author: String,
+    date: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct CachedFooter {
+    token: String,
+    content: String,
+    separator: String,
+}
+
+impl CommitCache {
+    pub(crate) fn load(git_dir: &Path, allowed_commit_types: &CommitsMetadata) -> Self {
+        let path = git_dir.join(CACHE_DIR).join(CACHE_FILE);
+        let commit_types = allowed_commit_types
+            .iter()
+            .map(|(commit_type, config)| match &config.alias_of {
+                Some(canonical) => format!("{}={}", commit_type.as_ref(), canonical.as_ref()),
+                None => commit_type.as_ref().to_string(),
+            })
+            .sorted()
+            .join(",");
+
+        let commits = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|cache| cache.commit_types == commit_types)
+            .map(|cache| cache.commits)
+            .unwrap_or_default();
+
+        CommitCache {
+            path,
+            commit_types,
+            commits: RefCell::new(commits),
+            dirty: Cell::new(false),
+        }
+    }
+
+    /// Parse a git commit, reusing the result of a previous run when available
+    pub(crate) fn parse(
+        &self,
+        commit: &Git2Commit,
+        allowed_commit_types: &CommitsMetadata,
+    ) -> Result<Commit, Box<ConventionalCommitError>> {
+        let oid = commit.id().to_string();
+        if let Some(cached) = self.commits.borrow().get(&oid) {
+            return Ok(cached.clone().into_commit(oid));
+        }
+
+        let parsed = Commit::from_git_commit(commit, allowed_commit_types)?;
+        self.commits
+            .borrow_mut()
+            .insert(oid, CachedCommit::from(&parsed));
+        self.dirty.set(true);
+
+        Ok(parsed)
+    }
+
+    pub(crate) fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let cache = CacheFile {
+            commit_types: self.commit_types.clone(),
+            commits: self.commits.borrow().clone(),
+        };
+
+        fs::write(&self.path, serde_json::to_string(&cache)?)
+    }
+}
+
+impl Drop for CommitCache {
+    fn drop(&mut self) {
+        if self.dirty.get() {
+            if let Err(err) = self.save() {
+                warn!("Failed to write the commit cache {:?}: {}", self.path, err);
+            }
+        }
+    }
+}
+
+/// Parse a git commit through the cache when there is one
+pub(crate) fn parse_commit(
+    cache: Option<&CommitCache>,
+    commit: &Git2Commit,
+    allowed_commit_types: &CommitsMetadata,
+) -> Result<Commit, Box<ConventionalCommitError>> {
+    match cache {
+        Some(cache) => cache.parse(commit, allowed_commit_types),
+        None => Commit::from_git_commit(commit, allowed_commit_types),
+    }
+}
+
+impl From<&Commit> for CachedCommit {
+    fn from(commit: &Commit) -> Self {
+        let message = &commit.message;
+        CachedCommit {
+            commit_type: message.commit_type.to_string(),
+            scope: message.scope.clone(),
+            summary: message.summary.clone(),
+            body: message.body.clone(),
+            footers: message
+                .footers
+                .iter()
+                .map(|footer| CachedFooter {
+                    token: footer.token.clone(),
+                    content: footer.content.clone(),
+                    separator: match footer.token_separator {
+                        Separator::Colon => ": ",
+                        Separator::ColonWithNewLine => ":\n",
+                        Separator::Hash => " #",
+                    }
+                    .to_string(),
+                })
+                .collect(),
+            breaking_change: message.is_breaking_change,
+            author: commit.author.clone(),
+            date: commit.date,
+        }
+    }
+}
+
+impl CachedCommit {
+    fn into_commit(self, oid: String) -> Commit {
+        let footers = self
+            .footers
+            .into_iter()
+            .map(|footer| Footer {
+                token: footer.token,
+                content: footer.content,
+                token_separator: match footer.separator.as_str() {
+                    " #" => Separator::Hash,
+                    ":\n" => Separator::ColonWithNewLine,
+                    _ => Separator::Colon,
+                },
+            })
+            .collect();
+
+        Commit {
+            oid,
+            message: ConventionalCommit {
+                commit_type: CommitType::from(self.commit_type.as_str()),
+                scope: self.scope,
+                summary: self.summary,
+                body: self.body,
+                footers,
+                is_breaking_change: self.breaking_change,
+            },
+            author: self.author,
+            date: self.date,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::cache::CommitCache;
+    use crate::git::repository::Repository;
+    use crate::settings::Settings;
+    use anyhow::Result;
+    use cmd_lib::run_fun;
+    use git2::Oid;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    #[sealed_test]
+    fn reuse_cached_commits() -> Result<()> {
+        // Arrange
+        let repository = Repository::init(".")?;
+        let oid = run_fun!(
+            git commit --allow-empty -q -m "feat(cache): cached commit" -m "Refs #42";
+            git log --format=%H -n 1;
+        )?;
+        let commit = repository.0.find_commit(Oid::from_str(&oid)?)?;
+        let commit_types = Settings::default().commit_types();
+
+        let cache = CommitCache::load(repository.0.path(), &commit_types);
+        let parsed = cache.parse(&commit, &commit_types)?;
+        drop(cache);
+
+        // Act
+        let cache = CommitCache::load(repository.0.path(), &commit_types);
+
+        // Assert
+        assert_that!(cache.commits.borrow().contains_key(&oid)).is_true();
+        assert_that!(cache.parse(&commit, &commit_types)?).is_equal_to(parsed);
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn discard_cache_when_commit_types_change() -> Result<()> {
+        // Arrange
+        let repository = Repository::init(".")?;
+        let oid = run_fun!(
+            git commit --allow-empty -q -m "feat: cached commit";
+            git log --format=%H -n 1;
+        )?;
+        let commit = repository.0.find_commit(Oid::from_str(&oid)?)?;
+        let commit_types = Settings::default().commit_types();
+        CommitCache::load(repository.0.path(), &commit_types).parse(&commit, &commit_types)?;
+
+        let mut custom_types = commit_types.clone();
+        custom_types.insert("wip".into(), crate::CommitConfig::new("WIP"));
+
+        // Act
+        let cache = CommitCache::load(repository.0.path(), &custom_types);
+
+        // Assert
+        assert_that!(cache.commits.borrow().is_empty()).is_true();
+        Ok(())
+    }
+}
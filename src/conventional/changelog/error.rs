@@ -3,12 +3,18 @@ use std::fmt::{Debug, Display, Formatter};
 use std::io;
 use std::path::PathBuf;
 
+use crate::conventional::changelog::overrides::OVERRIDES_PATH;
+
 #[derive(Debug)]
 pub enum ChangelogError {
     TemplateNotFound(PathBuf),
     TeraError(tera::Error),
     WriteError(io::Error),
     SeparatorNotFound(PathBuf),
+    ReadError(PathBuf, io::Error),
+    ReleaseNotFound(String, PathBuf),
+    InvalidOverrides(toml::de::Error),
+    InvalidRecord(String),
 }
 
 impl Display for ChangelogError {
@@ -28,6 +34,18 @@ impl Display for ChangelogError {
                 "cannot find default separator '- - -' in {}",
                 path.as_path().display()
             ),
+            ChangelogError::ReadError(path, err) => {
+                writeln!(f, "failed to read {}: \n\t{}", path.display(), err)
+            }
+            ChangelogError::ReleaseNotFound(tag, path) => {
+                writeln!(f, "no release {} found in {}", tag, path.display())
+            }
+            ChangelogError::InvalidOverrides(err) => {
+                writeln!(f, "invalid {}: \n\t{}", OVERRIDES_PATH, err)
+            }
+            ChangelogError::InvalidRecord(cause) => {
+                writeln!(f, "invalid commit record: \n\t{}", cause)
+            }
         }
     }
 }
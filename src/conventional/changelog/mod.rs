@@ -7,7 +7,8 @@ use std::fs;
 use std::path::Path;
 
 pub mod error;
-pub(crate) mod release;
+pub mod overrides;
+pub mod release;
 pub(crate) mod renderer;
 pub(crate) mod serde;
 pub mod template;
@@ -34,26 +35,147 @@ impl Release<'_> {
     ) -> Result<(), ChangelogError> {
         let renderer = Renderer::try_new(template)?;
         let changelog = renderer.render(self)?;
+        write_markdown(path, &changelog)
+    }
+}
+
+/// The notes of release `tag` in a written changelog, between its heading
+/// and the next separator or heading of the same level
+pub(crate) fn extract_release_notes(changelog: &str, tag: &str) -> Option<String> {
+    let mut lines = changelog.lines();
+    let level = lines
+        .by_ref()
+        .find_map(|line| release_heading_level(line, tag))?;
+
+    let notes: Vec<&str> = lines
+        .take_while(|line| {
+            line.trim() != CHANGELOG_SEPARATOR
+                && heading_level(line).is_none_or(|other| other > level)
+        })
+        .collect();
+
+    Some(notes.join("\n").trim().to_string())
+}
+
+/// Drop the heading of a rendered release, keeping only its notes
+pub(crate) fn strip_release_heading(release: &str) -> String {
+    let release = release.trim_start();
+    let notes = match release.split_once('\n') {
+        Some((first, notes)) if heading_level(first).is_some() => notes,
+        Some(_) => release,
+        None if heading_level(release).is_some() => "",
+        None => release,
+    };
+
+    notes.trim().to_string()
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|char| *char == '#').count();
+    (level > 0 && line[level..].starts_with(' ')).then_some(level)
+}
+
+// Headings start with the tag, possibly as a link: `## 1.0.0 - date`, `## [1.0.0](url) - date`
+fn release_heading_level(line: &str, tag: &str) -> Option<usize> {
+    let level = heading_level(line)?;
+    let title = line[level..].trim_start();
+    let title = title.strip_prefix('[').unwrap_or(title);
+    let rest = title.strip_prefix(tag)?;
+
+    // `1.0.0` must not match `1.0.0-rc.1` or `1.0.01`
+    match rest.chars().next() {
+        Some(char) if char.is_alphanumeric() || matches!(char, '.' | '-' | '+') => None,
+        _ => Some(level),
+    }
+}
+
+/// Write a changelog without any release, the releases are inserted below its header
+pub(crate) fn write_empty_markdown<S: AsRef<Path>>(path: S) -> Result<(), ChangelogError> {
+    fs::write(path.as_ref(), [DEFAULT_HEADER, DEFAULT_FOOTER].join("")).map_err(Into::into)
+}
+
+/// Insert an already rendered release at the top of the changelog file
+pub(crate) fn write_markdown<S: AsRef<Path>>(
+    path: S,
+    changelog: &str,
+) -> Result<(), ChangelogError> {
+    let mut changelog_content = fs::read_to_string(path.as_ref())
+        .unwrap_or_else(|_| [DEFAULT_HEADER, DEFAULT_FOOTER].join(""));
+
+    let separator_idx = changelog_content.find(CHANGELOG_SEPARATOR);
+
+    if let Some(idx) = separator_idx {
+        changelog_content.insert(idx + CHANGELOG_SEPARATOR.len(), '\n');
+        changelog_content.insert_str(idx + CHANGELOG_SEPARATOR.len() + 1, changelog);
+        changelog_content.insert_str(
+            idx + CHANGELOG_SEPARATOR.len() + 1 + changelog.len(),
+            "\n- - -\n",
+        );
 
-        let mut changelog_content = fs::read_to_string(path.as_ref())
-            .unwrap_or_else(|_| [DEFAULT_HEADER, DEFAULT_FOOTER].join(""));
-
-        let separator_idx = changelog_content.find(CHANGELOG_SEPARATOR);
-
-        if let Some(idx) = separator_idx {
-            changelog_content.insert(idx + CHANGELOG_SEPARATOR.len(), '\n');
-            changelog_content.insert_str(idx + CHANGELOG_SEPARATOR.len() + 1, &changelog);
-            changelog_content.insert_str(
-                idx + CHANGELOG_SEPARATOR.len() + 1 + changelog.len(),
-                "\n- - -\n",
-            );
-            fs::write(path.as_ref(), changelog_content)?;
-
-            Ok(())
-        } else {
-            Err(ChangelogError::SeparatorNotFound(
-                path.as_ref().to_path_buf(),
-            ))
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
         }
+
+        fs::write(path.as_ref(), changelog_content)?;
+
+        Ok(())
+    } else {
+        Err(ChangelogError::SeparatorNotFound(
+            path.as_ref().to_path_buf(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    use crate::conventional::changelog::{extract_release_notes, strip_release_heading};
+
+    const CHANGELOG: &str = indoc! {"
+        # Changelog
+        All notable changes to this project will be documented in this file.
+
+        - - -
+        ## [1.1.0](https://github.com/cocogitto/cocogitto/compare/1.0.0..1.1.0) - 2023-01-02
+        #### Features
+        - a feature - (17f7e23) - Tom
+
+        - - -
+
+        ## 1.1.0-rc.1 - 2023-01-01
+        #### Bug Fixes
+        - a pre-release fix - (fae3a28) - Tom
+
+        - - -
+
+        Changelog generated by [cocogitto](https://github.com/cocogitto/cocogitto).
+    "};
+
+    #[test]
+    fn extract_release_notes_between_separators() {
+        let notes = extract_release_notes(CHANGELOG, "1.1.0");
+
+        assert_that!(notes)
+            .is_some()
+            .is_equal_to("#### Features\n- a feature - (17f7e23) - Tom".to_string());
+    }
+
+    #[test]
+    fn extract_release_notes_does_not_match_pre_releases() {
+        let notes = extract_release_notes(CHANGELOG, "1.1.0-rc.1");
+
+        assert_that!(notes)
+            .is_some()
+            .is_equal_to("#### Bug Fixes\n- a pre-release fix - (fae3a28) - Tom".to_string());
+        assert_that!(extract_release_notes(CHANGELOG, "1.1")).is_none();
+    }
+
+    #[test]
+    fn strip_heading_of_rendered_release() {
+        let notes = strip_release_heading("## 1.1.0 - 2023-01-02\n#### Features\n- a feature\n\n");
+
+        assert_that!(notes).is_equal_to("#### Features\n- a feature".to_string());
     }
 }
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::conventional::changelog::release::Release;
+
+/// Where the changelog overrides are read from, relative to the working tree root
+pub const OVERRIDES_PATH: &str = ".cog/changelog-overrides.toml";
+
+/// Changelog curation of single commits, keyed by commit id or unambiguous id prefix.
+/// Release notes are polished here instead of rewriting the history:
+///
+/// ```toml
+/// [17f7e23]
+/// summary = "parse footers spanning several lines"
+/// notes = ["contributed by the parser working group"]
+///
+/// [9bb5fac]
+/// exclude = true
+/// ```
+#[derive(Debug, Default, Deserialize, Clone, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct ChangelogOverrides(HashMap<String, CommitOverride>);
+
+#[derive(Debug, Default, Deserialize, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct CommitOverride {
+    /// Replaces the commit summary
+    pub summary: Option<String>,
+    /// Appended to the commit notes
+    pub notes: Vec<String>,
+    /// Leave the commit out of the changelog
+    pub exclude: bool,
+}
+
+impl ChangelogOverrides {
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The override of a commit, matched by full id or id prefix
+    pub fn get(&self, oid: &str) -> Option<&CommitOverride> {
+        self.0
+            .iter()
+            .find(|(id, _)| !id.is_empty() && oid.starts_with(id.as_str()))
+            .map(|(_, commit_override)| commit_override)
+    }
+
+    /// Apply the overrides to a release and the previous ones
+    pub fn apply(&self, release: &mut Release) {
+        if self.is_empty() {
+            return;
+        }
+
+        release.retain_commits(|commit| !self.get(&commit.commit.oid).is_some_and(|o| o.exclude));
+        let mut release = Some(release);
+        while let Some(current) = release {
+            for commit in current.commits.iter_mut() {
+                if let Some(commit_override) = self.get(&commit.commit.oid) {
+                    if let Some(summary) = &commit_override.summary {
+                        commit.commit.message.summary = summary.clone();
+                    }
+
+                    commit.notes.extend(commit_override.notes.iter().cloned());
+                }
+            }
+
+            release = current.previous.as_deref_mut();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    use crate::conventional::changelog::overrides::ChangelogOverrides;
+    use crate::conventional::changelog::release::Release;
+
+    #[test]
+    fn should_apply_overrides() -> Result<()> {
+        // Arrange
+        let overrides = ChangelogOverrides::parse(indoc! {
+            r#"
+            [17f7e23]
+            summary = "polished summary"
+            notes = ["a note"]
+            "#
+        })?;
+        let mut release = Release::fixture();
+
+        // Act
+        overrides.apply(&mut release);
+
+        // Assert
+        assert_that!(release
+            .commits
+            .iter()
+            .all(|commit| commit.commit.message.summary == "polished summary"
+                && commit.notes == ["a note"]))
+        .is_true();
+        Ok(())
+    }
+
+    #[test]
+    fn should_exclude_commits() -> Result<()> {
+        // Arrange
+        let overrides = ChangelogOverrides::parse("[17f7e23081db]\nexclude = true")?;
+        let mut release = Release::fixture();
+
+        // Act
+        overrides.apply(&mut release);
+
+        // Assert
+        assert_that!(release.commits).is_empty();
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_fields() {
+        let overrides = ChangelogOverrides::parse("[17f7e23]\nsumary = \"typo\"");
+
+        assert_that!(overrides).is_err();
+    }
+}
@@ -1,21 +1,37 @@
-use chrono::{NaiveDateTime, Utc};
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
 use conventional_commit_parser::commit::Footer;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::conventional::cache::{parse_commit, CommitCache};
+use crate::conventional::changelog::error::ChangelogError;
 use crate::conventional::commit::Commit;
+use crate::conventional::error::ConventionalCommitError;
 use crate::git::oid::OidOf;
 use crate::git::revspec::CommitRange;
-use crate::settings;
+use crate::git::tag::Tag;
+use crate::reporter::{warn, Progress};
+use crate::settings::{ChangelogTrailers, Settings};
+use crate::CommitsMetadata;
 use colored::Colorize;
 use git2::Oid;
-use log::warn;
 
-#[derive(Debug, Serialize)]
+/// The commits of a version, linked to the release before it.
+///
+/// Changelogs returned by [`CocoGitto::get_changelog`](crate::CocoGitto::get_changelog)
+/// are chains of releases, newest first, walked with [`Release::iter`].
+#[derive(Debug, Clone, Serialize)]
 pub struct Release<'a> {
+    /// The release tag, or the last commit of the range when it is not tagged
     pub version: OidOf,
+    /// Where the release starts: the previous version tag or the first commit
     pub from: OidOf,
     pub date: NaiveDateTime,
+    /// The conventional commits of the release, newest first
     pub commits: Vec<ChangelogCommit<'a>>,
+    /// Free text rendered above the commits, e.g. the `changelog.stabilize_note` of a 1.0.0 release
+    pub notes: Option<String>,
     pub previous: Option<Box<Release<'a>>>,
 }
 
@@ -42,6 +58,48 @@ impl Release<'_> {
         }
     }
 
+    /// Keep only the commits matching `predicate`, in this release and the previous ones
+    pub fn retain_commits<F>(&mut self, predicate: F)
+    where
+        F: Fn(&ChangelogCommit) -> bool + Copy,
+    {
+        self.commits.retain(predicate);
+        if let Some(previous) = &mut self.previous {
+            previous.retain_commits(predicate);
+        }
+    }
+
+    /// Mark the commits sharing a `key` with an older commit as its duplicates, in this release and
+    /// the previous ones. Commits without key are never duplicates.
+    pub fn mark_duplicates<F>(&mut self, mut key: F)
+    where
+        F: FnMut(&ChangelogCommit) -> Option<String>,
+    {
+        let mut releases = vec![];
+        let mut release = Some(self);
+        while let Some(current) = release {
+            releases.push(&mut current.commits);
+            release = current.previous.as_deref_mut();
+        }
+
+        // Oldest commits first, so the first occurrence of a change is kept
+        let mut seen: HashMap<String, String> = HashMap::new();
+        for commits in releases.into_iter().rev() {
+            for commit in commits.iter_mut().rev() {
+                let Some(key) = key(commit) else {
+                    continue;
+                };
+
+                match seen.get(&key) {
+                    Some(original) => commit.duplicate_of = Some(original.clone()),
+                    None => {
+                        seen.insert(key, commit.commit.oid.clone());
+                    }
+                }
+            }
+        }
+    }
+
     pub fn contains_oid(&self, oid: &Oid) -> bool {
         self.commits
             .iter()
@@ -49,51 +107,302 @@ impl Release<'_> {
     }
 }
 
-impl<'a> From<CommitRange<'a>> for Release<'a> {
-    fn from(commit_range: CommitRange<'a>) -> Self {
-        let mut commits = vec![];
+impl<'a> Release<'a> {
+    /// The release before this one, when previous releases were requested
+    pub fn previous(&self) -> Option<&Release<'a>> {
+        self.previous.as_deref()
+    }
 
-        for commit in commit_range.commits {
-            // Ignore merge commits
-            if let Some(message) = commit.message() {
-                if message.starts_with("Merge") {
-                    continue;
-                }
-            }
+    /// This release followed by all the previous ones, newest first
+    pub fn iter(&self) -> Releases<'_, 'a> {
+        Releases { next: Some(self) }
+    }
 
-            match Commit::from_git_commit(&commit) {
-                Ok(commit) => commits.push(ChangelogCommit::from(commit)),
-                Err(err) => {
-                    let err = err.to_string().red();
-                    warn!("{}", err);
-                }
-            };
+    /// Build a release from a commit range, commit types and authors are resolved from `settings`
+    pub fn from_commit_range(commit_range: CommitRange<'a>, settings: &'a Settings) -> Self {
+        Self::from_cached_commit_range(commit_range, settings, None)
+    }
+
+    pub(crate) fn from_cached_commit_range(
+        commit_range: CommitRange<'a>,
+        settings: &'a Settings,
+        cache: Option<&CommitCache>,
+    ) -> Self {
+        ReleaseBuilder::new(settings).build(commit_range, cache, None)
+    }
+}
+
+/// A commit exported from another source than a git checkout, e.g. a forge API,
+/// see [`Release::from_records`]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct CommitRecord {
+    pub sha: String,
+    pub message: String,
+    /// The author name, matched against `changelog.authors` signatures
+    pub author: String,
+    pub date: DateTime<Utc>,
+}
+
+impl<'a> Release<'a> {
+    /// Build a release from exported commits, newest first, without a repository.
+    ///
+    /// The release is tagged `version` and starts at the `previous` tag, or at the oldest
+    /// record when `None`. It is dated like its newest commit. Like releases built from a
+    /// repository, merge and non conventional commits are skipped with a warning.
+    pub fn from_records(
+        version: &str,
+        previous: Option<&str>,
+        records: &[CommitRecord],
+        settings: &'a Settings,
+    ) -> Result<Self, ChangelogError> {
+        let (Some(newest), Some(oldest)) = (records.first(), records.last()) else {
+            return Err(ChangelogError::InvalidRecord(
+                "a release needs at least one commit".to_string(),
+            ));
+        };
+
+        let tag = |name: &str, oid: Option<Oid>| {
+            Tag::new(name, oid, None).map_err(|err| ChangelogError::InvalidRecord(err.to_string()))
+        };
+        let from = match previous {
+            Some(previous) => OidOf::Tag(tag(previous, None)?),
+            None => OidOf::Other(record_oid(oldest)?),
+        };
+
+        let builder = ReleaseBuilder::new(settings);
+        let commits = records
+            .iter()
+            .filter(|record| !record.message.starts_with("Merge"))
+            .map(|record| {
+                Commit::parse(
+                    record.sha.clone(),
+                    &record.message,
+                    record.author.clone(),
+                    record.date.naive_utc(),
+                    &builder.commit_types,
+                )
+            });
+
+        Ok(Release {
+            version: OidOf::Tag(tag(version, Some(record_oid(newest)?))?),
+            from,
+            date: newest.date.naive_utc(),
+            commits: builder.changelog_commits(commits),
+            notes: None,
+            previous: None,
+        })
+    }
+}
+
+fn record_oid(record: &CommitRecord) -> Result<Oid, ChangelogError> {
+    Oid::from_str(&record.sha)
+        .map_err(|_| ChangelogError::InvalidRecord(format!("`{}` is not a commit sha", record.sha)))
+}
+
+/// Builds the releases of a changelog. Commit types and author usernames are
+/// resolved once from the settings and shared by every release of the chain.
+pub(crate) struct ReleaseBuilder<'a> {
+    commit_types: CommitsMetadata,
+    usernames: HashMap<&'a str, &'a str>,
+    trailers: &'a ChangelogTrailers,
+}
+
+impl<'a> ReleaseBuilder<'a> {
+    pub(crate) fn new(settings: &'a Settings) -> Self {
+        // The first mapping of a signature wins, like `Settings::commit_username`
+        let usernames = settings
+            .changelog
+            .authors
+            .iter()
+            .rev()
+            .map(|author| (author.signature.as_str(), author.username.as_str()))
+            .collect();
+
+        ReleaseBuilder {
+            commit_types: settings.commit_types(),
+            usernames,
+            trailers: &settings.changelog.trailers,
+        }
+    }
+
+    /// Attach the changelog section title, author username and notes to a commit.
+    /// The section title is overridden by the `changelog.trailers.category` footer.
+    pub(crate) fn changelog_commit(&self, commit: Commit) -> ChangelogCommit<'a> {
+        let changelog_title = match self.category(&commit) {
+            Some(category) => self
+                .trailers
+                .categories
+                .iter()
+                .find(|(value, _)| value.eq_ignore_ascii_case(category))
+                .map(|(_, title)| title.clone())
+                .unwrap_or_else(|| category.to_string()),
+            None => self
+                .commit_types
+                .get(&commit.message.commit_type)
+                .map(|config| config.changelog_title.clone())
+                .unwrap_or_else(|| commit.message.commit_type.to_string()),
+        };
+
+        let notes = commit
+            .message
+            .footers
+            .iter()
+            .filter(|footer| {
+                self.trailers
+                    .notes
+                    .iter()
+                    .any(|token| footer.token.eq_ignore_ascii_case(token))
+            })
+            .map(|footer| footer.content.trim().to_string())
+            .collect();
+
+        ChangelogCommit {
+            author_username: self.usernames.get(commit.author.as_str()).copied(),
+            changelog_title,
+            notes,
+            duplicate_of: None,
+            commit,
         }
+    }
+
+    // Commits whose category footer is one of the `changelog.trailers.hidden` values
+    fn is_hidden(&self, commit: &Commit) -> bool {
+        self.category(commit).is_some_and(|category| {
+            self.trailers
+                .hidden
+                .iter()
+                .any(|hidden| hidden.eq_ignore_ascii_case(category))
+        })
+    }
+
+    fn category<'c>(&self, commit: &'c Commit) -> Option<&'c str> {
+        let token = self.trailers.category.as_deref()?;
+        commit
+            .message
+            .footers
+            .iter()
+            .find(|footer| footer.token.eq_ignore_ascii_case(token))
+            .map(|footer| footer.content.trim())
+    }
+
+    /// Build a release, ticking `progress` for each commit
+    pub(crate) fn build(
+        &self,
+        commit_range: CommitRange<'_>,
+        cache: Option<&CommitCache>,
+        mut progress: Option<&mut Progress>,
+    ) -> Release<'a> {
+        let commits = commit_range
+            .commits
+            .iter()
+            .inspect(|_| {
+                if let Some(progress) = progress.as_mut() {
+                    progress.tick();
+                }
+            })
+            // Ignore merge commits
+            .filter(|commit| {
+                !commit
+                    .message()
+                    .is_some_and(|message| message.starts_with("Merge"))
+            })
+            .map(|commit| parse_commit(cache, commit, &self.commit_types));
 
         Release {
             version: commit_range.to,
             from: commit_range.from,
             date: Utc::now().naive_utc(),
-            commits,
+            commits: self.changelog_commits(commits),
+            notes: None,
             previous: None,
         }
     }
+
+    // The changelog commits of the parsed commits that are not hidden,
+    // non conventional commits are reported and skipped
+    fn changelog_commits(
+        &self,
+        commits: impl Iterator<Item = Result<Commit, Box<ConventionalCommitError>>>,
+    ) -> Vec<ChangelogCommit<'a>> {
+        let mut changelog_commits = vec![];
+        for commit in commits {
+            match commit {
+                Ok(commit) if self.is_hidden(&commit) => {}
+                Ok(commit) => changelog_commits.push(self.changelog_commit(commit)),
+                Err(err) => {
+                    let err = err.to_string().red();
+                    warn!("{}", err);
+                }
+            };
+        }
+
+        changelog_commits
+    }
 }
 
-#[derive(Debug)]
+/// Iterator over a release and its predecessors, see [`Release::iter`]
+#[derive(Debug, Clone)]
+pub struct Releases<'r, 'a> {
+    next: Option<&'r Release<'a>>,
+}
+
+impl<'r, 'a> Iterator for Releases<'r, 'a> {
+    type Item = &'r Release<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let release = self.next?;
+        self.next = release.previous();
+        Some(release)
+    }
+}
+
+impl<'r, 'a> IntoIterator for &'r Release<'a> {
+    type Item = &'r Release<'a>;
+    type IntoIter = Releases<'r, 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A commit of a release with its changelog section title and author username
+#[derive(Debug, Clone)]
 pub struct ChangelogCommit<'a> {
     pub author_username: Option<&'a str>,
+    pub changelog_title: String,
+    /// The contents of the `changelog.trailers.notes` footers
+    pub notes: Vec<String>,
+    /// The id of the older commit of the changelog applying the same changes, see `changelog.dedupe`
+    pub duplicate_of: Option<String>,
     pub commit: Commit,
 }
 
-impl From<Commit> for ChangelogCommit<'_> {
-    fn from(commit: Commit) -> Self {
-        let author_username = settings::commit_username(&commit.author);
+impl ChangelogCommit<'_> {
+    /// The id of the commit this one was cherry-picked from, or its own id
+    pub(crate) fn original_oid(&self) -> &str {
+        let message = &self.commit.message;
+        let footer = message
+            .footers
+            .iter()
+            .find(|footer| footer.token.eq_ignore_ascii_case("X-Original-Sha"))
+            .map(|footer| footer.content.trim());
+        let cherry_picked = || {
+            message
+                .body
+                .iter()
+                .map(String::as_str)
+                .chain(message.footers.iter().map(|footer| footer.content.as_str()))
+                .flat_map(str::lines)
+                .find_map(|line| {
+                    line.trim()
+                        .strip_prefix("(cherry picked from commit ")?
+                        .strip_suffix(')')
+                })
+        };
 
-        ChangelogCommit {
-            author_username,
-            commit,
-        }
+        footer
+            .or_else(cherry_picked)
+            .unwrap_or(self.commit.oid.as_str())
     }
 }
 
@@ -121,12 +430,15 @@ mod test {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
 
-    use crate::conventional::changelog::release::{ChangelogCommit, Release};
+    use crate::conventional::changelog::release::{
+        ChangelogCommit, CommitRecord, Release, ReleaseBuilder,
+    };
     use crate::conventional::changelog::renderer::Renderer;
     use crate::conventional::changelog::template::{RemoteContext, Template, TemplateKind};
     use crate::conventional::commit::Commit;
     use crate::git::oid::OidOf;
     use crate::git::tag::Tag;
+    use crate::settings::{AuthorSetting, RemoteProvider, Settings};
 
     #[test]
     fn should_render_default_template() -> Result<()> {
@@ -160,6 +472,7 @@ mod test {
         let release = Release::fixture();
         let renderer = Renderer::try_new(Template {
             context: None,
+            repository: None,
             kind: TemplateKind::FullHash,
         })?;
 
@@ -193,6 +506,7 @@ mod test {
                 Some("cocogitto".into()),
                 Some("cocogitto".into()),
             ),
+            repository: None,
             kind: TemplateKind::Remote,
         })?;
 
@@ -216,6 +530,167 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn should_render_gitlab_urls() -> Result<()> {
+        // Arrange
+        let release = Release::fixture();
+        let renderer = Renderer::try_new(Template {
+            context: RemoteContext::try_new(
+                Some("gitlab.com".into()),
+                Some("cocogitto".into()),
+                Some("cocogitto".into()),
+            ),
+            repository: None,
+            kind: TemplateKind::Remote,
+        })?;
+
+        // Act
+        let changelog = renderer.render(release)?;
+
+        // Assert
+        assert!(changelog.starts_with(
+            "## [1.0.0](https://gitlab.com/cocogitto/cocogitto/-/compare/0.1.0...1.0.0) - 2015-09-05"
+        ));
+        assert!(changelog.contains(
+            "([17f7e23](https://gitlab.com/cocogitto/cocogitto/-/commit/17f7e23081db15e9318aeb37529b1d473cf41cbe))"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_bitbucket_urls_with_explicit_provider() {
+        let remote = RemoteContext::try_new(
+            Some("git.example.org".into()),
+            Some("cocogitto".into()),
+            Some("cocogitto".into()),
+        )
+        .unwrap()
+        .with_provider(RemoteProvider::Bitbucket);
+
+        assert_eq!(
+            remote.compare_url("1.0.0", "1.1.0"),
+            "https://git.example.org/cocogitto/cocogitto/branches/compare/1.1.0%0D1.0.0"
+        );
+        assert_eq!(
+            remote.commit_url("17f7e23"),
+            "https://git.example.org/cocogitto/cocogitto/commits/17f7e23"
+        );
+    }
+
+    #[test]
+    fn builder_resolves_usernames_once_first_mapping_wins() {
+        // Arrange
+        let mut settings = Settings::default();
+        settings.changelog.authors = vec![
+            AuthorSetting {
+                signature: "Paul Delafosse".to_string(),
+                username: "oknozor".to_string(),
+            },
+            AuthorSetting {
+                signature: "Paul Delafosse".to_string(),
+                username: "paul".to_string(),
+            },
+        ];
+
+        // Act
+        let builder = ReleaseBuilder::new(&settings);
+
+        // Assert
+        assert_eq!(builder.usernames.get("Paul Delafosse"), Some(&"oknozor"));
+        assert_eq!(
+            builder.usernames.get("Paul Delafosse").copied(),
+            settings.commit_username("Paul Delafosse")
+        );
+    }
+
+    fn record(sha: &str, message: &str) -> CommitRecord {
+        CommitRecord {
+            sha: sha.to_string(),
+            message: message.to_string(),
+            author: "Paul Delafosse".to_string(),
+            date: "2015-09-05T23:56:04Z".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_render_release_from_records() -> Result<()> {
+        // Arrange
+        let mut settings = Settings::default();
+        settings.changelog.authors = vec![AuthorSetting {
+            signature: "Paul Delafosse".to_string(),
+            username: "oknozor".to_string(),
+        }];
+        let records = [
+            record(
+                "17f7e23081db15e9318aeb37529b1d473cf41cbe",
+                "feat(parser): implement the changelog generator",
+            ),
+            record(
+                "9bb5facac5724bc81385fdd740fedbb49056da00",
+                "Merge branch 'parser'",
+            ),
+            record("fae3a288a1bc69b14f85a1d5fe57cee1964acd60", "Update README"),
+            record(
+                "2b0f2c1d4bd9fd7b3c6c1a8e3e5d7b0a1c2d3e4f",
+                "fix: handle empty input",
+            ),
+        ];
+
+        // Act
+        let release = Release::from_records("1.0.0", Some("0.1.0"), &records, &settings)?;
+        let changelog = release.into_markdown(Template::default())?;
+
+        // Assert
+        assert_eq!(
+            changelog,
+            indoc! {
+                "## 1.0.0 - 2015-09-05
+                #### Bug Fixes
+                - handle empty input - (2b0f2c1) - oknozor
+                #### Features
+                - **(parser)** implement the changelog generator - (17f7e23) - *oknozor*
+                "
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_start_records_release_at_oldest_commit() -> Result<()> {
+        // Arrange
+        let settings = Settings::default();
+        let records = [
+            record(
+                "17f7e23081db15e9318aeb37529b1d473cf41cbe",
+                "feat: a feature",
+            ),
+            record("fae3a288a1bc69b14f85a1d5fe57cee1964acd60", "fix: a fix"),
+        ];
+
+        // Act
+        let release = Release::from_records("1.0.0", None, &records, &settings)?;
+
+        // Assert
+        assert_eq!(
+            release.from.oid(),
+            &Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60")?
+        );
+        assert_eq!(release.commits.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_records() {
+        let settings = Settings::default();
+
+        let invalid_sha = [record("not a sha", "feat: a feature")];
+
+        assert!(Release::from_records("1.0.0", None, &invalid_sha, &settings).is_err());
+        assert!(Release::from_records("1.0.0", None, &[], &settings).is_err());
+    }
+
     impl Release<'_> {
         pub fn fixture() -> Release<'static> {
             let date =
@@ -226,11 +701,13 @@ mod test {
             let version = Tag::new(
                 "1.0.0",
                 Some(Oid::from_str("9bb5facac5724bc81385fdd740fedbb49056da00").unwrap()),
+                None,
             )
             .unwrap();
             let from = Tag::new(
                 "0.1.0",
                 Some(Oid::from_str("fae3a288a1bc69b14f85a1d5fe57cee1964acd60").unwrap()),
+                None,
             )
             .unwrap();
             Release {
@@ -240,6 +717,9 @@ mod test {
                 commits: vec![
                     ChangelogCommit {
                         author_username: Some("oknozor"),
+                        changelog_title: "Bug Fixes".to_string(),
+                        notes: vec![],
+                        duplicate_of: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
                             message: ConventionalCommit {
@@ -260,6 +740,9 @@ mod test {
                     },
                     ChangelogCommit {
                         author_username: None,
+                        changelog_title: "Features".to_string(),
+                        notes: vec![],
+                        duplicate_of: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
                             message: ConventionalCommit {
@@ -280,6 +763,9 @@ mod test {
                     },
                     ChangelogCommit {
                         author_username: Some("oknozor"),
+                        changelog_title: "Features".to_string(),
+                        notes: vec![],
+                        duplicate_of: None,
                         commit: Commit {
                             oid: a_commit_hash.to_string(),
                             message: ConventionalCommit {
@@ -299,6 +785,7 @@ mod test {
                         },
                     },
                 ],
+                notes: None,
                 previous: None,
             }
         }
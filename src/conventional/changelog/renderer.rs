@@ -3,7 +3,8 @@ use std::collections::HashMap;
 use tera::{get_json_pointer, to_value, try_get_value, Context, Tera, Value};
 
 use crate::conventional::changelog::release::Release;
-use crate::conventional::changelog::template::{RemoteContext, Template};
+use crate::conventional::changelog::template::{RemoteContext, Template, TemplateKind};
+use crate::git::oid::OidOf;
 
 #[derive(Debug)]
 pub struct Renderer {
@@ -20,43 +21,116 @@ impl Default for Renderer {
 impl Renderer {
     pub fn try_new(template: Template) -> Result<Self, tera::Error> {
         let mut tera = Tera::default();
-        let content = template.kind.get()?;
-        let content = String::from_utf8_lossy(content.as_slice());
+        tera.add_raw_templates(TemplateKind::builtins())?;
+
+        if let TemplateKind::Custom(_) = template.kind {
+            let content = template.kind.get()?;
+            let content = String::from_utf8_lossy(content.as_slice());
+            tera.add_raw_template(template.kind.name(), content.as_ref())?;
+        }
 
-        tera.add_raw_template(template.kind.name(), content.as_ref())?;
         tera.register_filter("upper_first", Self::upper_first_filter);
         tera.register_filter("unscoped", Self::unscoped);
+        tera.register_function("commit_url", Self::commit_url(template.context.clone()));
+        tera.register_function("compare_url", Self::compare_url(template.context.clone()));
 
         Ok(Renderer { tera, template })
     }
 
     pub(crate) fn render(&self, version: Release) -> Result<String, tera::Error> {
-        let mut release = self.render_release(&version)?;
+        let mut release = self.render_release(&version, None)?;
+        let mut next = Self::revision(&version.version);
         let mut version = version;
         while let Some(previous) = version.previous.map(|v| *v) {
             release.push_str("\n- - -\n\n");
-            release.push_str(self.render_release(&previous)?.as_str());
+            release.push_str(self.render_release(&previous, Some(&next))?.as_str());
+            next = Self::revision(&previous.version);
             version = previous;
         }
 
         Ok(release)
     }
-    fn render_release(&self, version: &Release) -> Result<String, tera::Error> {
+
+    // `next` is the revision of the release rendered right above this one, if any
+    fn render_release(&self, version: &Release, next: Option<&str>) -> Result<String, tera::Error> {
         let mut template_context = Context::from_serialize(version)?;
-        let context = self
-            .template
-            .context
-            .as_ref()
-            .map(RemoteContext::to_tera_context);
-
-        if let Some(context) = context {
-            template_context.extend(context);
+        let from = Self::revision(&version.from);
+        let to = Self::revision(&version.version);
+
+        if let Some(remote) = &self.template.context {
+            template_context.extend(remote.to_tera_context());
+            template_context.insert("previous_compare_url", &remote.compare_url(&from, &to));
+            if let Some(next) = next {
+                template_context.insert("next_compare_url", &remote.compare_url(&to, next));
+            }
+        }
+
+        if let Some(repository) = &self.template.repository {
+            template_context.insert("repository", repository);
+            template_context.insert("release_index", &repository.release_index(&to));
         }
 
         self.tera
             .render(self.template.kind.name(), &template_context)
     }
 
+    // Tags are compared by name, other revisions by their full commit id
+    fn revision(oid: &OidOf) -> String {
+        match oid {
+            OidOf::Tag(tag) => tag.to_string(),
+            OidOf::Head(oid) | OidOf::Other(oid) => oid.to_string(),
+        }
+    }
+
+    // `commit_url(oid=commit.id)`, built for the configured remote provider
+    fn commit_url(remote: Option<RemoteContext>) -> impl tera::Function {
+        move |args: &HashMap<String, Value>| {
+            let remote = Self::remote("commit_url", remote.as_ref())?;
+            let oid = Self::string_arg("commit_url", "oid", args)?;
+            Ok(Value::String(remote.commit_url(&oid)))
+        }
+    }
+
+    // `compare_url(from=from.tag, to=version.tag)`, built for the configured remote provider
+    fn compare_url(remote: Option<RemoteContext>) -> impl tera::Function {
+        move |args: &HashMap<String, Value>| {
+            let remote = Self::remote("compare_url", remote.as_ref())?;
+            let from = Self::string_arg("compare_url", "from", args)?;
+            let to = Self::string_arg("compare_url", "to", args)?;
+            Ok(Value::String(remote.compare_url(&from, &to)))
+        }
+    }
+
+    fn remote<'a>(
+        function: &str,
+        remote: Option<&'a RemoteContext>,
+    ) -> Result<&'a RemoteContext, tera::Error> {
+        remote.ok_or_else(|| {
+            tera::Error::msg(format!(
+                "Function `{}` requires the changelog 'remote', 'owner' and 'repository' settings",
+                function
+            ))
+        })
+    }
+
+    fn string_arg(
+        function: &str,
+        name: &str,
+        args: &HashMap<String, Value>,
+    ) -> Result<String, tera::Error> {
+        match args.get(name) {
+            Some(Value::String(value)) => Ok(value.clone()),
+            Some(value) => Err(tera::Error::msg(format!(
+                "Function `{}` expected a string for argument `{}`, got `{}`",
+                function, name, value
+            ))),
+            None => Err(tera::Error::msg(format!(
+                "Function `{}` is missing the `{}` argument",
+                function, name
+            ))),
+        }
+    }
+
     // From git-cliff: https://github.com/orhun/git-cliff/blob/main/git-cliff-core/src/template.rs
     fn upper_first_filter(value: &Value, _: &HashMap<String, Value>) -> Result<Value, tera::Error> {
         let mut s = tera::try_get_value!("upper_first_filter", "value", String, value);
@@ -4,7 +4,6 @@ use serde::{Serialize, Serializer};
 use crate::conventional::changelog::release::{ChangelogCommit, ChangelogFooter};
 use crate::git::oid::OidOf;
 use crate::git::tag::Tag;
-use crate::COMMITS_METADATA;
 
 impl Serialize for Tag {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -20,7 +19,7 @@ impl Serialize for ChangelogCommit<'_> {
     where
         S: Serializer,
     {
-        let mut commit = serializer.serialize_struct("Commit", 10)?;
+        let mut commit = serializer.serialize_struct("Commit", 12)?;
 
         let footers = &self
             .commit
@@ -30,21 +29,17 @@ impl Serialize for ChangelogCommit<'_> {
             .map(ChangelogFooter::from)
             .collect::<Vec<ChangelogFooter>>();
 
-        let commit_type = &COMMITS_METADATA
-            .iter()
-            .find(|(commit_type, _config)| *commit_type == &self.commit.message.commit_type)
-            .map(|meta| meta.1.changelog_title.clone())
-            .unwrap_or_else(|| self.commit.message.commit_type.to_string());
-
         commit.serialize_field("id", &self.commit.oid)?;
         commit.serialize_field("author", &self.author_username)?;
         commit.serialize_field("signature", &self.commit.author)?;
-        commit.serialize_field("type", commit_type)?;
+        commit.serialize_field("type", &self.changelog_title)?;
         commit.serialize_field("date", &self.commit.date)?;
         commit.serialize_field("scope", &self.commit.message.scope)?;
         commit.serialize_field("summary", &self.commit.message.summary)?;
         commit.serialize_field("body", &self.commit.message.body)?;
         commit.serialize_field("breaking_change", &self.commit.message.is_breaking_change)?;
+        commit.serialize_field("notes", &self.notes)?;
+        commit.serialize_field("duplicate_of", &self.duplicate_of)?;
         commit.serialize_field("footer", footers)?;
         commit.end()
     }
@@ -84,7 +79,7 @@ mod test {
 
     #[test]
     fn should_serialize_tag() {
-        let tag = Tag::new("1.0.0", Some(Oid::from_str("1234567890").unwrap())).unwrap();
+        let tag = Tag::new("1.0.0", Some(Oid::from_str("1234567890").unwrap()), None).unwrap();
 
         let result = toml::to_string(&tag);
 
@@ -97,6 +92,9 @@ mod test {
     fn should_serialize_commit() {
         let commit = ChangelogCommit {
             author_username: Some("Jm Doudou"),
+            changelog_title: "Bug Fixes".to_string(),
+            notes: vec![],
+            duplicate_of: None,
             commit: Commit {
                 oid: "1234567890".to_string(),
                 message: ConventionalCommit {
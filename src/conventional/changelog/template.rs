@@ -1,17 +1,20 @@
 use crate::conventional::changelog::error::ChangelogError;
+use crate::settings::RemoteProvider;
+use serde::Serialize;
 use std::io;
 use std::path::PathBuf;
 
-const DEFAULT_TEMPLATE: &[u8] = include_bytes!("template/simple");
+const DEFAULT_TEMPLATE: &str = include_str!("template/simple");
 const DEFAULT_TEMPLATE_NAME: &str = "default";
-const REMOTE_TEMPLATE: &[u8] = include_bytes!("template/remote");
+const REMOTE_TEMPLATE: &str = include_str!("template/remote");
 const REMOTE_TEMPLATE_NAME: &str = "remote";
-const FULL_HASH_TEMPLATE: &[u8] = include_bytes!("template/full_hash");
+const FULL_HASH_TEMPLATE: &str = include_str!("template/full_hash");
 const FULL_HASH_TEMPLATE_NAME: &str = "full_hash";
 
 #[derive(Debug, Default)]
 pub struct Template {
     pub context: Option<RemoteContext>,
+    pub repository: Option<RepositoryContext>,
     pub kind: TemplateKind,
 }
 
@@ -21,25 +24,27 @@ impl Template {
 
         Ok(Template {
             context,
+            repository: None,
             kind: template,
         })
     }
+
+    /// Expose the given repository metadata to the template
+    pub fn with_repository(mut self, repository: RepositoryContext) -> Self {
+        self.repository = Some(repository);
+        self
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum TemplateKind {
+    #[default]
     Default,
     FullHash,
     Remote,
     Custom(PathBuf),
 }
 
-impl Default for TemplateKind {
-    fn default() -> Self {
-        TemplateKind::Default
-    }
-}
-
 impl TemplateKind {
     /// Returns either a predefined template or a custom template
     fn from_arg(value: &str) -> Result<Self, ChangelogError> {
@@ -60,13 +65,23 @@ impl TemplateKind {
 
     pub(crate) fn get(&self) -> Result<Vec<u8>, io::Error> {
         match self {
-            TemplateKind::Default => Ok(DEFAULT_TEMPLATE.to_vec()),
-            TemplateKind::Remote => Ok(REMOTE_TEMPLATE.to_vec()),
-            TemplateKind::FullHash => Ok(FULL_HASH_TEMPLATE.to_vec()),
+            TemplateKind::Default => Ok(DEFAULT_TEMPLATE.as_bytes().to_vec()),
+            TemplateKind::Remote => Ok(REMOTE_TEMPLATE.as_bytes().to_vec()),
+            TemplateKind::FullHash => Ok(FULL_HASH_TEMPLATE.as_bytes().to_vec()),
             TemplateKind::Custom(path) => std::fs::read(path),
         }
     }
 
+    /// The predefined templates by name, custom templates can `{% extends %}` them
+    /// and override their `header`, `commits` and `footer` blocks
+    pub(crate) const fn builtins() -> [(&'static str, &'static str); 3] {
+        [
+            (DEFAULT_TEMPLATE_NAME, DEFAULT_TEMPLATE),
+            (REMOTE_TEMPLATE_NAME, REMOTE_TEMPLATE),
+            (FULL_HASH_TEMPLATE_NAME, FULL_HASH_TEMPLATE),
+        ]
+    }
+
     pub(crate) const fn name(&self) -> &'static str {
         match self {
             TemplateKind::Default => DEFAULT_TEMPLATE_NAME,
@@ -78,11 +93,12 @@ impl TemplateKind {
 }
 
 /// A wrapper to append remote repository information to template context
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RemoteContext {
     remote: String,
     repository: String,
     owner: String,
+    provider: RemoteProvider,
 }
 
 impl RemoteContext {
@@ -93,6 +109,7 @@ impl RemoteContext {
     ) -> Option<Self> {
         match (remote, repository, owner) {
             (Some(remote), Some(repository), Some(owner)) => Some(Self {
+                provider: RemoteProvider::from_remote(&remote),
                 remote,
                 repository,
                 owner,
@@ -102,15 +119,84 @@ impl RemoteContext {
         }
     }
 
+    /// Build urls for `provider` instead of the one guessed from the remote host
+    pub fn with_provider(mut self, provider: RemoteProvider) -> Self {
+        self.provider = provider;
+        self
+    }
+
+    pub(crate) fn provider(&self) -> RemoteProvider {
+        self.provider
+    }
+
+    /// The remote host, e.g. `github.com`
+    pub(crate) fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    /// The repository path on the remote, `owner/repository`
+    pub(crate) fn project(&self) -> String {
+        format!("{}/{}", self.owner, self.repository)
+    }
+
     pub(crate) fn to_tera_context(&self) -> tera::Context {
         let mut context = tera::Context::new();
         context.insert("platform", &format!("https://{}", self.remote.as_str()));
         context.insert("owner", self.owner.as_str());
-        context.insert(
-            "repository_url",
-            &format!("https://{}/{}/{}", self.remote, self.owner, self.repository),
-        );
+        context.insert("repository_url", &self.repository_url());
 
         context
     }
+
+    fn repository_url(&self) -> String {
+        format!("https://{}/{}/{}", self.remote, self.owner, self.repository)
+    }
+
+    /// Url comparing two revisions on the remote repository
+    pub(crate) fn compare_url(&self, from: &str, to: &str) -> String {
+        let repository_url = self.repository_url();
+        match self.provider {
+            RemoteProvider::Github => format!("{}/compare/{}..{}", repository_url, from, to),
+            RemoteProvider::Gitlab => format!("{}/-/compare/{}...{}", repository_url, from, to),
+            // Bitbucket puts the newest revision first, separated by an encoded carriage return
+            RemoteProvider::Bitbucket => {
+                format!("{}/branches/compare/{}%0D{}", repository_url, to, from)
+            }
+        }
+    }
+
+    /// Url of a single commit on the remote repository
+    pub(crate) fn commit_url(&self, oid: &str) -> String {
+        let repository_url = self.repository_url();
+        match self.provider {
+            RemoteProvider::Github => format!("{}/commit/{}", repository_url, oid),
+            RemoteProvider::Gitlab => format!("{}/-/commit/{}", repository_url, oid),
+            RemoteProvider::Bitbucket => format!("{}/commits/{}", repository_url, oid),
+        }
+    }
+}
+
+/// Repository metadata available to templates as `repository`
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RepositoryContext {
+    /// Url of the `origin` remote
+    pub remote_url: Option<String>,
+    pub default_branch: Option<String>,
+    pub tag_prefix: Option<String>,
+    /// Number of commits reachable from HEAD
+    pub commit_count: usize,
+    /// Version tags with their prefix, oldest first
+    pub tags: Vec<String>,
+}
+
+impl RepositoryContext {
+    /// The 1-based position of a release among all releases, a version
+    /// that is not tagged yet comes after the latest tag
+    pub(crate) fn release_index(&self, version: &str) -> usize {
+        self.tags
+            .iter()
+            .position(|tag| tag == version)
+            .unwrap_or(self.tags.len())
+            + 1
+    }
 }
@@ -2,15 +2,24 @@ use std::cmp::Ordering;
 use std::fmt::{self, Formatter};
 
 use crate::conventional::error::ConventionalCommitError;
-use crate::SETTINGS;
+use crate::conventional::lint::Linter;
+use crate::reporter::{info, warn};
+use crate::settings::Severity;
+use crate::CommitsMetadata;
 use chrono::{NaiveDateTime, Utc};
 use colored::*;
-use conventional_commit_parser::commit::ConventionalCommit;
-use git2::Commit as Git2Commit;
-use log::info;
+use conventional_commit_parser::commit::{CommitType, ConventionalCommit};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Eq, PartialEq)]
+#[cfg(feature = "git")]
+use {
+    crate::conventional::cache::{parse_commit, CommitCache},
+    crate::error::CocogittoError,
+    crate::git::repository::Repository,
+    git2::{Commit as Git2Commit, ErrorCode, Revwalk},
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Commit {
     pub(crate) oid: String,
     pub(crate) message: ConventionalCommit,
@@ -18,45 +27,66 @@ pub struct Commit {
     pub(crate) date: NaiveDateTime,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 pub struct CommitConfig {
     pub changelog_title: String,
+    /// The canonical type of a `commit_type_aliases` entry, parsed commits get it instead
+    #[serde(skip)]
+    pub alias_of: Option<CommitType>,
 }
 
 impl CommitConfig {
     pub(crate) fn new(changelog_title: &str) -> Self {
         CommitConfig {
             changelog_title: changelog_title.to_string(),
+            alias_of: None,
         }
     }
 }
 
 impl Commit {
+    #[cfg(feature = "git")]
     pub(crate) fn from_git_commit(
         commit: &Git2Commit,
+        allowed_commit_types: &CommitsMetadata,
     ) -> Result<Self, Box<ConventionalCommitError>> {
         let oid = commit.id().to_string();
-
-        let commit = commit.to_owned();
         let date = NaiveDateTime::from_timestamp(commit.time().seconds(), 0);
-        let message = commit.message();
-        let git2_message = message.unwrap().to_owned();
+        let message = commit.message().unwrap();
         let author = commit.author().name().unwrap_or("").to_string();
 
-        let message = git2_message.trim_end().trim_start();
-        let conventional_commit = conventional_commit_parser::parse(message);
+        Commit::parse(oid, message, author, date, allowed_commit_types)
+    }
+
+    /// Parse the message of a commit read from any source,
+    /// rejecting it like `cog check` does when it is not a conventional commit of an allowed type
+    pub fn parse(
+        oid: String,
+        message: &str,
+        author: String,
+        date: NaiveDateTime,
+        allowed_commit_types: &CommitsMetadata,
+    ) -> Result<Self, Box<ConventionalCommitError>> {
+        let trimmed = message.trim_end().trim_start();
+        let conventional_commit = conventional_commit_parser::parse(trimmed);
 
         match conventional_commit {
             Ok(message) => {
-                let commit = Commit {
+                let mut commit = Commit {
                     oid,
                     message,
                     author,
                     date,
                 };
 
-                match &SETTINGS.commit_types().get(&commit.message.commit_type) {
-                    Some(_) => Ok(commit),
+                match allowed_commit_types.get(&commit.message.commit_type) {
+                    Some(config) => {
+                        if let Some(canonical) = &config.alias_of {
+                            commit.message.commit_type = canonical.clone();
+                        }
+
+                        Ok(commit)
+                    }
                     None => Err(Box::new(ConventionalCommitError::CommitTypeNotAllowed {
                         oid: commit.oid.to_string(),
                         summary: format_summary(&commit.message),
@@ -66,7 +96,7 @@ impl Commit {
                 }
             }
             Err(cause) => {
-                let message = git2_message.trim_end();
+                let message = message.trim_end();
                 let summary = Commit::short_summary_from_str(message);
                 Err(Box::new(ConventionalCommitError::CommitFormat {
                     oid,
@@ -78,6 +108,24 @@ impl Commit {
         }
     }
 
+    /// The full commit id
+    pub fn oid(&self) -> &str {
+        &self.oid
+    }
+
+    pub fn message(&self) -> &ConventionalCommit {
+        &self.message
+    }
+
+    /// The author name, as written in the commit signature
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+
     pub(crate) fn shorthand(&self) -> &str {
         if self.oid != "not committed" {
             &self.oid[0..6]
@@ -176,7 +224,7 @@ impl fmt::Display for Commit {
 
 impl PartialOrd for Commit {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.message.scope.partial_cmp(&other.message.scope)
+        Some(self.cmp(other))
     }
 }
 
@@ -186,18 +234,63 @@ impl Ord for Commit {
     }
 }
 
+/// A lazy iterator over the conventional commits of a range, see [`CocoGitto::commit_iter`].
+/// Git commits are only looked up and parsed as the iterator advances.
+///
+/// [`CocoGitto::commit_iter`]: crate::CocoGitto::commit_iter
+#[cfg(feature = "git")]
+pub struct CommitIter<'repo> {
+    repository: &'repo Repository,
+    revwalk: Revwalk<'repo>,
+    allowed_commit_types: CommitsMetadata,
+    cache: Option<&'repo CommitCache>,
+}
+
+#[cfg(feature = "git")]
+impl<'repo> CommitIter<'repo> {
+    pub(crate) fn new(
+        repository: &'repo Repository,
+        revwalk: Revwalk<'repo>,
+        allowed_commit_types: CommitsMetadata,
+        cache: Option<&'repo CommitCache>,
+    ) -> Self {
+        CommitIter {
+            repository,
+            revwalk,
+            allowed_commit_types,
+            cache,
+        }
+    }
+}
+
+#[cfg(feature = "git")]
+impl Iterator for CommitIter<'_> {
+    type Item = Result<Commit, CocogittoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let commit = match self.revwalk.next()? {
+            Ok(oid) => self.repository.0.find_commit(oid),
+            // Shallow clones end with a missing parent
+            Err(err) if err.code() == ErrorCode::NotFound => return None,
+            Err(err) => Err(err),
+        };
+
+        let commit = commit.map_err(CocogittoError::from).and_then(|commit| {
+            parse_commit(self.cache, &commit, &self.allowed_commit_types).map_err(Into::into)
+        });
+
+        Some(commit)
+    }
+}
+
 pub fn verify(
     author: Option<String>,
     message: &str,
     ignore_merge_commit: bool,
+    allowed_commit_types: &CommitsMetadata,
+    linter: &Linter,
 ) -> Result<(), Box<ConventionalCommitError>> {
-    // Strip away comments from git message before parsing
-    let msg: String = message
-        .lines()
-        .filter(|line| !line.trim_start().starts_with('#'))
-        .collect::<Vec<&str>>()
-        .join("\n");
-
+    let msg = strip_comments(message);
     let msg = msg.trim();
 
     if msg.starts_with("Merge ") && ignore_merge_commit {
@@ -208,8 +301,24 @@ pub fn verify(
     let commit = conventional_commit_parser::parse(msg);
 
     match commit {
-        Ok(commit) => match &SETTINGS.commit_types().get(&commit.commit_type) {
+        Ok(commit) => match allowed_commit_types.get(&commit.commit_type) {
             Some(_) => {
+                let violations = linter.lint(&commit, msg);
+                if !violations.is_empty() {
+                    let err = ConventionalCommitError::Lint {
+                        oid: "not committed".to_string(),
+                        summary: format_summary(&commit),
+                        author: author.clone().unwrap_or_else(|| "Unknown".to_string()),
+                        violations,
+                    };
+
+                    if err.severity() == Severity::Error {
+                        return Err(Box::new(err));
+                    }
+
+                    warn!("{}", err);
+                }
+
                 info!(
                     "{}",
                     Commit {
@@ -232,6 +341,15 @@ pub fn verify(
     }
 }
 
+/// Strip away comments from a git message before parsing
+pub(crate) fn strip_comments(message: &str) -> String {
+    message
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
 pub(crate) fn format_summary(commit: &ConventionalCommit) -> String {
     match &commit.scope {
         None => format!("{}: {}", commit.commit_type, commit.summary,),
@@ -244,10 +362,12 @@ pub(crate) fn format_summary(commit: &ConventionalCommit) -> String {
 #[cfg(test)]
 mod test {
     use crate::conventional::commit::{format_summary, verify, Commit};
+    use crate::conventional::lint::Linter;
 
     use chrono::NaiveDateTime;
     use cmd_lib::run_fun;
 
+    use crate::settings::Settings;
     use crate::Repository;
     use anyhow::Result;
     use conventional_commit_parser::commit::{CommitType, ConventionalCommit, Footer, Separator};
@@ -311,7 +431,7 @@ mod test {
             },
         ]);
 
-        assert_that!(commit.to_string()).is_equal_to(&message.to_string())
+        assert_that!(commit.to_string()).is_equal_to(message.to_string())
     }
 
     #[test]
@@ -320,7 +440,13 @@ mod test {
         let message = "feat(database): add postgresql driver";
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(
+            Some("toml".into()),
+            message,
+            false,
+            &Settings::default().commit_types(),
+            &Linter::default(),
+        );
 
         // Assert
         assert_that!(result).is_ok();
@@ -338,7 +464,13 @@ mod test {
         );
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(
+            Some("toml".into()),
+            message,
+            false,
+            &Settings::default().commit_types(),
+            &Linter::default(),
+        );
 
         // Assert
         assert_that!(result).is_ok();
@@ -350,7 +482,13 @@ mod test {
         let message = "feat add postgresql driver";
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(
+            Some("toml".into()),
+            message,
+            false,
+            &Settings::default().commit_types(),
+            &Linter::default(),
+        );
 
         // Assert
         assert_that!(result).is_err();
@@ -362,7 +500,13 @@ mod test {
         let message = "post: add postgresql driver";
 
         // Act
-        let result = verify(Some("toml".into()), message, false);
+        let result = verify(
+            Some("toml".into()),
+            message,
+            false,
+            &Settings::default().commit_types(),
+            &Linter::default(),
+        );
 
         // Assert
         assert_that!(result).is_err();
@@ -383,7 +527,13 @@ mod test {
             "
         );
 
-        let outcome = verify(None, message, false);
+        let outcome = verify(
+            None,
+            message,
+            false,
+            &Settings::default().commit_types(),
+            &Linter::default(),
+        );
 
         assert_that!(outcome).is_ok();
         Ok(())
@@ -454,7 +604,7 @@ mod test {
         let commit = repo.0.find_commit(oid).expect("Unable to find commit");
 
         // Act
-        let commit = Commit::from_git_commit(&commit);
+        let commit = Commit::from_git_commit(&commit, &Settings::default().commit_types());
 
         // Assert
         assert_that!(commit).is_ok();
@@ -475,7 +625,7 @@ mod test {
         let commit = repo.0.find_commit(oid).expect("Unable to find commit");
 
         // Act
-        let commit = Commit::from_git_commit(&commit);
+        let commit = Commit::from_git_commit(&commit, &Settings::default().commit_types());
 
         // Assert
         assert_that!(commit).is_err();
@@ -496,7 +646,7 @@ mod test {
         let commit = repo.0.find_commit(oid).expect("Unable to find commit");
 
         // Act
-        let commit = Commit::from_git_commit(&commit);
+        let commit = Commit::from_git_commit(&commit, &Settings::default().commit_types());
 
         // Assert
         assert_that!(commit).is_err();
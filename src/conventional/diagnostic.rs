@@ -0,0 +1,304 @@
+//! Locate what is wrong in a commit message, for the CLI and editor integrations.
+
+use std::fmt::Write;
+use std::ops::Range;
+
+use conventional_commit_parser::error::{ParseError, ParseErrorKind};
+use itertools::Itertools;
+use pest::error::InputLocation;
+use serde::Serialize;
+
+use crate::CommitsMetadata;
+
+const MISSING_SEPARATOR: &str =
+    "separate the commit type from the summary with `: `, e.g. `feat: add a feature`";
+const MALFORMED_SCOPE: &str =
+    "put the scope between parenthesis right after the type, e.g. `feat(parser): ...`";
+const MALFORMED_FOOTER: &str = "separate footer tokens from their value with `: ` or ` #`, \
+    e.g. `Refs: #42`, and the body from the footers with a blank line";
+
+/// The part of a commit message a [`Diagnostic`] points at
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Component {
+    Type,
+    Scope,
+    Separator,
+    Summary,
+    Body,
+    Footer,
+}
+
+/// Why a commit message is not a valid conventional commit, and where
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub component: Component,
+    /// Byte range of the faulty part in the message, comment lines included
+    pub span: Range<usize>,
+    /// 1-based line of the span start
+    pub line: usize,
+    /// 1-based column of the span start, in characters
+    pub column: usize,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+/// Check a commit message like [`verify`](crate::conventional::commit::verify) does,
+/// returning where it fails instead of a flat error. `None` means the message is valid.
+pub fn diagnose(message: &str, allowed_commit_types: &CommitsMetadata) -> Option<Diagnostic> {
+    let stripped = StrippedMessage::new(message);
+    let text = stripped.text.trim();
+
+    let (component, span, diagnostic, suggestion) = match conventional_commit_parser::parse(text) {
+        Ok(commit) if allowed_commit_types.contains_key(&commit.commit_type) => return None,
+        Ok(commit) => {
+            let commit_type = commit.commit_type.to_string();
+            (
+                Component::Type,
+                0..commit_type.len(),
+                format!("Commit type `{}` not allowed", commit_type),
+                Some(suggest_commit_type(&commit_type, allowed_commit_types)),
+            )
+        }
+        Err(err) => {
+            let (component, span, suggestion) = locate_parse_error(text, &err);
+            (component, span, err.to_string(), suggestion)
+        }
+    };
+
+    let lead = stripped.text.len() - stripped.text.trim_start().len();
+    let start = stripped.original_offset(span.start + lead);
+    let end = stripped.original_offset(span.end + lead);
+    let before = &message[..start];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+
+    Some(Diagnostic {
+        component,
+        span: start..end,
+        line,
+        column,
+        message: diagnostic,
+        suggestion,
+    })
+}
+
+impl Diagnostic {
+    /// The faulty line of `message` underlined, followed by the suggestion
+    pub fn snippet(&self, message: &str) -> String {
+        let line = message.lines().nth(self.line - 1).unwrap_or_default();
+        let line_end = message[self.span.start..]
+            .find('\n')
+            .map_or(message.len(), |idx| self.span.start + idx);
+        let width = message[self.span.start..self.span.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        let mut snippet = String::new();
+        writeln!(snippet, "{} |", gutter).unwrap();
+        writeln!(snippet, "{} | {}", self.line, line).unwrap();
+        write!(
+            snippet,
+            "{} | {}{} {}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width),
+            self.message
+        )
+        .unwrap();
+
+        if let Some(suggestion) = &self.suggestion {
+            write!(snippet, "\n{} = suggestion: {}", gutter, suggestion).unwrap();
+        }
+
+        snippet
+    }
+}
+
+fn locate_parse_error(text: &str, err: &ParseError) -> (Component, Range<usize>, Option<String>) {
+    let span = match err.inner.location {
+        InputLocation::Pos(pos) => pos..(pos + 1).min(text.len()),
+        InputLocation::Span((start, end)) => start..end,
+    };
+
+    let header = text.lines().next().unwrap_or_default();
+    // The scope between parenthesis, when the header has one
+    let scope = header
+        .find('(')
+        .map(|start| start..header.find(')').map_or(header.len(), |end| end + 1));
+
+    match err.kind {
+        ParseErrorKind::MissingSeparator => {
+            (Component::Separator, span, Some(MISSING_SEPARATOR.into()))
+        }
+        ParseErrorKind::MissingWhiteSpace => (
+            Component::Separator,
+            span,
+            Some("add a space after `:`".into()),
+        ),
+        ParseErrorKind::UnexpectedParenthesis => (
+            Component::Scope,
+            scope.unwrap_or(span),
+            Some("remove the parenthesis inside the scope".into()),
+        ),
+        ParseErrorKind::UnexpectedWhitespaceOrNewLine => {
+            let suggestion = scope
+                .clone()
+                .map(|scope| header[scope].split_whitespace().join("-"))
+                .map(|scope| format!("use dashes instead of whitespace: `{}`", scope));
+            (Component::Scope, scope.unwrap_or(span), suggestion)
+        }
+        ParseErrorKind::MalformedScope => (
+            Component::Scope,
+            scope.unwrap_or(span),
+            Some(MALFORMED_SCOPE.into()),
+        ),
+        ParseErrorKind::MalformedOrUnexpectedFooterSeparator => {
+            (Component::Footer, span, Some(MALFORMED_FOOTER.into()))
+        }
+        ParseErrorKind::Other if span.start <= header.len() => (Component::Summary, span, None),
+        ParseErrorKind::Other => (Component::Body, span, None),
+    }
+}
+
+// Closest allowed type when it looks like a typo, the allowed types otherwise
+fn suggest_commit_type(commit_type: &str, allowed_commit_types: &CommitsMetadata) -> String {
+    let allowed = allowed_commit_types
+        .keys()
+        .map(|allowed| allowed.as_ref())
+        .sorted();
+
+    let closest = allowed
+        .clone()
+        .map(|allowed| (edit_distance(commit_type, allowed), allowed))
+        .min();
+
+    match closest {
+        Some((distance, closest)) if distance <= 2 => format!("did you mean `{}`?", closest),
+        _ => format!(
+            "use one of {}",
+            allowed.map(|allowed| format!("`{}`", allowed)).join(", ")
+        ),
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// A message without its comment lines, remembering where each kept line was in the original
+struct StrippedMessage {
+    text: String,
+    // (offset in `text`, offset in the original message) of each kept line
+    lines: Vec<(usize, usize)>,
+}
+
+impl StrippedMessage {
+    fn new(message: &str) -> Self {
+        let mut text = String::new();
+        let mut lines = vec![];
+        let mut offset = 0;
+
+        for line in message.split_inclusive('\n') {
+            let content = line.strip_suffix('\n').unwrap_or(line);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+
+            if !content.trim_start().starts_with('#') {
+                if !lines.is_empty() {
+                    text.push('\n');
+                }
+                lines.push((text.len(), offset));
+                text.push_str(content);
+            }
+
+            offset += line.len();
+        }
+
+        StrippedMessage { text, lines }
+    }
+
+    fn original_offset(&self, offset: usize) -> usize {
+        self.lines
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= offset)
+            .map_or(offset, |(start, original)| original + offset - start)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    use crate::conventional::diagnostic::{diagnose, Component};
+    use crate::settings::Settings;
+
+    #[test]
+    fn valid_message_has_no_diagnostic() {
+        let commit_types = Settings::default().commit_types();
+
+        let diagnostic = diagnose("feat(parser): add a parser", &commit_types);
+
+        assert_that!(diagnostic).is_none();
+    }
+
+    #[test]
+    fn unknown_type_suggests_the_closest_one() {
+        let commit_types = Settings::default().commit_types();
+
+        let diagnostic = diagnose("faet: add a parser", &commit_types).unwrap();
+
+        assert_that!(diagnostic.component).is_equal_to(Component::Type);
+        assert_that!(diagnostic.span).is_equal_to(0..4);
+        assert_that!(diagnostic.suggestion).is_equal_to(Some("did you mean `feat`?".to_string()));
+    }
+
+    #[test]
+    fn scope_with_whitespace_spans_the_scope() {
+        let commit_types = Settings::default().commit_types();
+
+        let diagnostic = diagnose("feat(my scope): add a parser", &commit_types).unwrap();
+
+        assert_that!(diagnostic.component).is_equal_to(Component::Scope);
+        assert_that!(diagnostic.span).is_equal_to(4..14);
+        assert_that!(diagnostic.suggestion).is_equal_to(Some(
+            "use dashes instead of whitespace: `(my-scope)`".to_string(),
+        ));
+    }
+
+    #[test]
+    fn span_accounts_for_comment_lines() {
+        let commit_types = Settings::default().commit_types();
+        let message = indoc! {"
+            # Please enter the commit message
+            feat add a parser
+        "};
+
+        let diagnostic = diagnose(message, &commit_types).unwrap();
+
+        assert_that!(diagnostic.component).is_equal_to(Component::Separator);
+        assert_that!(diagnostic.line).is_equal_to(2);
+        assert_that!(message[diagnostic.span.start..].to_string()).starts_with(" add a parser");
+    }
+}
@@ -1,7 +1,12 @@
+use crate::conventional::lint::{highlight, Violation};
+#[cfg(feature = "git")]
 use crate::git::error::{Git2Error, TagError};
+use crate::settings::Severity;
 use anyhow::anyhow;
 use colored::Colorize;
 use conventional_commit_parser::error::ParseError;
+use itertools::Itertools;
+use semver::Version;
 use serde::de::StdError;
 use std::fmt;
 use std::fmt::{Display, Formatter};
@@ -20,23 +25,35 @@ pub enum ConventionalCommitError {
         commit_type: String,
         author: String,
     },
+    /// A conventional commit violating `lint` rules
+    Lint {
+        oid: String,
+        summary: String,
+        author: String,
+        violations: Vec<Violation>,
+    },
     ParseError(ParseError),
 }
 
 #[derive(Debug)]
 pub enum BumpError {
+    #[cfg(feature = "git")]
     Git2Error(Git2Error),
+    #[cfg(feature = "git")]
     TagError(TagError),
     SemVerError(semver::Error),
     FmtError(fmt::Error),
     NoCommitFound,
+    AlreadyStable(Version),
 }
 
 impl Display for BumpError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "failed to bump version\n")?;
         match self {
+            #[cfg(feature = "git")]
             BumpError::Git2Error(err) => writeln!(f, "\t{}", err),
+            #[cfg(feature = "git")]
             BumpError::TagError(err) => writeln!(f, "\t{}", err),
             BumpError::SemVerError(err) => writeln!(f, "\t{}", err),
             BumpError::FmtError(err) => writeln!(f, "\t{}", err),
@@ -49,16 +66,23 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
     Alternatively consider using `cog bump <--version <VERSION>|--auto|--major|--minor>`
 "#
             ),
+            BumpError::AlreadyStable(version) => writeln!(
+                f,
+                "cause: {} is already a stable version, only 0.x versions and 1.0.0 pre-releases can be stabilized",
+                version
+            ),
         }
     }
 }
 
+#[cfg(feature = "git")]
 impl From<Git2Error> for BumpError {
     fn from(err: Git2Error) -> Self {
         Self::Git2Error(err)
     }
 }
 
+#[cfg(feature = "git")]
 impl From<TagError> for BumpError {
     fn from(err: TagError) -> Self {
         Self::TagError(err)
@@ -77,6 +101,44 @@ impl From<fmt::Error> for BumpError {
     }
 }
 
+impl ConventionalCommitError {
+    /// The oid and summary of the rejected commit, `None` for a message that is not committed
+    pub(crate) fn commit(&self) -> Option<(&str, &str)> {
+        match self {
+            ConventionalCommitError::CommitFormat { oid, summary, .. }
+            | ConventionalCommitError::CommitTypeNotAllowed { oid, summary, .. }
+            | ConventionalCommitError::Lint { oid, summary, .. } => Some((oid, summary)),
+            ConventionalCommitError::ParseError(_) => None,
+        }
+    }
+
+    /// `Warning` when the commit only violates lint rules configured as warnings
+    pub fn severity(&self) -> Severity {
+        match self {
+            ConventionalCommitError::Lint { violations, .. }
+                if violations
+                    .iter()
+                    .all(|violation| violation.severity == Severity::Warning) =>
+            {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+
+    /// Why the commit was rejected, without the commit details nor colors
+    pub(crate) fn cause(&self) -> String {
+        match self {
+            ConventionalCommitError::CommitFormat { cause, .. } => cause.to_string(),
+            ConventionalCommitError::CommitTypeNotAllowed { commit_type, .. } => {
+                format!("Commit type `{}` not allowed", commit_type)
+            }
+            ConventionalCommitError::Lint { violations, .. } => violations.iter().join("\n"),
+            ConventionalCommitError::ParseError(err) => err.to_string(),
+        }
+    }
+}
+
 impl Display for ConventionalCommitError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -126,6 +188,37 @@ impl Display for ConventionalCommitError {
                     commit_type = commit_type.red()
                 )
             }
+            ConventionalCommitError::Lint {
+                summary,
+                oid,
+                author,
+                violations,
+            } => {
+                let error_header = match self.severity() {
+                    Severity::Warning => "Commit with warnings: ".bold().yellow(),
+                    _ => "Errored commit: ".bold().red(),
+                };
+                let author = format!("<{}>", author).blue();
+                writeln!(
+                    f,
+                    "{}{} {}\n\t{message}'{summary}'",
+                    error_header,
+                    oid,
+                    author,
+                    message = "Commit message:".yellow().bold(),
+                    summary = highlight(summary, violations).italic(),
+                )?;
+
+                for violation in violations {
+                    let title = match violation.severity {
+                        Severity::Warning => "Warning:",
+                        _ => "Error:",
+                    };
+                    writeln!(f, "\t{}{}", title.yellow().bold(), violation)?;
+                }
+
+                Ok(())
+            }
             ConventionalCommitError::ParseError(err) => {
                 let err = anyhow!(err.clone());
                 writeln!(f, "{:?}", err)
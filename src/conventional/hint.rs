@@ -0,0 +1,124 @@
+//! The comment shown above an errored commit message when editing it with `cog edit`.
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::conventional::diagnostic::diagnose;
+use crate::CommitsMetadata;
+
+const EDIT_HINT_TEMPLATE: &str = include_str!("template/edit_hint");
+
+/// The context of the edit hint template
+#[derive(Debug, Serialize)]
+pub(crate) struct EditHint<'a> {
+    oid: &'a str,
+    /// The lines explaining why the commit was rejected
+    error: Vec<&'a str>,
+    /// The faulty part of the message underlined, when it is not a valid conventional commit
+    snippet: Option<Vec<String>>,
+    types: Vec<HintType<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct HintType<'a> {
+    name: &'a str,
+    changelog_title: &'a str,
+    alias_of: Option<&'a str>,
+}
+
+impl<'a> EditHint<'a> {
+    pub(crate) fn new(
+        oid: &'a str,
+        message: &str,
+        error: &'a str,
+        commit_types: &'a CommitsMetadata,
+    ) -> Self {
+        let snippet = diagnose(message, commit_types).map(|diagnostic| {
+            let snippet = diagnostic.snippet(message);
+            snippet.lines().map(str::to_string).collect()
+        });
+
+        let types = commit_types
+            .iter()
+            .map(|(commit_type, config)| HintType {
+                name: commit_type.as_ref(),
+                changelog_title: &config.changelog_title,
+                alias_of: config.alias_of.as_ref().map(AsRef::as_ref),
+            })
+            .sorted_by_key(|commit_type| commit_type.name)
+            .collect();
+
+        EditHint {
+            oid,
+            error: error.lines().collect(),
+            snippet,
+            types,
+        }
+    }
+
+    /// Render the hint, every line of it is a comment
+    pub(crate) fn render(&self) -> Result<String, tera::Error> {
+        let context = tera::Context::from_serialize(self)?;
+        tera::Tera::one_off(EDIT_HINT_TEMPLATE, &context, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::hint::EditHint;
+    use crate::settings::Settings;
+
+    use anyhow::Result;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_render_edit_hint() -> Result<()> {
+        // Arrange
+        let commit_types = Settings::default().commit_types();
+        let message = "faet: add a parser";
+
+        // Act
+        let hint = EditHint::new(
+            "2a4c1b0",
+            message,
+            "Commit type `faet` not allowed",
+            &commit_types,
+        )
+        .render()?;
+
+        // Assert
+        assert_that!(hint.lines().all(|line| line.starts_with('#'))).is_true();
+        assert_that!(hint.as_str())
+            .starts_with("# Editing commit 2a4c1b0\n#\n#   |\n# 1 | faet: add a parser\n");
+        assert_that!(hint.as_str()).contains("^^^^ Commit type `faet` not allowed");
+        assert_that!(hint.as_str()).contains("suggestion: did you mean `feat`?");
+        assert_that!(hint.as_str()).contains("#   feat: Features\n");
+        assert_that!(hint.as_str()).contains("# Examples:");
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_aliases() -> Result<()> {
+        // Arrange
+        let mut settings = Settings::default();
+        settings
+            .commit_type_aliases
+            .insert("bugfix".into(), "fix".into());
+        let commit_types = settings.commit_types();
+
+        // Act
+        let hint = EditHint::new(
+            "2a4c1b0",
+            "bugfix: handle empty input",
+            "canonical-type: use `fix` instead of `bugfix`",
+            &commit_types,
+        )
+        .render()?;
+
+        // Assert
+        assert_that!(hint.as_str())
+            .contains("#\n# canonical-type: use `fix` instead of `bugfix`\n#\n");
+        assert_that!(hint.as_str()).contains("#   bugfix: Bug Fixes (alias of fix)\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,923 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "git")]
+use std::io::Write;
+#[cfg(feature = "git")]
+use std::process::Stdio;
+
+use anyhow::Context;
+use colored::Colorize;
+use conventional_commit_parser::commit::{ConventionalCommit, Footer, Separator};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use itertools::Itertools;
+use regex::Regex;
+use serde::Serialize;
+
+#[cfg(feature = "git")]
+use crate::hook::shell_command;
+use crate::settings::{HookShell, Settings, Severity};
+
+/// The id of every lint rule, the keys of `lint.severities`
+pub const RULES: &[&str] = &[
+    "banned-word",
+    "blank-line-after-summary",
+    "body-line-length",
+    "canonical-type",
+    "external",
+    "imperative-mood",
+    "spelling",
+    "staged-scope",
+    "ticket-reference",
+];
+
+/// A lint rule a conventional commit message does not comply with
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct Violation {
+    /// The rule id, e.g. `ticket-reference`
+    pub rule: &'static str,
+    pub message: String,
+    /// The offending word of the summary, highlighted in reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+    /// The severity configured in `lint.severities`, warnings do not fail checks
+    pub severity: Severity,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.rule)
+    }
+}
+
+/// The `lint` rules checked by `cog check`, `cog verify` and `cog commit` on top of the
+/// conventional commit format
+#[derive(Debug, Clone, Default)]
+pub struct Linter {
+    /// The `commit_type_aliases`, rules read the canonical types
+    aliases: HashMap<String, String>,
+    canonical_types: bool,
+    /// Lowercase
+    banned_words: Vec<String>,
+    /// Lowercase misspellings and their correction, `None` without `lint.spellcheck`
+    misspellings: Option<HashMap<String, String>>,
+    /// Lowercase verb forms rejected at the start of summaries, with their imperative form when
+    /// known. `None` without `lint.imperative_mood`.
+    non_imperative_verbs: Option<HashMap<String, Option<String>>>,
+    blank_line_after_summary: bool,
+    body_max_line_length: Option<usize>,
+    ticket_reference: Option<TicketReference>,
+    /// Rules configured with another severity than `error`
+    severities: HashMap<String, Severity>,
+    external: Vec<String>,
+    hook_shell: HookShell,
+    /// The files of each scope
+    scope_paths: HashMap<String, GlobSet>,
+    /// The files of the commit being authored, `None` when checking commits
+    staged_files: Option<Vec<String>>,
+}
+
+// Verbs commonly starting commit summaries: imperative, past tense and gerund
+const VERBS: &[(&str, &str, &str)] = &[
+    ("add", "added", "adding"),
+    ("adjust", "adjusted", "adjusting"),
+    ("allow", "allowed", "allowing"),
+    ("avoid", "avoided", "avoiding"),
+    ("build", "built", "building"),
+    ("bump", "bumped", "bumping"),
+    ("change", "changed", "changing"),
+    ("clean", "cleaned", "cleaning"),
+    ("configure", "configured", "configuring"),
+    ("convert", "converted", "converting"),
+    ("correct", "corrected", "correcting"),
+    ("create", "created", "creating"),
+    ("delete", "deleted", "deleting"),
+    ("deprecate", "deprecated", "deprecating"),
+    ("disable", "disabled", "disabling"),
+    ("document", "documented", "documenting"),
+    ("drop", "dropped", "dropping"),
+    ("enable", "enabled", "enabling"),
+    ("ensure", "ensured", "ensuring"),
+    ("expose", "exposed", "exposing"),
+    ("extract", "extracted", "extracting"),
+    ("fix", "fixed", "fixing"),
+    ("handle", "handled", "handling"),
+    ("implement", "implemented", "implementing"),
+    ("improve", "improved", "improving"),
+    ("increase", "increased", "increasing"),
+    ("introduce", "introduced", "introducing"),
+    ("make", "made", "making"),
+    ("merge", "merged", "merging"),
+    ("migrate", "migrated", "migrating"),
+    ("move", "moved", "moving"),
+    ("optimize", "optimized", "optimizing"),
+    ("prevent", "prevented", "preventing"),
+    ("reduce", "reduced", "reducing"),
+    ("refactor", "refactored", "refactoring"),
+    ("release", "released", "releasing"),
+    ("remove", "removed", "removing"),
+    ("rename", "renamed", "renaming"),
+    ("replace", "replaced", "replacing"),
+    ("resolve", "resolved", "resolving"),
+    ("return", "returned", "returning"),
+    ("revert", "reverted", "reverting"),
+    ("simplify", "simplified", "simplifying"),
+    ("split", "split", "splitting"),
+    ("support", "supported", "supporting"),
+    ("update", "updated", "updating"),
+    ("upgrade", "upgraded", "upgrading"),
+    ("use", "used", "using"),
+    ("write", "wrote", "writing"),
+];
+
+// Common misspellings in commit summaries, with their correction
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accomodate", "accommodate"),
+    ("acheive", "achieve"),
+    ("adress", "address"),
+    ("agressive", "aggressive"),
+    ("alot", "a lot"),
+    ("arguement", "argument"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("calender", "calendar"),
+    ("commited", "committed"),
+    ("comparision", "comparison"),
+    ("compatability", "compatibility"),
+    ("definately", "definitely"),
+    ("dependancy", "dependency"),
+    ("dependancies", "dependencies"),
+    ("enviroment", "environment"),
+    ("existant", "existent"),
+    ("explicitely", "explicitly"),
+    ("foward", "forward"),
+    ("funtion", "function"),
+    ("guarentee", "guarantee"),
+    ("implemention", "implementation"),
+    ("independant", "independent"),
+    ("initialise", "initialize"),
+    ("lenght", "length"),
+    ("neccessary", "necessary"),
+    ("necesary", "necessary"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("paramter", "parameter"),
+    ("paramters", "parameters"),
+    ("persistant", "persistent"),
+    ("posible", "possible"),
+    ("preceeding", "preceding"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("refered", "referred"),
+    ("reponse", "response"),
+    ("retreive", "retrieve"),
+    ("seperate", "separate"),
+    ("sucess", "success"),
+    ("succesful", "successful"),
+    ("teh", "the"),
+    ("threshhold", "threshold"),
+    ("untill", "until"),
+    ("wich", "which"),
+];
+
+#[derive(Debug, Clone)]
+struct TicketReference {
+    pattern: Regex,
+    types: Vec<String>,
+}
+
+impl Linter {
+    pub fn new(settings: &Settings) -> anyhow::Result<Self> {
+        let ticket_reference = settings
+            .lint
+            .ticket_reference
+            .as_ref()
+            .map(|rule| -> anyhow::Result<_> {
+                Ok(TicketReference {
+                    pattern: Regex::new(&rule.pattern)
+                        .context("invalid lint.ticket_reference pattern")?,
+                    types: rule.types.clone(),
+                })
+            })
+            .transpose()?;
+
+        let scope_paths = settings
+            .lint
+            .scope_paths
+            .iter()
+            .map(|(scope, globs)| {
+                let mut builder = GlobSetBuilder::new();
+                for glob in globs {
+                    builder
+                        .add(Glob::new(glob).with_context(|| {
+                            format!("invalid lint.scope_paths glob `{}`", glob)
+                        })?);
+                }
+
+                Ok((scope.clone(), builder.build()?))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let misspellings =
+            settings.lint.spellcheck.then(|| {
+                MISSPELLINGS
+                    .iter()
+                    .map(|(word, correction)| (word.to_string(), correction.to_string()))
+                    .chain(
+                        settings.lint.misspellings.iter().map(|(word, correction)| {
+                            (word.to_lowercase(), correction.to_string())
+                        }),
+                    )
+                    .collect()
+            });
+
+        // Past tense forms equal to the imperative, like `split`, are not rejected
+        let non_imperative_verbs = settings.lint.imperative_mood.then(|| {
+            VERBS
+                .iter()
+                .flat_map(|(imperative, past, gerund)| {
+                    [past, gerund]
+                        .into_iter()
+                        .filter(move |form| *form != imperative)
+                        .map(move |form| (form.to_string(), Some(imperative.to_string())))
+                })
+                .chain(
+                    settings
+                        .lint
+                        .non_imperative_verbs
+                        .iter()
+                        .map(|verb| (verb.to_lowercase(), None)),
+                )
+                .collect()
+        });
+
+        Ok(Linter {
+            aliases: settings.commit_type_aliases.clone(),
+            canonical_types: settings.lint.canonical_types,
+            banned_words: settings
+                .lint
+                .banned_words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect(),
+            misspellings,
+            non_imperative_verbs,
+            blank_line_after_summary: settings.lint.blank_line_after_summary,
+            body_max_line_length: settings.lint.body_max_line_length,
+            ticket_reference,
+            severities: settings.lint.severities.clone(),
+            external: settings.lint.external.clone(),
+            hook_shell: settings.hook_shell,
+            scope_paths,
+            staged_files: None,
+        })
+    }
+
+    /// Check the scope of commits against the staged files, see `lint.scope_paths`
+    pub fn with_staged_files(mut self, staged_files: Vec<String>) -> Self {
+        self.staged_files = Some(staged_files);
+        self
+    }
+
+    /// The rules violated by `commit`, parsed from `message`, empty when it complies with all of
+    /// them. The commit type is read as written in the message, aliases included.
+    pub fn lint(&self, commit: &ConventionalCommit, message: &str) -> Vec<Violation> {
+        let mut violations = vec![];
+        let message = message.trim();
+        // Parsed commits get the canonical type of aliases
+        let written_type = message
+            .split(['(', '!', ':'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let commit_type = match self.aliases.get(&written_type) {
+            Some(canonical) => {
+                if self.canonical_types {
+                    violations.push(Violation {
+                        rule: "canonical-type",
+                        message: format!(
+                            "Commit type `{}` is an alias of `{}`",
+                            written_type, canonical
+                        ),
+                        word: None,
+                        severity: Severity::Error,
+                    });
+                }
+
+                canonical.as_str()
+            }
+            None => commit.commit_type.as_ref(),
+        };
+
+        if let Some(rule) = &self.ticket_reference {
+            if let Some(violation) = rule.check(commit_type, commit) {
+                violations.push(violation);
+            }
+        }
+
+        violations.extend(self.check_staged_scope(commit));
+        violations.extend(self.check_mood(&commit.summary));
+        violations.extend(self.check_words(&commit.summary));
+        violations.extend(self.check_body(message));
+        #[cfg(feature = "git")]
+        violations.extend(self.check_external(message));
+        violations
+            .into_iter()
+            .filter_map(|mut violation| {
+                violation.severity = self
+                    .severities
+                    .get(violation.rule)
+                    .copied()
+                    .unwrap_or_default();
+                (violation.severity != Severity::Off).then_some(violation)
+            })
+            .collect()
+    }
+
+    // The scope of the commit being authored must have staged files
+    fn check_staged_scope(&self, commit: &ConventionalCommit) -> Option<Violation> {
+        let scope = commit.scope.as_deref()?;
+        let staged_files = self.staged_files.as_ref()?;
+        if self.scope_paths.is_empty() {
+            return None;
+        }
+
+        let matches = |scope: &str| {
+            self.scope_paths
+                .get(scope)
+                .is_some_and(|paths| staged_files.iter().any(|file| paths.is_match(file)))
+        };
+
+        if matches(scope) {
+            return None;
+        }
+
+        let mut message = format!("No staged file belongs to scope `{}`", scope);
+        let staged_scopes = self
+            .scope_paths
+            .keys()
+            .filter(|scope| matches(scope))
+            .sorted()
+            .map(|scope| format!("`{}`", scope))
+            .join(", ");
+        if !staged_scopes.is_empty() {
+            message.push_str(&format!(", the staged files belong to {}", staged_scopes));
+        }
+
+        Some(Violation {
+            rule: "staged-scope",
+            message,
+            word: None,
+            severity: Severity::Error,
+        })
+    }
+
+    fn check_body(&self, message: &str) -> Vec<Violation> {
+        let mut violations = vec![];
+        let second_line = message.lines().nth(1);
+        if self.blank_line_after_summary && second_line.is_some_and(|line| !line.trim().is_empty())
+        {
+            violations.push(Violation {
+                rule: "blank-line-after-summary",
+                message: "Missing blank line between the summary and the body".to_string(),
+                word: None,
+                severity: Severity::Error,
+            });
+        }
+
+        if let Some(max_length) = self.body_max_line_length {
+            // Urls cannot be wrapped
+            let too_long = message
+                .lines()
+                .enumerate()
+                .skip(1)
+                .map(|(idx, line)| (idx + 1, line.chars().count(), line))
+                .filter(|(_, length, line)| *length > max_length && !line.contains("://"));
+
+            for (line_number, length, _) in too_long {
+                violations.push(Violation {
+                    rule: "body-line-length",
+                    message: format!(
+                        "Line {} is {} characters long, the maximum is {}",
+                        line_number, length, max_length
+                    ),
+                    word: None,
+                    severity: Severity::Error,
+                });
+            }
+        }
+
+        violations
+    }
+
+    // Pipe the message to each external command, a failing command reports its stderr
+    #[cfg(feature = "git")]
+    fn check_external(&self, message: &str) -> Vec<Violation> {
+        if self.severities.get("external") == Some(&Severity::Off) {
+            return vec![];
+        }
+
+        self.external
+            .iter()
+            .filter_map(|command| {
+                let message = match run_external(self.hook_shell, command, message) {
+                    Ok(None) => return None,
+                    Ok(Some(message)) => message,
+                    Err(err) => format!("Failed to run `{}`: {}", command, err),
+                };
+
+                Some(Violation {
+                    rule: "external",
+                    message,
+                    word: None,
+                    severity: Severity::Error,
+                })
+            })
+            .collect()
+    }
+
+    fn check_mood(&self, summary: &str) -> Option<Violation> {
+        let verbs = self.non_imperative_verbs.as_ref()?;
+        let word = words(summary).next()?;
+        let imperative = verbs.get(&word.to_lowercase())?;
+        let message = match imperative {
+            Some(imperative) => format!(
+                "Summary starts with `{}`, use the imperative mood: `{}`",
+                word, imperative
+            ),
+            None => format!("Summary starts with `{}`, use the imperative mood", word),
+        };
+
+        Some(Violation {
+            rule: "imperative-mood",
+            message,
+            word: Some(word.to_string()),
+            severity: Severity::Error,
+        })
+    }
+
+    // The banned and misspelled words of the summary, each reported once
+    fn check_words(&self, summary: &str) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = vec![];
+        for word in words(summary) {
+            let lowercase = word.to_lowercase();
+            let reported = violations
+                .iter()
+                .any(|violation| violation.word.as_deref() == Some(word));
+            if reported {
+                continue;
+            }
+
+            if self.banned_words.contains(&lowercase) {
+                violations.push(Violation {
+                    rule: "banned-word",
+                    message: format!("Banned word `{}` in the summary", word),
+                    word: Some(word.to_string()),
+                    severity: Severity::Error,
+                });
+            } else if let Some(correction) = self
+                .misspellings
+                .as_ref()
+                .and_then(|misspellings| misspellings.get(&lowercase))
+            {
+                violations.push(Violation {
+                    rule: "spelling",
+                    message: format!(
+                        "Misspelled word `{}` in the summary, did you mean `{}`?",
+                        word, correction
+                    ),
+                    word: Some(word.to_string()),
+                    severity: Severity::Error,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+// The stderr of `command` when it rejects the message, `None` when it accepts it
+#[cfg(feature = "git")]
+fn run_external(shell: HookShell, command: &str, message: &str) -> anyhow::Result<Option<String>> {
+    let mut child = shell_command(shell, command)?
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Commands are free not to read the message
+    let _ = child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(message.as_bytes());
+
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        Ok(Some(format!("`{}` exited with {}", command, output.status)))
+    } else {
+        Ok(Some(stderr))
+    }
+}
+
+impl TicketReference {
+    fn check(&self, commit_type: &str, commit: &ConventionalCommit) -> Option<Violation> {
+        if !self.types.is_empty() && !self.types.iter().any(|typ| typ == commit_type) {
+            return None;
+        }
+
+        let referenced = commit
+            .scope
+            .iter()
+            .chain(commit.body.iter())
+            .cloned()
+            .chain(commit.footers.iter().map(footer_line))
+            .any(|text| self.pattern.is_match(&text));
+
+        (!referenced).then(|| Violation {
+            rule: "ticket-reference",
+            message: format!(
+                "No ticket reference matching `{}` in the scope, body or footers",
+                self.pattern
+            ),
+            word: None,
+            severity: Severity::Error,
+        })
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|char: char| !char.is_alphanumeric() && char != '\'')
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+}
+
+/// The summary with the offending words of `violations` highlighted
+pub(crate) fn highlight(summary: &str, violations: &[Violation]) -> String {
+    let offending: Vec<&str> = violations
+        .iter()
+        .filter_map(|violation| violation.word.as_deref())
+        .collect();
+
+    let mut highlighted = String::with_capacity(summary.len());
+    let mut rest = summary;
+    for word in words(summary) {
+        // `words` yields sub slices of `summary`, in order
+        let start = rest.find(word).expect("word of the summary");
+        highlighted.push_str(&rest[..start]);
+        if offending.contains(&word) {
+            highlighted.push_str(&word.red().bold().to_string());
+        } else {
+            highlighted.push_str(word);
+        }
+        rest = &rest[start + word.len()..];
+    }
+
+    highlighted.push_str(rest);
+    highlighted
+}
+
+/// The footer as written in the commit message, `Closes #12` is parsed as a `Closes` footer
+/// with the content `12`
+pub(crate) fn footer_line(footer: &Footer) -> String {
+    match footer.token_separator {
+        Separator::Hash => format!("{} #{}", footer.token, footer.content),
+        _ => format!("{}: {}", footer.token, footer.content),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::lint::{highlight, Linter, Violation};
+    use crate::settings::{LintSettings, Settings, Severity, TicketReferenceRule};
+
+    use anyhow::Result;
+    use colored::Colorize;
+    use conventional_commit_parser::parse;
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    fn lint(linter: &Linter, message: &str) -> Result<Vec<Violation>> {
+        Ok(linter.lint(&parse(message)?, message))
+    }
+
+    fn linter(types: &[&str]) -> Result<Linter> {
+        let settings = Settings {
+            lint: LintSettings {
+                ticket_reference: Some(TicketReferenceRule {
+                    pattern: r"[A-Z]+-\d+".to_string(),
+                    types: types.iter().map(|typ| typ.to_string()).collect(),
+                }),
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+
+        Linter::new(&settings)
+    }
+
+    #[test]
+    fn should_find_ticket_reference_in_scope_body_or_footer() -> Result<()> {
+        let linter = linter(&[])?;
+
+        for message in [
+            "feat(PAY-12): add refunds",
+            "feat: add refunds\n\nAs requested in PAY-12",
+            "feat: add refunds\n\nRefs: PAY-12",
+        ] {
+            assert_that!(lint(&linter, message)?).is_empty();
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_require_ticket_reference() -> Result<()> {
+        // Arrange
+        let linter = linter(&[])?;
+
+        // Act
+        let violations = lint(&linter, "feat: add refunds for PAY-12")?;
+
+        // Assert
+        assert_that!(violations).has_length(1);
+        assert_that!(violations[0].rule).is_equal_to("ticket-reference");
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_require_ticket_reference_for_selected_types() -> Result<()> {
+        let linter = linter(&["feat", "fix"])?;
+
+        assert_that!(lint(&linter, "chore: bump dependencies")?).is_empty();
+        assert_that!(lint(&linter, "fix: crash on empty cart")?).has_length(1);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_type_aliases() -> Result<()> {
+        // Arrange
+        let mut settings = Settings {
+            commit_type_aliases: [("feature".to_string(), "feat".to_string())].into(),
+            ..Settings::default()
+        };
+        let accepting = Linter::new(&settings)?;
+        settings.lint.canonical_types = true;
+        let rejecting = Linter::new(&settings)?;
+
+        // Act
+        let violations = lint(&rejecting, "feature: add refunds")?;
+
+        // Assert
+        assert_that!(lint(&accepting, "feature: add refunds")?).is_empty();
+        assert_that!(violations).has_length(1);
+        assert_that!(violations[0].message.as_str())
+            .is_equal_to("Commit type `feature` is an alias of `feat`");
+        Ok(())
+    }
+
+    #[test]
+    fn ticket_reference_types_match_aliases() -> Result<()> {
+        let settings = Settings {
+            commit_type_aliases: [("feature".to_string(), "feat".to_string())].into(),
+            ..Settings::default()
+        };
+        let linter = Linter {
+            ticket_reference: linter(&["feat"])?.ticket_reference,
+            ..Linter::new(&settings)?
+        };
+
+        assert_that!(lint(&linter, "feature: add refunds")?).has_length(1);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_banned_and_misspelled_words() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            lint: LintSettings {
+                spellcheck: true,
+                banned_words: vec!["wip".to_string()],
+                misspellings: [("featur".to_string(), "feature".to_string())].into(),
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+
+        // Act
+        let violations = lint(&linter, "feat(WIP): recieve featur, WIP")?;
+
+        // Assert
+        assert_that!(violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>())
+        .is_equal_to(vec![
+            "Misspelled word `recieve` in the summary, did you mean `receive`? (spelling)"
+                .to_string(),
+            "Misspelled word `featur` in the summary, did you mean `feature`? (spelling)"
+                .to_string(),
+            "Banned word `WIP` in the summary (banned-word)".to_string(),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_highlight_offending_words() {
+        let violations = vec![Violation {
+            rule: "banned-word",
+            message: "Banned word `wip` in the summary".to_string(),
+            word: Some("wip".to_string()),
+            severity: Severity::Error,
+        }];
+
+        let highlighted = highlight("feat: wip, don't merge", &violations);
+
+        let expected = format!("feat: {}, don't merge", "wip".red().bold());
+        assert_that!(highlighted).is_equal_to(expected);
+    }
+
+    #[test]
+    fn should_require_imperative_mood() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            lint: LintSettings {
+                imperative_mood: true,
+                non_imperative_verbs: vec!["tweaked".to_string()],
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+
+        // Act
+        let added = lint(&linter, "feat: Added refunds")?;
+        let tweaked = lint(&linter, "fix: tweaked rounding")?;
+
+        // Assert
+        assert_that!(added[0].message.as_str())
+            .is_equal_to("Summary starts with `Added`, use the imperative mood: `add`");
+        assert_that!(tweaked[0].message.as_str())
+            .is_equal_to("Summary starts with `tweaked`, use the imperative mood");
+        assert_that!(lint(&linter, "fix: split the added lines")?).is_empty();
+        Ok(())
+    }
+
+    #[test]
+    fn should_require_blank_line_after_summary() -> Result<()> {
+        let settings = Settings {
+            lint: LintSettings {
+                blank_line_after_summary: true,
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+
+        let violations = lint(&linter, "feat: add refunds\nRefunds are partial")?;
+
+        assert_that!(violations).has_length(1);
+        assert_that!(violations[0].rule).is_equal_to("blank-line-after-summary");
+        assert_that!(lint(&linter, "feat: add refunds\n\nRefunds are partial")?).is_empty();
+        assert_that!(lint(&linter, "feat: add refunds")?).is_empty();
+        Ok(())
+    }
+
+    #[test]
+    fn should_limit_body_line_length_except_urls() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            lint: LintSettings {
+                body_max_line_length: Some(20),
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+        let message = indoc! {"
+            feat: add refunds to the checkout page
+
+            Refunds are partial for now.
+            See https://example.org/a/very/long/url
+        "};
+
+        // Act
+        let violations = lint(&linter, message)?;
+
+        // Assert
+        assert_that!(violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>())
+        .is_equal_to(vec![
+            "Line 3 is 28 characters long, the maximum is 20 (body-line-length)".to_string(),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_apply_configured_severities() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            lint: LintSettings {
+                spellcheck: true,
+                banned_words: vec!["wip".to_string()],
+                severities: [
+                    ("spelling".to_string(), Severity::Warning),
+                    ("banned-word".to_string(), Severity::Off),
+                ]
+                .into(),
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+
+        // Act
+        let violations = lint(&linter, "fix: recieve webhooks, wip")?;
+
+        // Assert
+        assert_that!(violations).has_length(1);
+        assert_that!(violations[0].rule).is_equal_to("spelling");
+        assert_that!(violations[0].severity).is_equal_to(Severity::Warning);
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_failing_external_commands() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            lint: LintSettings {
+                external: vec![
+                    "true".to_string(),
+                    "grep -q Signed-off-by || { echo 'Missing sign-off' >&2; exit 1; }".to_string(),
+                    "exit 3".to_string(),
+                ],
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+
+        // Act
+        let violations = lint(&linter, "feat: add refunds")?;
+
+        // Assert
+        assert_that!(violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>())
+        .is_equal_to(vec![
+            "Missing sign-off (external)".to_string(),
+            "`exit 3` exited with exit status: 3 (external)".to_string(),
+        ]);
+        assert_that!(lint(&linter, "feat: add refunds\n\nSigned-off-by: Tom")?).has_length(1);
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_check_staged_scope_when_authoring() -> Result<()> {
+        // Arrange
+        let settings = Settings {
+            lint: LintSettings {
+                scope_paths: [("api".to_string(), vec!["services/api/**".to_string()])].into(),
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+        let linter = Linter::new(&settings)?;
+        let authoring = linter
+            .clone()
+            .with_staged_files(vec!["docs/refunds.md".to_string()]);
+
+        // Act
+        let violations = lint(&authoring, "feat(api): add refunds")?;
+
+        // Assert
+        assert_that!(lint(&linter, "feat(api): add refunds")?).is_empty();
+        assert_that!(lint(&authoring, "docs: add refunds")?).is_empty();
+        assert_that!(violations).has_length(1);
+        assert_that!(violations[0].message.as_str())
+            .is_equal_to("No staged file belongs to scope `api`");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_scope_glob_is_an_error() {
+        let settings = Settings {
+            lint: LintSettings {
+                scope_paths: [("api".to_string(), vec!["services/{api".to_string()])].into(),
+                ..LintSettings::default()
+            },
+            ..Settings::default()
+        };
+
+        assert_that!(Linter::new(&settings)).is_err();
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use semver::Version;
+
+/// Read the version declared in a manifest, used as the current version until the first tag.
+/// `Cargo.toml`, `package.json` and `pyproject.toml` are parsed, any other file is expected to
+/// contain the version alone, like a `VERSION` file.
+pub(crate) fn read_manifest_version(path: &Path) -> Result<Version> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read version source {:?}", path))?;
+    parse_manifest_version(path, &content)
+}
+
+/// Parse the version declared in `content`, the manifest format being guessed from `path`
+/// like [`read_manifest_version`] does
+pub(crate) fn parse_manifest_version(path: &Path, content: &str) -> Result<Version> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    let version = match file_name {
+        "Cargo.toml" => {
+            let manifest: toml::Value = toml::from_str(content)?;
+            toml_string(&manifest, &["package", "version"])
+                .or_else(|| toml_string(&manifest, &["workspace", "package", "version"]))
+        }
+        "pyproject.toml" => {
+            let manifest: toml::Value = toml::from_str(content)?;
+            toml_string(&manifest, &["project", "version"])
+                .or_else(|| toml_string(&manifest, &["tool", "poetry", "version"]))
+        }
+        "package.json" => {
+            let manifest: serde_json::Value = serde_json::from_str(content)?;
+            manifest["version"].as_str().map(str::to_string)
+        }
+        _ => Some(content.trim().to_string()),
+    };
+
+    let version = version.ok_or_else(|| anyhow!("no version found in {:?}", path))?;
+    Version::parse(&version).with_context(|| format!("invalid version '{}' in {:?}", version, path))
+}
+
+fn toml_string(manifest: &toml::Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .try_fold(manifest, |value, key| value.get(key))
+        .and_then(toml::Value::as_str)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::manifest::read_manifest_version;
+    use anyhow::Result;
+    use sealed_test::prelude::*;
+    use semver::Version;
+    use speculoos::prelude::*;
+    use std::fs;
+    use std::path::Path;
+
+    #[sealed_test]
+    fn read_version_from_manifests() -> Result<()> {
+        // Arrange
+        fs::write("Cargo.toml", "[workspace.package]\nversion = \"1.2.3\"")?;
+        fs::write("pyproject.toml", "[tool.poetry]\nversion = \"2.0.0\"")?;
+        fs::write(
+            "package.json",
+            r#"{ "name": "app", "version": "3.1.0-rc.1" }"#,
+        )?;
+        fs::write("VERSION", "4.0.1\n")?;
+
+        // Act
+        let cargo = read_manifest_version(Path::new("Cargo.toml"))?;
+        let pyproject = read_manifest_version(Path::new("pyproject.toml"))?;
+        let package = read_manifest_version(Path::new("package.json"))?;
+        let version = read_manifest_version(Path::new("VERSION"))?;
+
+        // Assert
+        assert_that!(cargo).is_equal_to(Version::new(1, 2, 3));
+        assert_that!(pyproject).is_equal_to(Version::new(2, 0, 0));
+        assert_that!(package).is_equal_to(Version::parse("3.1.0-rc.1")?);
+        assert_that!(version).is_equal_to(Version::new(4, 0, 1));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn manifest_without_version_fails() -> Result<()> {
+        // Arrange
+        fs::write("Cargo.toml", "[package]\nname = \"cog\"")?;
+
+        // Act
+        let version = read_manifest_version(Path::new("Cargo.toml"));
+
+        // Assert
+        assert_that!(version).is_err();
+        Ok(())
+    }
+}
@@ -1,4 +1,15 @@
+#[cfg(feature = "git")]
+pub(crate) mod cache;
+#[cfg(feature = "git")]
 pub mod changelog;
 pub mod commit;
-pub(crate) mod error;
+pub mod diagnostic;
+pub mod error;
+#[cfg(feature = "git")]
+pub(crate) mod hint;
+pub mod lint;
+#[cfg(feature = "git")]
+pub(crate) mod manifest;
+#[cfg(feature = "git")]
+pub mod rewrite;
 pub mod version;
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::settings::RewriteRule;
+
+/// The `import_history` rules, compiled
+#[derive(Debug)]
+pub(crate) struct Rewriter {
+    rules: Vec<(Regex, String)>,
+}
+
+/// A commit message rewritten by `cog import-history`
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct RewrittenCommit {
+    /// The id of the commit before it was rewritten
+    pub oid: String,
+    pub original: String,
+    pub message: String,
+}
+
+impl Rewriter {
+    pub(crate) fn new(rules: &[RewriteRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let regex = Regex::new(&rule.pattern).with_context(|| {
+                    format!("invalid import_history rule pattern `{}`", rule.pattern)
+                })?;
+                Ok((regex, rule.template.clone()))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Rewriter { rules })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Rewrite `message` with the first matching rule, `None` when no rule matches
+    pub(crate) fn rewrite(&self, message: &str) -> Option<String> {
+        let (regex, template) = self
+            .rules
+            .iter()
+            .find(|(regex, _)| regex.is_match(message))?;
+        let message = regex.replacen(message, 1, template.as_str());
+        Some(message.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::rewrite::Rewriter;
+    use crate::settings::RewriteRule;
+
+    use anyhow::Result;
+    use speculoos::prelude::*;
+
+    fn rule(pattern: &str, template: &str) -> RewriteRule {
+        RewriteRule {
+            pattern: pattern.to_string(),
+            template: template.to_string(),
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_rewrites() -> Result<()> {
+        // Arrange
+        let rewriter = Rewriter::new(&[
+            rule(r"^\[FIX\] ", "fix: "),
+            rule(
+                r"^\[(?P<module>\w+)\] (?P<summary>.*)",
+                "feat(${module}): ${summary}",
+            ),
+        ])?;
+
+        // Act
+        let fix = rewriter.rewrite("[FIX] handle empty input\n\nThe body\n");
+        let feat = rewriter.rewrite("[parser] add arrays");
+        let unmatched = rewriter.rewrite("Update README");
+
+        // Assert
+        assert_that!(fix).contains_value("fix: handle empty input\n\nThe body".to_string());
+        assert_that!(feat).contains_value("feat(parser): add arrays".to_string());
+        assert_that!(unmatched).is_none();
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        let rewriter = Rewriter::new(&[rule("[FIX", "fix: ")]);
+
+        assert_that!(rewriter).is_err();
+    }
+}
@@ -1,37 +1,84 @@
 use crate::conventional::commit::Commit;
-use crate::git::repository::Repository;
-use std::fmt;
 
 use crate::conventional::error::BumpError;
-use crate::git::revspec::RevspecPattern;
-use colored::*;
 use conventional_commit_parser::commit::CommitType;
-use git2::Commit as Git2Commit;
-use itertools::Itertools;
-use log::info;
+use schemars::JsonSchema;
 use semver::Version;
-use std::fmt::Write;
-
-#[derive(Debug, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "git")]
+use {
+    crate::git::repository::Repository, crate::git::revspec::RevspecPattern, crate::reporter::info,
+    crate::CommitsMetadata, colored::*, git2::Commit as Git2Commit, itertools::Itertools, std::fmt,
+    std::fmt::Write,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VersionIncrement {
     Major,
     Minor,
     Patch,
     Auto,
     Manual(String),
+    /// Graduate a 0.x version, or a 1.0.0 pre-release, to 1.0.0
+    Stabilize,
+}
+
+/// The SemVer component incremented by a bump, as computed from the current and next versions
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Increment {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl Increment {
+    pub fn between(current: &Version, next: &Version) -> Self {
+        if next.major != current.major {
+            Increment::Major
+        } else if next.minor != current.minor {
+            Increment::Minor
+        } else {
+            Increment::Patch
+        }
+    }
 }
 
 impl VersionIncrement {
+    #[cfg(feature = "git")]
     pub(crate) fn bump(
         &self,
         current_version: &Version,
         repository: &Repository,
+        commit_types: &CommitsMetadata,
+    ) -> Result<Version, BumpError> {
+        match self {
+            VersionIncrement::Auto => VersionIncrement::create_version_from_commit_history(
+                current_version,
+                repository,
+                commit_types,
+            ),
+            _ => self.bump_from_commits(current_version, &[]),
+        }
+    }
+
+    /// The version following `current_version`. `Auto` increments are computed from `commits`,
+    /// as `cog bump --auto` does with the commits since the latest tag
+    pub fn bump_from_commits(
+        &self,
+        current_version: &Version,
+        commits: &[Commit],
     ) -> Result<Version, BumpError> {
         match self {
             VersionIncrement::Manual(version) => Version::parse(version).map_err(Into::into),
             VersionIncrement::Auto => {
-                VersionIncrement::create_version_from_commit_history(current_version, repository)
+                VersionIncrement::version_increment_from_commit_history(current_version, commits)?
+                    .bump_from_commits(current_version, commits)
+            }
+            VersionIncrement::Stabilize if is_unstable(current_version) => {
+                Ok(Version::new(1, 0, 0))
             }
+            VersionIncrement::Stabilize => Err(BumpError::AlreadyStable(current_version.clone())),
             VersionIncrement::Major => Ok(Version::new(current_version.major + 1, 0, 0)),
             VersionIncrement::Patch => Ok(Version::new(
                 current_version.major,
@@ -46,9 +93,11 @@ impl VersionIncrement {
         }
     }
 
+    #[cfg(feature = "git")]
     fn create_version_from_commit_history(
         current_version: &Version,
         repository: &Repository,
+        commit_types: &CommitsMetadata,
     ) -> Result<Version, BumpError> {
         let changelog_start_oid = repository
             .get_latest_tag_oid()
@@ -70,20 +119,15 @@ impl VersionIncrement {
             .filter(|commit| !commit.message().unwrap_or("").starts_with("Merge "))
             .collect();
 
-        VersionIncrement::display_history(&commits)?;
+        VersionIncrement::display_history(&commits, commit_types)?;
 
         let conventional_commits: Vec<Commit> = commits
             .iter()
-            .map(|commit| Commit::from_git_commit(commit))
+            .map(|commit| Commit::from_git_commit(commit, commit_types))
             .filter_map(Result::ok)
             .collect();
 
-        let increment_type = VersionIncrement::version_increment_from_commit_history(
-            current_version,
-            &conventional_commits,
-        )?;
-
-        increment_type.bump(current_version, repository)
+        VersionIncrement::Auto.bump_from_commits(current_version, &conventional_commits)
     }
 
     fn version_increment_from_commit_history(
@@ -120,10 +164,14 @@ impl VersionIncrement {
         }
     }
 
-    fn display_history(commits: &[&Git2Commit]) -> Result<(), fmt::Error> {
+    #[cfg(feature = "git")]
+    fn display_history(
+        commits: &[&Git2Commit],
+        commit_types: &CommitsMetadata,
+    ) -> Result<(), fmt::Error> {
         let conventional_commits: Vec<Result<_, _>> = commits
             .iter()
-            .map(|commit| Commit::from_git_commit(commit))
+            .map(|commit| Commit::from_git_commit(commit, commit_types))
             .collect();
 
         // Commits which type are neither feat, fix nor breaking changes
@@ -187,6 +235,15 @@ impl VersionIncrement {
     }
 }
 
+// Initial development versions, and pre-releases of 1.0.0
+fn is_unstable(version: &Version) -> bool {
+    version.major == 0
+        || (version.major == 1
+            && version.minor == 0
+            && version.patch == 0
+            && !version.pre.is_empty())
+}
+
 #[cfg(test)]
 // Auto version tests resides in test/ dir since it rely on git log
 // To generate the version
@@ -194,10 +251,11 @@ mod test {
     use std::str::FromStr;
 
     use crate::conventional::commit::Commit;
-    use crate::conventional::version::VersionIncrement;
+    use crate::conventional::version::{Increment, VersionIncrement};
 
-    use crate::Repository;
-    use anyhow::Result;
+    use crate::settings::Settings;
+    use crate::{CommitsMetadata, Repository};
+    use anyhow::{anyhow, Result};
     use chrono::Utc;
     use conventional_commit_parser::commit::{CommitType, ConventionalCommit};
     use pretty_assertions::assert_eq;
@@ -230,13 +288,37 @@ mod test {
         let base_version = Version::new(1, 0, 0);
 
         // Act
-        let version = VersionIncrement::Major.bump(&base_version, &repository)?;
+        let version =
+            VersionIncrement::Major.bump(&base_version, &repository, &CommitsMetadata::new())?;
 
         // Assert
         assert_that!(version).is_equal_to(Version::new(2, 0, 0));
         Ok(())
     }
 
+    #[test]
+    fn stabilize_initial_development_version() -> Result<()> {
+        // Arrange
+        let base_version = Version::new(0, 14, 3);
+        let rc = Version::parse("1.0.0-rc.2")?;
+
+        // Act
+        let version = VersionIncrement::Stabilize.bump_from_commits(&base_version, &[])?;
+        let from_rc = VersionIncrement::Stabilize.bump_from_commits(&rc, &[])?;
+
+        // Assert
+        assert_that!(version).is_equal_to(Version::new(1, 0, 0));
+        assert_that!(from_rc).is_equal_to(Version::new(1, 0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn stabilize_fails_on_stable_version() {
+        let result = VersionIncrement::Stabilize.bump_from_commits(&Version::new(1, 2, 0), &[]);
+
+        assert_that!(result).is_err();
+    }
+
     #[sealed_test]
     fn minor_bump() -> Result<()> {
         // Arrange
@@ -244,7 +326,8 @@ mod test {
 
         // Act
         let base_version = Version::new(1, 0, 0);
-        let version = VersionIncrement::Minor.bump(&base_version, &repository)?;
+        let version =
+            VersionIncrement::Minor.bump(&base_version, &repository, &CommitsMetadata::new())?;
 
         // Assert
         assert_that!(version).is_equal_to(Version::new(1, 1, 0));
@@ -258,7 +341,8 @@ mod test {
         let base_version = Version::new(1, 0, 0);
 
         // Act
-        let version = VersionIncrement::Patch.bump(&base_version, &repository)?;
+        let version =
+            VersionIncrement::Patch.bump(&base_version, &repository, &CommitsMetadata::new())?;
 
         // Assert
         assert_that!(version).is_equal_to(Version::new(1, 0, 1));
@@ -272,7 +356,7 @@ mod test {
         let version = Version::from_str("1.1.1")?;
 
         // Act
-        let bumped = VersionIncrement::Minor.bump(&version, &repository);
+        let bumped = VersionIncrement::Minor.bump(&version, &repository, &CommitsMetadata::new());
 
         // Assert
         assert_that!(bumped)
@@ -289,7 +373,7 @@ mod test {
         let version = Version::from_str("1.1.1")?;
 
         // Act
-        let bumped = VersionIncrement::Major.bump(&version, &repository);
+        let bumped = VersionIncrement::Major.bump(&version, &repository, &CommitsMetadata::new());
 
         // Assert
         assert_that!(bumped)
@@ -306,7 +390,7 @@ mod test {
         let version = Version::from_str("1.1.1-pre+10.1")?;
 
         // Act
-        let bumped = VersionIncrement::Patch.bump(&version, &repository);
+        let bumped = VersionIncrement::Patch.bump(&version, &repository, &CommitsMetadata::new());
 
         // Assert
         assert_that!(bumped)
@@ -426,4 +510,42 @@ suggestion: Please see https://conventionalcommits.org/en/v1.0.0/#summary for mo
 
         Ok(())
     }
+
+    #[test]
+    fn increment_between_versions() {
+        let current = Version::new(1, 2, 3);
+
+        assert_that!(Increment::between(&current, &Version::new(2, 0, 0)))
+            .is_equal_to(Increment::Major);
+        assert_that!(Increment::between(&current, &Version::new(1, 3, 0)))
+            .is_equal_to(Increment::Minor);
+        assert_that!(Increment::between(&current, &Version::new(1, 2, 4)))
+            .is_equal_to(Increment::Patch);
+        assert_that!(Increment::between(
+            &current,
+            &Version::parse("1.2.4-alpha").unwrap()
+        ))
+        .is_equal_to(Increment::Patch);
+    }
+
+    #[test]
+    fn auto_bump_from_parsed_commits() -> Result<()> {
+        // Arrange
+        let commit_types = Settings::default().commit_types();
+        let date = Utc::now().naive_utc();
+        let commits = ["chore: release", "fix: a bug", "feat(parser): a feature"]
+            .into_iter()
+            .map(|message| {
+                Commit::parse("1234".into(), message, "".into(), date, &commit_types)
+                    .map_err(|err| anyhow!("{}", err))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Act
+        let version = VersionIncrement::Auto.bump_from_commits(&Version::new(1, 0, 0), &commits)?;
+
+        // Assert
+        assert_that!(version).is_equal_to(Version::new(1, 1, 0));
+        Ok(())
+    }
 }
@@ -0,0 +1,154 @@
+//! HTTP requests sent with curl. The whole request, credentials and body included, is written
+//! to curl's stdin as a config file, so no secret ever appears on its command line where any
+//! local user could read it.
+
+use std::fmt::Write as _;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{ensure, Context, Result};
+
+// A hung server must not block a release
+const MAX_TIME_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct CurlRequest<'a> {
+    method: &'a str,
+    url: &'a str,
+    headers: Vec<String>,
+    user: Option<String>,
+    body: Option<String>,
+}
+
+impl<'a> CurlRequest<'a> {
+    pub(crate) fn new(method: &'a str, url: &'a str) -> Self {
+        CurlRequest {
+            method,
+            url,
+            headers: vec![],
+            user: None,
+            body: None,
+        }
+    }
+
+    pub(crate) fn header(mut self, header: impl Into<String>) -> Self {
+        self.headers.push(header.into());
+        self
+    }
+
+    /// Basic authentication, written `user:password`
+    pub(crate) fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub(crate) fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Send the request and return the response body, failing on HTTP errors
+    pub(crate) fn send(&self) -> Result<Vec<u8>> {
+        let curl = which::which("curl").context("Cannot find curl to send HTTP requests")?;
+        let mut child = Command::new(curl)
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let write_result = child
+            .stdin
+            .take()
+            .expect("curl stdin is piped")
+            .write_all(self.config().as_bytes());
+
+        let output = child.wait_with_output()?;
+        write_result?;
+        ensure!(
+            output.status.success(),
+            "{} request to {} failed with {}",
+            self.method,
+            self.url,
+            output.status
+        );
+
+        Ok(output.stdout)
+    }
+
+    // The curl config file describing the request
+    fn config(&self) -> String {
+        let mut config = String::new();
+        let mut option = |name: &str, value: &str| {
+            writeln!(config, "{} = \"{}\"", name, escape(value)).unwrap();
+        };
+
+        option("url", self.url);
+        option("request", self.method);
+        option("max-time", &MAX_TIME_SECS.to_string());
+        for header in &self.headers {
+            option("header", header);
+        }
+
+        if let Some(user) = &self.user {
+            option("user", user);
+        }
+
+        // Unlike `data-binary`, a body starting with `@` is not read as a file name
+        if let Some(body) = &self.body {
+            option("data-raw", body);
+        }
+
+        config.push_str("silent\nshow-error\nfail\n");
+        config
+    }
+}
+
+// Escape a value written between double quotes in a curl config file
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use crate::curl::CurlRequest;
+
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_write_request_as_config() {
+        // Arrange
+        let request = CurlRequest::new("POST", "https://example.org/hook")
+            .header("Authorization: Bearer secret")
+            .user("bot:token")
+            .body("{\"text\": \"line\\\\one\nline two\"}");
+
+        // Act
+        let config = request.config();
+
+        // Assert
+        assert_that!(config.as_str()).is_equal_to(indoc! {r#"
+            url = "https://example.org/hook"
+            request = "POST"
+            max-time = "60"
+            header = "Authorization: Bearer secret"
+            user = "bot:token"
+            data-raw = "{\"text\": \"line\\\\one\nline two\"}"
+            silent
+            show-error
+            fail
+        "#});
+    }
+}
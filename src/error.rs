@@ -1,59 +1,230 @@
 use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
 
+use crate::conventional::changelog::error::ChangelogError;
+use crate::conventional::error::{BumpError, ConventionalCommitError};
+use crate::git::error::{Git2Error, TagError};
 use crate::git::oid::OidOf;
+use crate::git::status::Statuses;
+use crate::hook::HookPolicy;
+use crate::settings::error::SettingError;
 
-use crate::conventional::error::ConventionalCommitError;
 use colored::*;
+use conventional_commit_parser::error::ParseError;
+use semver::Version;
+use thiserror::Error;
+
+/// Errors returned by the public [`CocoGitto`](crate::CocoGitto) API
+#[derive(Debug, Error)]
+pub enum CocogittoError {
+    /// Some commits in the checked range do not follow the conventional commit specification
+    #[error("{0}")]
+    NotConventional(Box<CogCheckReport>),
+    /// A single commit or message does not follow the conventional commit specification
+    #[error(transparent)]
+    InvalidCommit(#[from] Box<ConventionalCommitError>),
+    #[error("unable to get any tag")]
+    NoTag,
+    #[error("{0}")]
+    DirtyRepository(Statuses),
+    #[error("No patterns matched in {patterns:?} for branch '{branch}', {action} is not allowed")]
+    BranchNotAllowed {
+        branch: String,
+        patterns: Vec<String>,
+        /// `bump` or `commit`
+        action: &'static str,
+    },
+    #[error(
+        "Branch '{branch}' matches the denied pattern '{pattern}', {action} is not allowed\n\
+        \tuse `--ignore-branch-protection` to {action} anyway"
+    )]
+    BranchDenied {
+        branch: String,
+        pattern: String,
+        action: &'static str,
+    },
+    #[error("version {next} is out of the maintenance line of branch '{branch}', versions must be lower than {ceiling}")]
+    MaintenanceCeilingExceeded {
+        branch: String,
+        next: Version,
+        ceiling: Version,
+    },
+    #[error(
+        "tag {tag} already exists{}\n\tchoose another version with `--version` or `--pre`, \
+        or move the tag with `--force-tag`",
+        remote.as_ref().map(|remote| format!(" on remote {}", remote)).unwrap_or_default()
+    )]
+    TagAlreadyExists {
+        tag: String,
+        /// The remote having the tag, `None` when it exists locally
+        remote: Option<String>,
+    },
+    #[error(
+        "branch '{branch}' is {behind} commit(s) behind '{upstream}'{}, \
+        pull the remote changes before bumping",
+        if *ahead > 0 { format!(" and {} commit(s) ahead", ahead) } else { String::new() }
+    )]
+    BranchNotUpToDate {
+        branch: String,
+        upstream: String,
+        ahead: usize,
+        behind: usize,
+    },
+    #[error(
+        "{} breaking change(s) since the latest version would ship in 1.0.0:\n{}\n\
+        \treview them, then stabilize with `--acknowledge-breaking-changes`",
+        commits.len(),
+        commits.iter().map(|commit| format!("\t- {}", commit)).collect::<Vec<_>>().join("\n")
+    )]
+    UnacknowledgedBreakingChanges {
+        /// The short id and summary of each breaking commit
+        commits: Vec<String>,
+    },
+    #[error("cannot bump a bare repository, a working tree is required")]
+    BareRepository,
+    #[error(
+        "{}:\n\t{} version MUST be greater than current one: {}\n",
+        "SemVer Error".red(),
+        "cause:".red(),
+        format!("{} <= {}", current, next).red()
+    )]
+    SemverViolation { current: Version, next: Version },
+    #[error("hook `{command}` failed: {cause}")]
+    HookFailed {
+        command: String,
+        policy: HookPolicy,
+        // Part of the message, not a source, so that it is not reported twice
+        cause: anyhow::Error,
+    },
+    #[error(transparent)]
+    PreHook(#[from] PreHookError),
+    #[error(transparent)]
+    Git(#[from] Git2Error),
+    #[error(transparent)]
+    Tag(TagError),
+    #[error(transparent)]
+    Bump(#[from] BumpError),
+    #[error(transparent)]
+    Changelog(#[from] ChangelogError),
+    #[error(transparent)]
+    Settings(#[from] SettingError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl From<TagError> for CocogittoError {
+    fn from(err: TagError) -> Self {
+        match err {
+            TagError::NoTag => CocogittoError::NoTag,
+            err => CocogittoError::Tag(err),
+        }
+    }
+}
+
+impl From<git2::Error> for CocogittoError {
+    fn from(err: git2::Error) -> Self {
+        CocogittoError::Git(Git2Error::from(err))
+    }
+}
+
+impl From<ParseError> for CocogittoError {
+    fn from(err: ParseError) -> Self {
+        CocogittoError::InvalidCommit(Box::new(ConventionalCommitError::ParseError(err)))
+    }
+}
+
+impl From<tera::Error> for CocogittoError {
+    fn from(err: tera::Error) -> Self {
+        CocogittoError::Changelog(ChangelogError::from(err))
+    }
+}
+
+// Typed errors raised behind an `anyhow` context are recovered rather than wrapped again
+impl From<anyhow::Error> for CocogittoError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<CocogittoError>() {
+            Ok(err) => err,
+            Err(err) => CocogittoError::Other(err),
+        }
+    }
+}
 
 #[derive(Debug)]
-pub(crate) struct CogCheckReport {
+pub struct CogCheckReport {
     pub from: OidOf,
     pub errors: Vec<ConventionalCommitError>,
+    /// Commits only violating lint rules configured as warnings
+    pub warnings: Vec<ConventionalCommitError>,
 }
 
 impl Display for CogCheckReport {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let header = format!(
-            "\nFound {} non compliant commits in {}..HEAD:\n",
-            self.errors.len(),
-            self.from
-        )
-        .red()
-        .bold();
+        if !self.errors.is_empty() {
+            let header = format!(
+                "\nFound {} non compliant commits in {}..HEAD:\n",
+                self.errors.len(),
+                self.from
+            )
+            .red()
+            .bold();
 
-        writeln!(f, "{}", header)?;
+            writeln!(f, "{}", header)?;
+            write_commits(f, &self.errors)?;
+        }
+
+        if !self.warnings.is_empty() {
+            let header = format!(
+                "\nFound {} commits with warnings in {}..HEAD:\n",
+                self.warnings.len(),
+                self.from
+            )
+            .yellow()
+            .bold();
 
-        for err in &self.errors {
-            let underline = format!("{:>57}", " ").underline();
-            writeln!(f, "{:>5}\n", underline)?;
-            write!(f, "{}", err)?;
+            writeln!(f, "{}", header)?;
+            write_commits(f, &self.warnings)?;
         }
+
         Ok(())
     }
 }
 
-// This is not meant to be unwrapped like other errors
-// just to emit a warning on hook failure
-pub(crate) struct PreHookError {
+fn write_commits(f: &mut Formatter<'_>, commits: &[ConventionalCommitError]) -> fmt::Result {
+    for err in commits {
+        let underline = format!("{:>57}", " ").underline();
+        writeln!(f, "{:>5}\n", underline)?;
+        write!(f, "{}", err)?;
+    }
+
+    Ok(())
+}
+
+/// A failed pre-bump hook, once the changes made by the hooks have been stashed
+#[derive(Debug)]
+pub struct PreHookError {
     pub(crate) cause: String,
     pub(crate) version: String,
     pub(crate) stash_number: u32,
+    pub(crate) policy: Option<HookPolicy>,
 }
 
 impl fmt::Display for PreHookError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let header = format!(
-            "Error: {} `{}` {}",
-            "prehook run".red(),
-            self.cause,
-            "failed".red()
-        );
+        let header = format!("{}: {}", "prehook run failed".red(), self.cause);
         let stash_ref = format!("`cog_bump_{}`", self.version);
         let suggestion = format!(
             "\tAll changes made during hook runs have been stashed on {}\n\
         \tyou can run `git stash apply stash@{}` to restore these changes.",
             stash_ref, self.stash_number
         );
-        write!(f, "{}\n{}", header, suggestion)
+        writeln!(f, "{}", header)?;
+        if let Some(policy) = &self.policy {
+            writeln!(f, "\thook policy: {}", policy)?;
+        }
+        write!(f, "{}", suggestion)
     }
 }
+
+impl std::error::Error for PreHookError {}
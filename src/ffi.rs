@@ -0,0 +1,249 @@
+//! C bindings, enabled with the `ffi` feature and declared in `include/cocogitto.h`.
+//!
+//! Every function returns `0` on success and `-1` on failure, in which case
+//! [`cog_last_error`] describes what went wrong. Strings written to `out` are
+//! owned by the caller and must be released with [`cog_string_free`].
+
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+
+use crate::conventional::diagnostic::diagnose;
+use crate::git::revspec::RevspecPattern;
+use crate::settings::Settings;
+use crate::CocoGitto;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+const OK: c_int = 0;
+const INVALID: c_int = 1;
+const ERROR: c_int = -1;
+
+/// Check `message` against the commit types configured in the repository at `repo_path`,
+/// or the default ones when `repo_path` is null.
+///
+/// Returns `0` when the message is valid and `1` when it is not, in which case `out`
+/// receives the diagnostic as JSON.
+///
+/// # Safety
+///
+/// `message` and `repo_path`, unless null, must be valid nul-terminated strings and
+/// `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn cog_verify_message(
+    repo_path: *const c_char,
+    message: *const c_char,
+    out: *mut *mut c_char,
+) -> c_int {
+    let result = (|| {
+        let message = to_str(message, "message")?;
+        let settings = match optional_str(repo_path, "repo_path")? {
+            Some(path) => CocoGitto::open(path)?.settings().clone(),
+            None => Settings::default(),
+        };
+
+        diagnose(message, &settings.commit_types())
+            .map(|diagnostic| serde_json::to_string(&diagnostic))
+            .transpose()
+            .map_err(Into::into)
+    })();
+
+    match result {
+        Ok(None) => OK,
+        Ok(Some(diagnostic)) => write_out(out, diagnostic).map_or(ERROR, |_| INVALID),
+        Err(err) => set_last_error(err),
+    }
+}
+
+/// Write the version `cog bump --auto` would create in the repository at `repo_path`
+/// to `out`, tag prefix included.
+///
+/// # Safety
+///
+/// `repo_path` must be a valid nul-terminated string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn cog_next_version(
+    repo_path: *const c_char,
+    out: *mut *mut c_char,
+) -> c_int {
+    let result = (|| {
+        let cocogitto = CocoGitto::open(to_str(repo_path, "repo_path")?)?;
+        let version = cocogitto.next_auto_version()?;
+        Ok(cocogitto.prefixed_version(&version))
+    })();
+
+    finish(result, out)
+}
+
+/// Write the changelog of the repository at `repo_path` for the revision range `range`
+/// to `out`, rendered with the configured template.
+/// A null `range` renders everything since the latest tag.
+///
+/// # Safety
+///
+/// `repo_path` and `range`, unless null, must be valid nul-terminated strings and
+/// `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn cog_render_changelog(
+    repo_path: *const c_char,
+    range: *const c_char,
+    out: *mut *mut c_char,
+) -> c_int {
+    let result = (|| {
+        let cocogitto = CocoGitto::open(to_str(repo_path, "repo_path")?)?;
+        let pattern = optional_str(range, "range")?
+            .map(RevspecPattern::from)
+            .unwrap_or_default();
+        let template = cocogitto
+            .settings()
+            .get_changelog_template()?
+            .with_repository(cocogitto.repository_context()?);
+        let changelog = cocogitto.get_changelog(pattern, true)?;
+        Ok(changelog.into_markdown(template)?)
+    })();
+
+    finish(result, out)
+}
+
+/// The error of the last failed call on this thread, or null.
+/// The string is owned by the library and valid until the next call on this thread.
+#[no_mangle]
+pub extern "C" fn cog_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |err| err.as_ptr())
+    })
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+///
+/// `string` must be null or a string written to `out` by one of the functions above,
+/// not already released.
+#[no_mangle]
+pub unsafe extern "C" fn cog_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+unsafe fn to_str<'a>(string: *const c_char, name: &str) -> anyhow::Result<&'a str> {
+    optional_str(string, name)?.ok_or_else(|| anyhow::anyhow!("{} must not be null", name))
+}
+
+unsafe fn optional_str<'a>(string: *const c_char, name: &str) -> anyhow::Result<Option<&'a str>> {
+    if string.is_null() {
+        return Ok(None);
+    }
+
+    CStr::from_ptr(string)
+        .to_str()
+        .map(Some)
+        .map_err(|_| anyhow::anyhow!("{} is not valid UTF-8", name))
+}
+
+unsafe fn finish(result: anyhow::Result<String>, out: *mut *mut c_char) -> c_int {
+    match result.and_then(|value| write_out(out, value)) {
+        Ok(()) => OK,
+        Err(err) => set_last_error(err),
+    }
+}
+
+unsafe fn write_out(out: *mut *mut c_char, value: String) -> anyhow::Result<()> {
+    if out.is_null() {
+        anyhow::bail!("out must not be null");
+    }
+
+    *out = CString::new(value)?.into_raw();
+    Ok(())
+}
+
+fn set_last_error(err: anyhow::Error) -> c_int {
+    let message = format!("{:#}", err).replace('\0', "");
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(message).ok());
+    ERROR
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::{c_char, CStr, CString};
+    use std::ptr;
+
+    use anyhow::Result;
+    use cmd_lib::run_cmd;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    use crate::ffi::*;
+
+    unsafe fn take(string: *mut c_char) -> String {
+        let value = CStr::from_ptr(string).to_string_lossy().into_owned();
+        cog_string_free(string);
+        value
+    }
+
+    #[test]
+    fn verify_valid_message() {
+        let message = CString::new("feat: add the C bindings").unwrap();
+        let mut out = ptr::null_mut();
+
+        let status = unsafe { cog_verify_message(ptr::null(), message.as_ptr(), &mut out) };
+
+        assert_that!(status).is_equal_to(0);
+        assert_that!(out.is_null()).is_true();
+    }
+
+    #[test]
+    fn verify_invalid_message_writes_the_diagnostic() {
+        let message = CString::new("faet: add the C bindings").unwrap();
+        let mut out = ptr::null_mut();
+
+        let status = unsafe { cog_verify_message(ptr::null(), message.as_ptr(), &mut out) };
+
+        assert_that!(status).is_equal_to(1);
+        let diagnostic = unsafe { take(out) };
+        assert_that!(diagnostic).contains(r#""component":"type""#);
+    }
+
+    #[sealed_test]
+    fn next_version_and_changelog() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            git commit --allow-empty -m "chore: init";
+            git tag 1.0.0;
+            git commit --allow-empty -m "feat: add the C bindings";
+        )?;
+        let repo_path = CString::new(".")?;
+        let mut version = ptr::null_mut();
+        let mut changelog = ptr::null_mut();
+
+        // Act
+        let version_status = unsafe { cog_next_version(repo_path.as_ptr(), &mut version) };
+        let changelog_status =
+            unsafe { cog_render_changelog(repo_path.as_ptr(), ptr::null(), &mut changelog) };
+
+        // Assert
+        assert_that!(version_status).is_equal_to(0);
+        assert_that!(unsafe { take(version) }).is_equal_to("1.1.0".to_string());
+        assert_that!(changelog_status).is_equal_to(0);
+        assert_that!(unsafe { take(changelog) }).contains("- add the C bindings");
+        Ok(())
+    }
+
+    #[test]
+    fn failure_sets_the_last_error() {
+        let mut out = ptr::null_mut();
+
+        let status = unsafe { cog_next_version(ptr::null(), &mut out) };
+
+        assert_that!(status).is_equal_to(-1);
+        let error = unsafe { CStr::from_ptr(cog_last_error()) };
+        assert_that!(error.to_string_lossy().into_owned())
+            .is_equal_to("repo_path must not be null".to_string());
+    }
+}
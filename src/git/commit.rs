@@ -1,28 +1,59 @@
 use crate::git::error::Git2Error;
+use crate::git::identity::Identity;
 use crate::git::repository::Repository;
 use git2::{Commit, ObjectType, Oid, ResetType, Signature, Tree};
-use std::io::Write;
-use std::process::{Command, Stdio};
+
+/// How cog creates a commit, the git config identity is used unless overridden
+#[derive(Debug, Default)]
+pub(crate) struct CommitOptions<'a> {
+    pub(crate) sign: bool,
+    pub(crate) author: Option<&'a Identity>,
+    pub(crate) committer: Option<&'a Identity>,
+    /// Commit even when the index has no changes
+    pub(crate) allow_empty: bool,
+}
 
 impl Repository {
     pub(crate) fn commit(&self, message: &str, sign: bool) -> Result<Oid, Git2Error> {
-        let sig = self.0.signature()?;
+        self.commit_with(
+            message,
+            &CommitOptions {
+                sign,
+                ..CommitOptions::default()
+            },
+        )
+    }
+
+    pub(crate) fn commit_with(
+        &self,
+        message: &str,
+        options: &CommitOptions,
+    ) -> Result<Oid, Git2Error> {
+        let signature = |identity: Option<&Identity>| match identity {
+            Some(identity) => identity.signature(),
+            None => self.0.signature(),
+        };
+        let author = signature(options.author)?;
+        let committer = signature(options.committer)?;
+        let sig = Signatures {
+            author: &author,
+            committer: &committer,
+        };
+
         let tree_id = self.0.index()?.write_tree()?;
         let tree = self.0.find_tree(tree_id)?;
         let is_empty = self.0.head().is_err();
         let has_delta = self.get_diff(false).is_some();
 
-        if !is_empty && has_delta {
+        if !is_empty && (has_delta || options.allow_empty) {
             let head = &self.0.head()?;
             let head_target = head.target().expect("Cannot get HEAD target");
             let tip = &self.0.find_commit(head_target)?;
 
-            self.commit_or_signed_commit(&sig, message, &tree, &[tip], sign)
-                .map_err(Git2Error::from)
-        } else if is_empty && has_delta {
+            self.commit_or_signed_commit(&sig, message, &tree, &[tip], options.sign)
+        } else if is_empty && (has_delta || options.allow_empty) {
             // First repo commit
-            self.commit_or_signed_commit(&sig, message, &tree, &[], sign)
-                .map_err(Git2Error::from)
+            self.commit_or_signed_commit(&sig, message, &tree, &[], options.sign)
         } else {
             let statuses = self.get_statuses()?;
             let statuses = if statuses.0.is_empty() {
@@ -35,9 +66,37 @@ impl Repository {
         }
     }
 
+    /// Replace HEAD with a signed copy of its commit, e.g. after a rebase step dropped the
+    /// signature. HEAD is left detached, as it is during a rebase.
+    pub(crate) fn sign_head_commit(&self) -> Result<Oid, Git2Error> {
+        let head = self.get_head_commit()?;
+        let parents: Vec<Commit> = head.parents().collect();
+        let parents: Vec<&Commit> = parents.iter().collect();
+        let message = String::from_utf8_lossy(head.message_raw_bytes());
+
+        let commit_buf = self.0.commit_create_buffer(
+            &head.author(),
+            &head.committer(),
+            &message,
+            &head.tree()?,
+            &parents,
+        )?;
+
+        let commit_as_str = std::str::from_utf8(&commit_buf)
+            .expect("Invalid UTF-8 commit message")
+            .to_string();
+
+        let gpg_signature = self.sign_buffer(&commit_as_str)?;
+        let oid = self
+            .0
+            .commit_signed(&commit_as_str, &gpg_signature, Some("gpgsig"))?;
+        self.0.set_head_detached(oid)?;
+        Ok(oid)
+    }
+
     fn commit_or_signed_commit(
         &self,
-        sig: &Signature,
+        sig: &Signatures,
         commit_message: &str,
         tree: &Tree,
         parents: &[&Commit],
@@ -46,20 +105,30 @@ impl Repository {
         if !sign {
             return self
                 .0
-                .commit(Some("HEAD"), sig, sig, commit_message, tree, parents)
+                .commit(
+                    Some("HEAD"),
+                    sig.author,
+                    sig.committer,
+                    commit_message,
+                    tree,
+                    parents,
+                )
                 .map_err(Git2Error::Other);
         }
 
-        let commit_buf = self
-            .0
-            .commit_create_buffer(sig, sig, commit_message, tree, parents)?;
+        let commit_buf = self.0.commit_create_buffer(
+            sig.author,
+            sig.committer,
+            commit_message,
+            tree,
+            parents,
+        )?;
 
         let commit_as_str = std::str::from_utf8(&commit_buf)
             .expect("Invalid UTF-8 commit message")
             .to_string();
 
-        let key = self.signin_key().ok();
-        let gpg_signature = gpg_sign_string(key, &commit_as_str)?;
+        let gpg_signature = self.sign_buffer(&commit_as_str)?;
         let oid = self
             .0
             .commit_signed(&commit_as_str, &gpg_signature, Some("gpgsig"))?;
@@ -71,34 +140,9 @@ impl Repository {
     }
 }
 
-fn gpg_sign_string(key: Option<String>, content: &str) -> Result<String, Git2Error> {
-    let mut child = Command::new("gpg");
-    child.args(["--armor", "--detach-sig"]);
-
-    if let Some(key) = &key {
-        child.args(["--default-key", key]);
-    }
-
-    let mut child = child
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .spawn()
-        .expect("error calling gpg command, is gpg installed ?");
-
-    {
-        let stdin = child.stdin.as_mut().unwrap();
-        stdin.write_all(content.as_bytes())?;
-    }
-
-    child.wait_with_output().map(|output| {
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
-            Err(Git2Error::GpgError(
-                String::from_utf8_lossy(&output.stderr).to_string(),
-            ))
-        }
-    })?
+struct Signatures<'a> {
+    author: &'a Signature<'a>,
+    committer: &'a Signature<'a>,
 }
 
 #[cfg(test)]
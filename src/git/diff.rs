@@ -1,8 +1,10 @@
+use crate::git::error::Git2Error;
 use crate::git::repository::Repository;
-use git2::{Diff, DiffOptions};
+use git2::{Diff, DiffFormat, DiffOptions, Oid};
+use globset::GlobSet;
 
 impl Repository {
-    pub(crate) fn get_diff(&self, include_untracked: bool) -> Option<Diff> {
+    pub(crate) fn get_diff(&self, include_untracked: bool) -> Option<Diff<'_>> {
         let mut options = DiffOptions::new();
         options.include_untracked(include_untracked);
 
@@ -26,13 +28,64 @@ impl Repository {
             Err(..) => None,
         }
     }
+
+    /// The paths of the staged files, relative to the repository root
+    pub(crate) fn get_staged_files(&self) -> Vec<String> {
+        let Some(diff) = self.get_diff(false) else {
+            return vec![];
+        };
+
+        diff.deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect()
+    }
+
+    /// The staged changes as a patch, `None` when nothing is staged
+    pub(crate) fn get_staged_patch(&self) -> Option<String> {
+        let diff = self.get_diff(false)?;
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin());
+            }
+            patch.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })
+        .ok()?;
+
+        Some(patch)
+    }
+
+    /// Whether the commit adds, modifies or deletes a file matching `paths`,
+    /// compared to its first parent
+    pub(crate) fn commit_touches(&self, oid: Oid, paths: &GlobSet) -> Result<bool, Git2Error> {
+        let commit = self.0.find_commit(oid)?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+
+        let diff = self
+            .0
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+        Ok(diff.deltas().any(|delta| {
+            [delta.new_file().path(), delta.old_file().path()]
+                .into_iter()
+                .flatten()
+                .any(|path| paths.is_match(path))
+        }))
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::git::repository::Repository;
     use anyhow::Result;
-    use cmd_lib::run_cmd;
+    use cmd_lib::{run_cmd, run_fun};
+    use git2::Oid;
+    use globset::{Glob, GlobSetBuilder};
     use sealed_test::prelude::*;
 
     #[sealed_test]
@@ -102,4 +155,56 @@ mod test {
         assert!(diffs.is_none());
         Ok(())
     }
+
+    #[sealed_test]
+    fn get_staged_files() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            mkdir api;
+            echo changes > api/file;
+            echo changes > unstaged;
+            git add api;
+        )?;
+
+        let repo = Repository::open(".")?;
+
+        // Act
+        let files = repo.get_staged_files();
+
+        // Assert
+        assert_eq!(files, vec!["api/file".to_string()]);
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn commit_touches_matching_paths() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            mkdir -p src/parser;
+            echo init > README;
+            git add .;
+            git commit -q -m "chore: init";
+            echo changes > src/parser/lib.rs;
+            git add .;
+            git commit -q -m "feat(parser): add parser";
+        )?;
+
+        let repo = Repository::open(".")?;
+        let paths = GlobSetBuilder::new()
+            .add(Glob::new("src/parser/**")?)
+            .build()?;
+        let head = Oid::from_str(&run_fun!(git rev-parse HEAD)?)?;
+        let root = Oid::from_str(&run_fun!(git rev-parse HEAD~1)?)?;
+
+        // Act
+        let head_touches = repo.commit_touches(head, &paths)?;
+        let root_touches = repo.commit_touches(root, &paths)?;
+
+        // Assert
+        assert!(head_touches);
+        assert!(!root_touches);
+        Ok(())
+    }
 }
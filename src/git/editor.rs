@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use tempfile::TempDir;
+
+use crate::git::error::Git2Error;
+use crate::git::repository::Repository;
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+/// A shell-like editor command such as `code --wait`, files are passed as its last argument
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Editor(String);
+
+impl Repository {
+    /// The editor git would use: `GIT_EDITOR`, `core.editor`, `VISUAL` then `EDITOR`,
+    /// `notepad` on Windows and `vi` elsewhere when none is set
+    pub(crate) fn editor(&self) -> Editor {
+        let core_editor = self
+            .0
+            .config()
+            .and_then(|config| config.get_string("core.editor"))
+            .ok();
+
+        let editor = [
+            std::env::var("GIT_EDITOR").ok(),
+            core_editor,
+            std::env::var("VISUAL").ok(),
+            std::env::var("EDITOR").ok(),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+        Editor(editor)
+    }
+}
+
+impl Editor {
+    /// Edit the file at `path`, returning once the editor exits
+    pub(crate) fn edit_file(&self, path: &Path) -> Result<(), Git2Error> {
+        let status = self
+            .command()?
+            .arg(path)
+            .stdout(Stdio::inherit())
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|err| Git2Error::EditorError(format!("failed to run `{}`: {}", self, err)))?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Git2Error::EditorError(format!(
+                "`{}` exited with {}",
+                self, status
+            )))
+        }
+    }
+
+    /// Edit `content` in a temporary file named `file_name`, returning the edited content
+    pub(crate) fn edit(&self, content: &str, file_name: &str) -> Result<String, Git2Error> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join(file_name);
+        fs::write(&path, content)?;
+        self.edit_file(&path)?;
+        Ok(fs::read_to_string(&path)?)
+    }
+
+    fn command(&self) -> Result<Command, Git2Error> {
+        // An editor path containing spaces, e.g. under `C:\Program Files`, is not split
+        if Path::new(&self.0).is_file() {
+            return Ok(Command::new(&self.0));
+        }
+
+        // Backslashes are path separators on Windows, not escapes
+        let editor = if cfg!(windows) {
+            self.0.replace('\\', "\\\\")
+        } else {
+            self.0.clone()
+        };
+
+        let words = shell_words::split(&editor)
+            .map_err(|err| Git2Error::EditorError(format!("invalid editor `{}`: {}", self, err)))?;
+        let (program, args) = words
+            .split_first()
+            .ok_or_else(|| Git2Error::EditorError("the editor command is empty".to_string()))?;
+
+        let mut command = Command::new(program);
+        command.args(args);
+        Ok(command)
+    }
+}
+
+impl std::fmt::Display for Editor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::git::editor::Editor;
+    use crate::git::repository::Repository;
+    use anyhow::Result;
+    use cmd_lib::run_cmd;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    #[sealed_test]
+    fn editor_follows_git_precedence() -> Result<()> {
+        // Arrange
+        std::env::remove_var("GIT_EDITOR");
+        std::env::set_var("VISUAL", "code --wait");
+        std::env::set_var("EDITOR", "nano");
+        let repo = Repository::init(".")?;
+        let visual = repo.editor();
+        run_cmd!(git config --local core.editor "vim -f")?;
+
+        // Act
+        let core_editor = repo.editor();
+
+        // Assert
+        assert_that!(visual).is_equal_to(Editor("code --wait".to_string()));
+        assert_that!(core_editor).is_equal_to(Editor("vim -f".to_string()));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn edit_with_arguments() -> Result<()> {
+        // Arrange
+        std::fs::write("editor.sh", "echo \"$1\" >> \"$2\"")?;
+        let editor = Editor("sh editor.sh 'edited line'".to_string());
+
+        // Act
+        let edited = editor.edit("first line\n", "COMMIT_EDITMSG")?;
+
+        // Assert
+        assert_that!(edited.as_str()).is_equal_to("first line\nedited line\n");
+        Ok(())
+    }
+
+    #[test]
+    fn failing_editor_is_an_error() {
+        let editor = Editor("false".to_string());
+
+        let edited = editor.edit("content", "COMMIT_EDITMSG");
+
+        assert_that!(edited).is_err();
+    }
+}
@@ -22,6 +22,11 @@ pub enum Git2Error {
     CommitNotFound(git2::Error),
     IOError(io::Error),
     GpgError(String),
+    FetchError(String),
+    EditorError(String),
+    ShallowClone {
+        tag: Option<String>,
+    },
     Other(git2::Error),
     NoTagFound,
     CommitterNotFound,
@@ -116,7 +121,24 @@ impl Display for Git2Error {
                 statuses
             ),
             Git2Error::IOError(_) => writeln!(f, "IO Error"),
-            Git2Error::GpgError(_) => writeln!(f, "failed to sign commit"),
+            Git2Error::GpgError(_) => writeln!(f, "failed to sign git object"),
+            Git2Error::FetchError(_) => writeln!(f, "failed to fetch the repository history"),
+            Git2Error::EditorError(_) => writeln!(f, "failed to edit the commit message"),
+            Git2Error::ShallowClone { tag } => {
+                match tag {
+                    Some(tag) => writeln!(
+                        f,
+                        "latest tag {} is not reachable in this shallow clone",
+                        tag
+                    )?,
+                    None => writeln!(f, "no tag found in this shallow clone")?,
+                }
+
+                writeln!(
+                    f,
+                    "\nFetch the full history with `git fetch --unshallow --tags` or set `unshallow = true` in cog.toml"
+                )
+            }
         }?;
 
         match self {
@@ -129,7 +151,9 @@ impl Display for Git2Error {
             | Git2Error::StatusError(err)
             | Git2Error::Other(err)
             | Git2Error::CommitNotFound(err) => writeln!(f, "\ncause: {}", err),
-            Git2Error::GpgError(err) => writeln!(f, "\ncause: {}", err),
+            Git2Error::GpgError(err) | Git2Error::FetchError(err) | Git2Error::EditorError(err) => {
+                writeln!(f, "\ncause: {}", err)
+            }
             Git2Error::IOError(err) => writeln!(f, "\ncause: {}", err),
             _ => fmt::Result::Ok(()),
         }
@@ -4,14 +4,15 @@ use std::io;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
+use crate::error::CocogittoError;
 use crate::CocoGitto;
 
-use anyhow::{anyhow, Result};
-
 pub(crate) static PRE_PUSH_HOOK: &[u8] = include_bytes!("assets/pre-push");
 pub(crate) static PREPARE_COMMIT_HOOK: &[u8] = include_bytes!("assets/commit-msg");
-const PRE_COMMIT_HOOK_PATH: &str = ".git/hooks/commit-msg";
-const PRE_PUSH_HOOK_PATH: &str = ".git/hooks/pre-push";
+pub(crate) static PRE_COMMIT_FRAMEWORK_HOOKS: &str = include_str!("assets/pre-commit-hooks.yaml");
+pub(crate) static HUSKY_COMMIT_MSG_HOOK: &str = include_str!("assets/husky-commit-msg");
+const PRE_COMMIT_HOOK_PATH: &str = "commit-msg";
+const PRE_PUSH_HOOK_PATH: &str = "pre-push";
 
 pub enum HookKind {
     PrepareCommit,
@@ -19,20 +20,36 @@ pub enum HookKind {
     All,
 }
 
+/// Third party hook managers cocogitto can generate a configuration for
+pub enum HookFramework {
+    /// A `.pre-commit-hooks.yaml` compatible hook definition for the pre-commit framework
+    PreCommit,
+    /// A `.husky/commit-msg` script for husky
+    Husky,
+}
+
+impl HookFramework {
+    /// Returns the hook definition for this framework, wrapping `cog verify --file`
+    pub fn config(&self) -> &'static str {
+        match self {
+            HookFramework::PreCommit => PRE_COMMIT_FRAMEWORK_HOOKS,
+            HookFramework::Husky => HUSKY_COMMIT_MSG_HOOK,
+        }
+    }
+}
+
 impl CocoGitto {
-    pub fn install_hook(&self, kind: HookKind) -> Result<()> {
-        let repodir = &self
-            .repository
-            .get_repo_dir()
-            .ok_or_else(|| anyhow!("Repository root directory not found"))?
-            .to_path_buf();
+    pub fn install_hook(&self, kind: HookKind) -> Result<(), CocogittoError> {
+        // Hooks are shared by all the worktrees of a repository, unless `core.hooksPath` is set
+        let hooks_dir = &self.repository.hooks_dir();
+        fs::create_dir_all(hooks_dir)?;
 
         match kind {
-            HookKind::PrepareCommit => create_hook(repodir, HookKind::PrepareCommit)?,
-            HookKind::PrePush => create_hook(repodir, HookKind::PrePush)?,
+            HookKind::PrepareCommit => create_hook(hooks_dir, HookKind::PrepareCommit)?,
+            HookKind::PrePush => create_hook(hooks_dir, HookKind::PrePush)?,
             HookKind::All => {
-                create_hook(repodir, HookKind::PrepareCommit)?;
-                create_hook(repodir, HookKind::PrePush)?
+                create_hook(hooks_dir, HookKind::PrepareCommit)?;
+                create_hook(hooks_dir, HookKind::PrePush)?
             }
         };
 
@@ -62,7 +79,7 @@ fn create_hook(path: &Path, kind: HookKind) -> io::Result<()> {
 mod tests {
     use std::fs::File;
 
-    use crate::git::hook::HookKind;
+    use crate::git::hook::{HookFramework, HookKind};
     use crate::CocoGitto;
 
     use anyhow::Result;
@@ -87,6 +104,25 @@ mod tests {
         Ok(())
     }
 
+    #[sealed_test]
+    fn add_hook_in_core_hooks_path() -> Result<()> {
+        // Arrange
+        run_cmd!(
+            git init;
+            git config core.hooksPath .githooks;
+        )?;
+
+        let cog = CocoGitto::get()?;
+
+        // Act
+        cog.install_hook(HookKind::PrepareCommit)?;
+
+        // Assert
+        assert_that!(Path::new(".githooks/commit-msg")).exists();
+        assert_that!(Path::new(".git/hooks/commit-msg")).does_not_exist();
+        Ok(())
+    }
+
     #[sealed_test]
     fn add_pre_push_hook() -> Result<()> {
         // Arrange
@@ -138,4 +174,24 @@ mod tests {
         assert_that!(metadata.permissions().mode() & 0o777).is_equal_to(0o755);
         Ok(())
     }
+
+    #[test]
+    fn pre_commit_framework_hook_wraps_verify_file() {
+        // Act
+        let config = HookFramework::PreCommit.config();
+
+        // Assert
+        assert_that!(config).contains("entry: cog verify --file");
+        assert_that!(config).contains("stages: [commit-msg]");
+    }
+
+    #[test]
+    fn husky_hook_wraps_verify_file() {
+        // Act
+        let script = HookFramework::Husky.config();
+
+        // Assert
+        assert_that!(script).starts_with("#!/usr/bin/env sh");
+        assert_that!(script).contains("cog verify --file \"$1\"");
+    }
 }
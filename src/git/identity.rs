@@ -0,0 +1,67 @@
+use std::fmt;
+use std::str::FromStr;
+
+use git2::Signature;
+
+/// A commit author or committer, written `Name <email>` like `git commit --author`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Identity {
+    pub name: String,
+    pub email: String,
+}
+
+impl Identity {
+    pub(crate) fn signature(&self) -> Result<Signature<'static>, git2::Error> {
+        Signature::now(&self.name, &self.email)
+    }
+}
+
+impl FromStr for Identity {
+    type Err = String;
+
+    fn from_str(identity: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid identity '{}', expected 'Name <email>'", identity);
+        let (name, email) = identity.trim().split_once('<').ok_or_else(invalid)?;
+        let email = email.strip_suffix('>').ok_or_else(invalid)?.trim();
+        let name = name.trim();
+
+        if name.is_empty() || email.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Identity {
+            name: name.to_string(),
+            email: email.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use crate::git::identity::Identity;
+
+    #[test]
+    fn parse_identity() {
+        let identity = "release-bot <bot@example.org>".parse::<Identity>();
+
+        assert_that!(identity).is_ok().is_equal_to(Identity {
+            name: "release-bot".to_string(),
+            email: "bot@example.org".to_string(),
+        });
+    }
+
+    #[test]
+    fn parse_identity_without_email_fails() {
+        let identity = "release-bot".parse::<Identity>();
+
+        assert_that!(identity).is_err();
+    }
+}
@@ -1,10 +1,13 @@
 pub mod commit;
 pub mod diff;
-pub(crate) mod error;
+pub(crate) mod editor;
+pub mod error;
 pub mod hook;
+pub mod identity;
 pub mod oid;
 pub mod repository;
 pub mod revspec;
+pub(crate) mod sign;
 pub mod stash;
 pub mod status;
 pub mod tag;
@@ -5,7 +5,7 @@ use git2::Oid;
 use crate::git::tag::Tag;
 
 /// A wrapper for git2 oid including tags and HEAD ref
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OidOf {
     Tag(Tag),
     Head(Oid),
@@ -1,12 +1,59 @@
+use std::cell::RefCell;
 use std::fmt::{Debug, Formatter};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::rc::Rc;
 
 use crate::git::error::Git2Error;
+use crate::git::identity::Identity;
+use crate::git::tag::TagIndex;
 use git2::{
-    Commit as Git2Commit, IndexAddOption, Object, ObjectType, Oid, Repository as Git2Repository,
+    BranchType, Commit as Git2Commit, IndexAddOption, Object, ObjectType, Oid,
+    Repository as Git2Repository,
 };
+use globset::GlobSet;
+use semver::Version;
+
+/// A git repository along with the rules used to resolve its version tags
+/// and the index of those tags, built on first lookup
+pub(crate) struct Repository(
+    pub(crate) Git2Repository,
+    TagRules,
+    pub(crate) RefCell<Option<Rc<TagIndex>>>,
+);
+
+/// The prefix of the tags created by cog, the legacy prefixes still accepted when reading tags
+/// and the tags never considered as versions
+#[derive(Debug)]
+pub(crate) struct TagRules {
+    canonical: Option<String>,
+    legacy: Vec<String>,
+    ignore: GlobSet,
+    // Only versions lower than this one are read on maintenance branches
+    ceiling: Option<Version>,
+}
+
+/// The remote branch a local branch tracks, with the commits each one is missing
+#[derive(Debug)]
+pub(crate) struct Upstream {
+    pub(crate) branch: String,
+    pub(crate) name: String,
+    /// Local commits missing from the upstream
+    pub(crate) ahead: usize,
+    /// Upstream commits missing from the local branch
+    pub(crate) behind: usize,
+}
 
-pub(crate) struct Repository(pub(crate) Git2Repository);
+impl Default for TagRules {
+    fn default() -> Self {
+        TagRules {
+            canonical: None,
+            legacy: vec![],
+            ignore: GlobSet::empty(),
+            ceiling: None,
+        }
+    }
+}
 
 impl Repository {
     pub(crate) fn signin_key(&self) -> Result<String, Git2Error> {
@@ -19,24 +66,298 @@ impl Repository {
         config.get_bool("commit.gpgSign").unwrap_or(false)
     }
 
+    pub(crate) fn tag_sign(&self) -> bool {
+        let config = self.0.config().expect("failed to retrieve gitconfig");
+        config.get_bool("tag.gpgSign").unwrap_or(false)
+    }
+
     pub(crate) fn init<S: AsRef<Path> + ?Sized>(path: &S) -> Result<Repository, Git2Error> {
         let repository =
             Git2Repository::init(path).map_err(Git2Error::FailedToInitializeRepository)?;
-        Ok(Repository(repository))
+        Ok(Repository(
+            repository,
+            TagRules::default(),
+            RefCell::default(),
+        ))
     }
 
     pub(crate) fn open<S: AsRef<Path> + ?Sized>(path: &S) -> Result<Repository, Git2Error> {
         let repo = Git2Repository::discover(path).map_err(Git2Error::FailedToOpenRepository)?;
-        Ok(Repository(repo))
+        Ok(Repository(repo, TagRules::default(), RefCell::default()))
+    }
+
+    pub(crate) fn with_tag_rules(
+        self,
+        tag_prefix: Option<String>,
+        legacy_tag_prefixes: Vec<String>,
+        ignored_tags: GlobSet,
+    ) -> Repository {
+        let rules = TagRules {
+            canonical: tag_prefix,
+            legacy: legacy_tag_prefixes,
+            ignore: ignored_tags,
+            ceiling: None,
+        };
+
+        Repository(self.0, rules, RefCell::default())
+    }
+
+    /// Restrict the version tags to the ones lower than `ceiling`
+    pub(crate) fn with_version_ceiling(self, ceiling: Option<Version>) -> Repository {
+        let rules = TagRules { ceiling, ..self.1 };
+        Repository(self.0, rules, RefCell::default())
+    }
+
+    pub(crate) fn version_ceiling(&self) -> Option<&Version> {
+        self.1.ceiling.as_ref()
+    }
+
+    /// The prefix of the tags created by cog
+    pub(crate) fn tag_prefix(&self) -> Option<&str> {
+        self.1.canonical.as_deref()
+    }
+
+    /// The prefix of an existing tag: the longest configured prefix it starts with,
+    /// legacy prefixes included, or the canonical prefix
+    pub(crate) fn tag_prefix_of(&self, tag: &str) -> Option<&str> {
+        self.1
+            .canonical
+            .iter()
+            .chain(self.1.legacy.iter())
+            .filter(|prefix| tag.starts_with(prefix.as_str()))
+            .max_by_key(|prefix| prefix.len())
+            .map(String::as_str)
+            .or_else(|| self.tag_prefix())
     }
 
+    pub(crate) fn legacy_tag_prefixes(&self) -> &[String] {
+        &self.1.legacy
+    }
+
+    /// Whether the tag matches one of the ignored tag patterns
+    pub(crate) fn is_ignored_tag(&self, tag: &str) -> bool {
+        self.1.ignore.is_match(tag)
+    }
+
+    /// The working tree root, `None` for bare repositories
     pub(crate) fn get_repo_dir(&self) -> Option<&Path> {
         self.0.workdir()
     }
 
+    pub(crate) fn is_shallow(&self) -> bool {
+        self.0.is_shallow()
+    }
+
+    /// Fetch the missing history and tags of a shallow clone.
+    /// libgit2 cannot deepen a shallow repository, this runs `git fetch --unshallow`.
+    pub(crate) fn unshallow(&self) -> Result<(), Git2Error> {
+        let output = Command::new("git")
+            .arg("--git-dir")
+            .arg(self.0.path())
+            .args(["fetch", "--unshallow", "--tags", "--quiet"])
+            .output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(Git2Error::FetchError(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Fetch the remote the current branch tracks and compare the branch with its upstream,
+    /// `None` when the branch tracks no remote branch
+    pub(crate) fn fetch_upstream(&self) -> Result<Option<Upstream>, Git2Error> {
+        let Some(branch) = self.get_branch_shorthand() else {
+            return Ok(None);
+        };
+
+        let Ok(remote) = self
+            .0
+            .branch_upstream_remote(&format!("refs/heads/{}", branch))
+        else {
+            return Ok(None);
+        };
+
+        let output = Command::new("git")
+            .arg("--git-dir")
+            .arg(self.0.path())
+            .args(["fetch", "--quiet"])
+            .arg(remote.as_str().unwrap_or_default())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Git2Error::FetchError(
+                String::from_utf8_lossy(&output.stderr).to_string(),
+            ));
+        }
+
+        let upstream = self.0.find_branch(&branch, BranchType::Local)?.upstream()?;
+        let upstream_oid = upstream.get().peel_to_commit()?.id();
+        let (ahead, behind) = self
+            .0
+            .graph_ahead_behind(self.get_head_commit_oid()?, upstream_oid)?;
+
+        Ok(Some(Upstream {
+            branch,
+            name: upstream.name()?.unwrap_or_default().to_string(),
+            ahead,
+            behind,
+        }))
+    }
+
+    /// Whether the given commit is HEAD or one of its ancestors available in the local history
+    pub(crate) fn is_reachable_from_head(&self, oid: Oid) -> bool {
+        match self.get_head_commit_oid() {
+            Ok(head) => head == oid || self.0.graph_descendant_of(head, oid).unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    /// The git directory shared by all worktrees, where hooks and config live
+    pub(crate) fn get_common_dir(&self) -> PathBuf {
+        let git_dir = self.0.path();
+        if self.0.is_worktree() {
+            // A linked worktree git dir points to the main git dir in its `commondir` file
+            if let Ok(common_dir) = std::fs::read_to_string(git_dir.join("commondir")) {
+                let common_dir = git_dir.join(common_dir.trim());
+                return common_dir.canonicalize().unwrap_or(common_dir);
+            }
+        }
+
+        git_dir.to_path_buf()
+    }
+
+    /// The directory git runs hooks from: `core.hooksPath`, relative to the working tree,
+    /// or the `hooks` directory of the common git dir
+    pub(crate) fn hooks_dir(&self) -> PathBuf {
+        let hooks_path = self
+            .0
+            .config()
+            .and_then(|config| config.get_path("core.hooksPath"));
+
+        match hooks_path {
+            Ok(path) if path.is_absolute() => path,
+            Ok(path) => self.get_repo_dir().unwrap_or(self.0.path()).join(path),
+            Err(_) => self.get_common_dir().join("hooks"),
+        }
+    }
+
+    /// The content of the `commit.template` file, relative paths are resolved from the working tree
+    pub(crate) fn commit_template(&self) -> Option<String> {
+        let path = self.0.config().ok()?.get_path("commit.template").ok()?;
+        let path = match self.get_repo_dir() {
+            Some(repo_dir) if path.is_relative() => repo_dir.join(path),
+            _ => path,
+        };
+
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// Whether `commit.verbose` asks to show the staged changes when editing a commit message
+    pub(crate) fn commit_verbose(&self) -> bool {
+        self.0
+            .config()
+            .and_then(|config| config.get_bool("commit.verbose"))
+            .unwrap_or(false)
+    }
+
+    /// The `origin` remote, or the first remote when there is no `origin`
+    pub(crate) fn default_remote(&self) -> Option<String> {
+        let remotes = self.0.remotes().ok()?;
+        if remotes.iter().flatten().any(|name| name == "origin") {
+            Some("origin".to_string())
+        } else {
+            remotes.iter().flatten().next().map(str::to_string)
+        }
+    }
+
+    /// The url of the default remote
+    pub(crate) fn remote_url(&self) -> Option<String> {
+        let remote = self.0.find_remote(&self.default_remote()?).ok()?;
+        remote.url().map(str::to_string)
+    }
+
+    /// The branch `origin/HEAD` points to, falling back to `init.defaultBranch` then the current branch
+    pub(crate) fn default_branch(&self) -> Option<String> {
+        let origin_head = self
+            .0
+            .find_reference("refs/remotes/origin/HEAD")
+            .ok()
+            .and_then(|reference| reference.symbolic_target().map(str::to_string))
+            .and_then(|target| {
+                target
+                    .strip_prefix("refs/remotes/origin/")
+                    .map(str::to_string)
+            });
+
+        origin_head
+            .or_else(|| {
+                self.0
+                    .config()
+                    .and_then(|config| config.get_string("init.defaultBranch"))
+                    .ok()
+            })
+            .or_else(|| self.get_branch_shorthand())
+    }
+
+    /// The number of commits reachable from HEAD
+    pub(crate) fn commit_count(&self) -> Result<usize, Git2Error> {
+        let mut revwalk = self.0.revwalk()?;
+        if revwalk.push_head().is_err() {
+            // Unborn branch
+            return Ok(0);
+        }
+
+        Ok(revwalk.count())
+    }
+
+    /// The number of commits reachable from HEAD but not from `from`
+    pub(crate) fn commit_count_since(&self, from: Oid) -> Result<usize, Git2Error> {
+        let mut revwalk = self.0.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.hide(from)?;
+        Ok(revwalk.count())
+    }
+
+    /// The `git patch-id` of the changes of a commit to its first parent
+    pub(crate) fn patch_id(&self, oid: &str) -> Option<Oid> {
+        let commit = self.0.find_commit(Oid::from_str(oid).ok()?).ok()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree().ok()?),
+            Err(_) => None,
+        };
+        let diff = self
+            .0
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree().ok()?), None)
+            .ok()?;
+        diff.patchid(None).ok()
+    }
+
+    /// Read a file from the tree of the HEAD commit, used when there is no working tree
+    pub(crate) fn get_head_file_content(&self, path: &str) -> Option<String> {
+        let head = self.get_head_commit().ok()?;
+        self.get_file_content_at(&head, Path::new(path))
+    }
+
+    /// Read a file from the tree of a commit, `None` when the commit does not have it
+    pub(crate) fn get_file_content_at(&self, commit: &Git2Commit, path: &Path) -> Option<String> {
+        let entry = commit.tree().ok()?.get_path(path).ok()?;
+        let blob = entry.to_object(&self.0).ok()?.peel_to_blob().ok()?;
+        String::from_utf8(blob.content().to_vec()).ok()
+    }
+
     pub(crate) fn add_all(&self) -> Result<(), Git2Error> {
+        self.add_all_matching(|_| true)
+    }
+
+    /// Stage the changes to the paths `include` accepts, relative to the working tree root
+    pub(crate) fn add_all_matching(&self, include: impl Fn(&str) -> bool) -> Result<(), Git2Error> {
+        let mut skip_excluded =
+            |path: &Path, _: &[u8]| i32::from(!include(&path.to_string_lossy()));
         let mut index = self.0.index()?;
-        index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+        index.add_all(["*"], IndexAddOption::DEFAULT, Some(&mut skip_excluded))?;
         index.write().map_err(Git2Error::GitAddError)
     }
 
@@ -44,7 +365,7 @@ impl Repository {
         self.get_head_commit().map(|commit| commit.id())
     }
 
-    pub(crate) fn get_head_commit(&self) -> Result<Git2Commit, Git2Error> {
+    pub(crate) fn get_head_commit(&self) -> Result<Git2Commit<'_>, Git2Error> {
         let head_ref = self.0.head();
         match head_ref {
             Ok(head) => head.peel_to_commit().map_err(Git2Error::PeelToCommitError),
@@ -61,7 +382,7 @@ impl Repository {
             .map_err(Git2Error::CommitNotFound)
     }
 
-    pub(crate) fn get_head(&self) -> Option<Object> {
+    pub(crate) fn get_head(&self) -> Option<Object<'_>> {
         Repository::tree_to_treeish(&self.0, Some(&"HEAD".to_string()))
             .ok()
             .flatten()
@@ -82,6 +403,18 @@ impl Repository {
             .ok_or(Git2Error::CommitterNotFound)
     }
 
+    /// The git config identity, used for the commits created by cog
+    pub(crate) fn get_identity(&self) -> Result<Identity, Git2Error> {
+        let signature = self.0.signature()?;
+        match (signature.name(), signature.email()) {
+            (Some(name), Some(email)) => Ok(Identity {
+                name: name.to_string(),
+                email: email.to_string(),
+            }),
+            _ => Err(Git2Error::CommitterNotFound),
+        }
+    }
+
     fn tree_to_treeish<'a>(
         repo: &'a Git2Repository,
         arg: Option<&String>,
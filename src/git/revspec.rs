@@ -1,13 +1,17 @@
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Formatter;
 
-use git2::{Commit, ErrorCode, Oid};
+use git2::{Commit, ErrorCode, Oid, Revwalk, Sort};
 
-use crate::conventional::changelog::release::Release;
+use crate::conventional::cache::CommitCache;
+use crate::conventional::changelog::release::{Release, ReleaseBuilder};
 use crate::git::error::Git2Error;
 use crate::git::oid::OidOf;
 use crate::git::repository::Repository;
 use crate::git::tag::Tag;
+use crate::reporter::{Progress, Task};
+use crate::settings::Settings;
 
 #[derive(Debug)]
 pub struct CommitRange<'repo> {
@@ -65,7 +69,7 @@ impl From<(&str, &str)> for RevspecPattern {
 
 impl Repository {
     /// Return a [`CommitRange`] containing all commit in the current repository
-    pub fn all_commits(&self) -> Result<CommitRange, Git2Error> {
+    pub fn all_commits(&self) -> Result<CommitRange<'_>, Git2Error> {
         let mut revwalk = self.0.revwalk()?;
         revwalk.push_head()?;
         let mut commits = vec![];
@@ -98,7 +102,32 @@ impl Repository {
         Ok(CommitRange { from, to, commits })
     }
 
-    pub(crate) fn get_release_range(&self, pattern: RevspecPattern) -> Result<Release, Git2Error> {
+    /// The newest merge commit between `from`, excluded, and HEAD. Without `from` the whole
+    /// history of HEAD is searched.
+    pub(crate) fn newest_merge_since(&self, from: Option<Oid>) -> Result<Option<Oid>, Git2Error> {
+        let mut revwalk = self.0.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL)?;
+        revwalk.push_head()?;
+        if let Some(from) = from {
+            revwalk.hide(from)?;
+        }
+
+        for oid in revwalk {
+            let oid = oid?;
+            if self.0.find_commit(oid)?.parent_count() > 1 {
+                return Ok(Some(oid));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn get_release_range<'a>(
+        &'a self,
+        pattern: RevspecPattern,
+        settings: &'a Settings,
+        cache: Option<&CommitCache>,
+    ) -> Result<Release<'a>, Git2Error> {
         let target = if let Some(target) = pattern.from {
             self.resolve_oid_of(&target)
         } else {
@@ -110,11 +139,13 @@ impl Repository {
             to: pattern.to,
         };
 
+        let builder = ReleaseBuilder::new(settings);
+        let mut progress = Progress::start(Task::ReadReleases, None);
         let range = self.get_commit_range(&pattern)?;
-        let release = Release::from(range);
+        let release = builder.build(range, cache, Some(&mut progress));
 
         let mut release = if !release.contains_oid(target.oid()) {
-            self.populate_previous_release(release, target.oid())?
+            self.populate_previous_release(release, target.oid(), &builder, cache, &mut progress)?
         } else {
             release
         };
@@ -128,6 +159,9 @@ impl Repository {
         &'a self,
         mut release: Release<'a>,
         target: &Oid,
+        builder: &ReleaseBuilder<'a>,
+        cache: Option<&CommitCache>,
+        progress: &mut Progress,
     ) -> Result<Release<'a>, Git2Error> {
         let pattern = format!("..{}", release.from);
         let pattern = RevspecPattern::from(pattern.as_str());
@@ -142,15 +176,16 @@ impl Repository {
         // We have reached the `from` target commit
         else if target_in_range {
             if range.from != range.to {
-                let previous = Release::from(range);
+                let previous = builder.build(range, cache, Some(progress));
                 release.previous = Some(Box::new(previous));
             }
 
             return Ok(release);
         }
 
-        let previous = Release::from(range);
-        let previous = self.populate_previous_release(previous, target)?;
+        let previous = builder.build(range, cache, Some(progress));
+        let previous =
+            self.populate_previous_release(previous, target, builder, cache, progress)?;
         release.previous = Some(Box::new(previous));
 
         Ok(release)
@@ -159,34 +194,47 @@ impl Repository {
     /// Return a commit range
     /// `from` : either a tag or an oid, latest tag if none, fallbacks to first commit
     /// `to`: HEAD if none
-    pub fn get_commit_range(&self, pattern: &RevspecPattern) -> Result<CommitRange, Git2Error> {
+    pub fn get_commit_range(&self, pattern: &RevspecPattern) -> Result<CommitRange<'_>, Git2Error> {
+        let (from, to, revwalk) = self.get_range_revwalk(pattern)?;
+
+        let mut commits: Vec<Commit> = vec![];
+
+        for oid in revwalk {
+            let oid = oid?;
+            let commit = self.0.find_commit(oid)?;
+            commits.push(commit);
+        }
+
+        Ok(CommitRange { from, to, commits })
+    }
+
+    /// Resolve the bounds of a commit range, returned along with a revwalk over its commits.
+    /// Commits are only looked up while walking, newest first.
+    pub(crate) fn get_range_revwalk(
+        &self,
+        pattern: &RevspecPattern,
+    ) -> Result<(OidOf, OidOf, Revwalk<'_>), Git2Error> {
         let from = pattern.from.as_deref();
         let to = pattern.to.as_deref();
 
-        // Is the given `to` arg a tag or an oid ?
-        let maybe_to_tag = match to {
-            // No target tag provided, check if HEAD is tagged
-            None => {
-                let head = self.get_head_commit_oid()?;
-                self.get_latest_tag()
-                    .ok()
-                    .filter(|tag| *tag.oid_unchecked() == head)
-            }
-            // Try to resolve a tag from the provided range, ex: ..1.0.0
-            Some(to) => self.resolve_tag(to).ok(),
-        };
-
         // get/validate the target oid
-        let to = match to {
+        let to_oid = match to {
             None => self.get_head_commit_oid()?,
             Some(to) => self.0.revparse_single(to)?.id(),
         };
 
+        // Is the given `to` arg a tag or an oid ?
+        let maybe_to_tag = to
+            // Try to resolve a tag from the provided range, ex: ..1.0.0
+            .and_then(|to| self.resolve_tag(to).ok())
+            // Otherwise check if the target commit is tagged
+            .or_else(|| self.tag_index().ok()?.find_by_oid(&to_oid).cloned());
+
         // Either user input, latest tag since `to`, or first commit
         let from = match from {
             // No `from` arg provided get latest tag in `to` parents
             None => self
-                .get_latest_tag_starting_from(to)
+                .get_latest_tag_starting_from(to_oid)
                 .map(OidOf::Tag)
                 // No tag in the tree, fallback to first commit
                 .unwrap_or_else(|_| {
@@ -199,15 +247,16 @@ impl Repository {
         };
 
         // Resolve shorthands and tags
-        let spec = format!("{}..{}", from, to);
+        let spec = format!("{}..{}", from, to_oid);
         // Attempt to resolve tag names, fallback to oid
         let to = maybe_to_tag
             .map(OidOf::Tag)
-            .unwrap_or_else(|| OidOf::Other(to));
+            .unwrap_or_else(|| OidOf::Other(to_oid));
 
-        let commits = self.get_commit_range_from_spec(&spec)?;
+        let mut revwalk = self.0.revwalk()?;
+        revwalk.push_range(&spec)?;
 
-        Ok(CommitRange { from, to, commits })
+        Ok((from, to, revwalk))
     }
 
     fn resolve_oid_of(&self, from: &str) -> OidOf {
@@ -220,10 +269,10 @@ impl Repository {
 
                 // Is the oid pointing to a tag ?
                 let tag = self
-                    .all_tags()
+                    .tag_index()
                     .expect("Error trying to get repository tags")
-                    .into_iter()
-                    .find(|tag| *tag.oid_unchecked() == object.id());
+                    .find_by_oid(&object.id())
+                    .cloned();
 
                 match tag {
                     None => OidOf::Other(object.id()),
@@ -232,22 +281,6 @@ impl Repository {
             })
     }
 
-    fn get_commit_range_from_spec(&self, spec: &str) -> Result<Vec<Commit>, Git2Error> {
-        let mut revwalk = self.0.revwalk()?;
-
-        revwalk.push_range(spec)?;
-
-        let mut commits: Vec<Commit> = vec![];
-
-        for oid in revwalk {
-            let oid = oid?;
-            let commit = self.0.find_commit(oid)?;
-            commits.push(commit);
-        }
-
-        Ok(commits)
-    }
-
     // Hide all commit after `starting_point` and get the closest tag
     fn get_latest_tag_starting_from(&self, starting_point: Oid) -> Result<Tag, Git2Error> {
         let starting_point = self.0.find_commit(starting_point)?;
@@ -257,39 +290,16 @@ impl Repository {
         let range = format!("{}..{}", first_commit, starting_point.id());
 
         revwalk.push_range(&range)?;
-        let mut range = vec![];
-        for oid in revwalk {
-            range.push(oid?);
-        }
-
-        let mut tags = vec![];
-        self.0
-            .tag_foreach(|mut oid, name| {
-                let name = String::from_utf8_lossy(name);
-                let name = name.as_ref().strip_prefix("refs/tags/").unwrap();
-
-                // If this is an annotated tag, find the first parent commit
-                if self.0.revparse_single(name).unwrap().as_commit().is_none() {
-                    if let Some(commit) = self
-                        .0
-                        .revparse_single([name, "^{}"].concat().as_str())
-                        .unwrap()
-                        .as_commit()
-                    {
-                        oid = commit.id();
-                    }
-                };
-
-                if range.contains(&oid) {
-                    if let Ok(tag) = Tag::new(name, Some(oid)) {
-                        tags.push(tag);
-                    };
-                };
-                true
-            })
-            .expect("Unable to walk tags");
+        let range = revwalk.collect::<Result<HashSet<Oid>, _>>()?;
 
-        let latest_tag: Option<Tag> = tags.into_iter().max();
+        let latest_tag = self
+            .tag_index()
+            .map_err(|_| Git2Error::NoTagFound)?
+            .tags()
+            .iter()
+            .rev()
+            .find(|tag| range.contains(tag.oid_unchecked()))
+            .cloned();
 
         latest_tag.ok_or(Git2Error::NoTagFound)
     }
@@ -301,6 +311,7 @@ mod test {
     use anyhow::{anyhow, Result};
     use cmd_lib::{run_cmd, run_fun};
     use git2::Oid;
+    use globset::GlobSet;
     use sealed_test::prelude::*;
     use speculoos::prelude::*;
 
@@ -308,6 +319,7 @@ mod test {
     use crate::git::repository::Repository;
     use crate::git::revspec::RevspecPattern;
     use crate::git::tag::Tag;
+    use crate::settings::Settings;
 
     const COCOGITTO_REPOSITORY: &str = env!("CARGO_MANIFEST_DIR");
 
@@ -377,7 +389,9 @@ mod test {
         let format_version = |release: &Release| format!("{}", release.version);
 
         // Act
-        let release = repo.get_release_range(RevspecPattern::from("0.32.1..0.32.3"))?;
+        let settings = Settings::default();
+        let release =
+            repo.get_release_range(RevspecPattern::from("0.32.1..0.32.3"), &settings, None)?;
 
         // Assert
         assert_that!(format_version(&release)).is_equal_to("0.32.3".to_string());
@@ -465,9 +479,9 @@ mod test {
         // Arrange
         let repo = Repository::open(COCOGITTO_REPOSITORY)?;
         let v1_0_0 = Oid::from_str("549070fa99986b059cbaa9457b6b6f065bbec46b")?;
-        let v1_0_0 = OidOf::Tag(Tag::new("1.0.0", Some(v1_0_0))?);
+        let v1_0_0 = OidOf::Tag(Tag::new("1.0.0", Some(v1_0_0), None)?);
         let v3_0_0 = Oid::from_str("c6508e243e2816e2d2f58828ee0c6721502958dd")?;
-        let v3_0_0 = OidOf::Tag(Tag::new("3.0.0", Some(v3_0_0))?);
+        let v3_0_0 = OidOf::Tag(Tag::new("3.0.0", Some(v3_0_0), None)?);
 
         // Act
         let range = repo.get_commit_range(&RevspecPattern::from("1.0.0..3.0.0"))?;
@@ -495,7 +509,7 @@ mod test {
         };
 
         let v1_0_0 = Oid::from_str("549070fa99986b059cbaa9457b6b6f065bbec46b")?;
-        let v1_0_0 = OidOf::Tag(Tag::new("1.0.0", Some(v1_0_0))?);
+        let v1_0_0 = OidOf::Tag(Tag::new("1.0.0", Some(v1_0_0), None)?);
 
         // Act
         let range = repo.get_commit_range(&RevspecPattern::from("1.0.0.."))?;
@@ -513,8 +527,8 @@ mod test {
         let repo = Repository::open(COCOGITTO_REPOSITORY)?;
         let head = repo.get_head_commit_oid()?;
         let head = OidOf::Other(head);
-        let mut tags = repo.all_tags()?;
-        tags.sort();
+        let index = repo.tag_index()?;
+        let tags = index.tags();
         let mut latest = tags.last().unwrap();
 
         if latest.oid().unwrap() == head.oid() {
@@ -538,9 +552,9 @@ mod test {
         // Arrange
         let repo = Repository::open(COCOGITTO_REPOSITORY)?;
         let v2_1_1 = Oid::from_str("9dcf728d2eef6b5986633dd52ecbe9e416234898")?;
-        let v2_1_1 = OidOf::Tag(Tag::new("2.1.1", Some(v2_1_1))?);
+        let v2_1_1 = OidOf::Tag(Tag::new("2.1.1", Some(v2_1_1), None)?);
         let v3_0_0 = Oid::from_str("c6508e243e2816e2d2f58828ee0c6721502958dd")?;
-        let v3_0_0 = OidOf::Tag(Tag::new("3.0.0", Some(v3_0_0))?);
+        let v3_0_0 = OidOf::Tag(Tag::new("3.0.0", Some(v3_0_0), None)?);
 
         // Act
         let range = repo.get_commit_range(&RevspecPattern::from("..3.0.0"))?;
@@ -565,7 +579,8 @@ mod test {
         };
 
         // Act
-        let mut release = repo.get_release_range(RevspecPattern::from(".."))?;
+        let settings = Settings::default();
+        let mut release = repo.get_release_range(RevspecPattern::from(".."), &settings, None)?;
         let mut count = 0;
 
         while let Some(previous) = release.previous {
@@ -609,7 +624,9 @@ mod test {
         let pattern = format!("{}..", &from[0..7]);
 
         // Act
-        let release = repo.get_release_range(RevspecPattern::from(pattern.as_str()))?;
+        let settings = Settings::default();
+        let release =
+            repo.get_release_range(RevspecPattern::from(pattern.as_str()), &settings, None)?;
 
         // Assert
         let oids: Vec<String> = release
@@ -654,7 +671,9 @@ mod test {
         let pattern = format!("{}..", &from[0..7]);
 
         // Act
-        let release = repo.get_release_range(RevspecPattern::from(pattern.as_str()))?;
+        let settings = Settings::default();
+        let release =
+            repo.get_release_range(RevspecPattern::from(pattern.as_str()), &settings, None)?;
 
         // Assert
         let head_to_v1: Vec<String> = release
@@ -676,4 +695,32 @@ mod test {
 
         Ok(())
     }
+
+    #[sealed_test]
+    fn range_to_commit_with_several_tags() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?.with_tag_rules(
+            Some("v".to_string()),
+            vec!["release-".to_string()],
+            GlobSet::empty(),
+        );
+        run_cmd!(
+            git commit --allow-empty -q -m "chore: init";
+            git commit --allow-empty -q -m "chore: release";
+            git tag v1.0.0;
+            git commit --allow-empty -q -m "feat: a feature";
+            git tag release-1.1.0;
+            git tag v1.1.0;
+        )?;
+        let head = run_fun!(git rev-parse HEAD)?;
+
+        // Act
+        let range = repo.get_commit_range(&RevspecPattern::from(format!("..{}", head).as_str()))?;
+
+        // Assert
+        assert_that!(range.to.to_string()).is_equal_to("v1.1.0".to_string());
+        assert_that!(range.from.to_string()).is_equal_to("v1.0.0".to_string());
+        assert_that!(range.commits).has_length(1);
+        Ok(())
+    }
 }
@@ -0,0 +1,189 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::git::error::Git2Error;
+use crate::git::repository::Repository;
+use tempfile::NamedTempFile;
+
+/// The signature format, read from the `gpg.format` git config
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum SigningFormat {
+    OpenPgp,
+    Ssh,
+}
+
+impl Repository {
+    pub(crate) fn signing_format(&self) -> SigningFormat {
+        let format = self
+            .0
+            .config()
+            .and_then(|config| config.get_string("gpg.format"))
+            .unwrap_or_default();
+
+        match format.as_str() {
+            "ssh" => SigningFormat::Ssh,
+            _ => SigningFormat::OpenPgp,
+        }
+    }
+
+    /// The program used to sign, read from `gpg.program`, `gpg.openpgp.program`
+    /// or `gpg.ssh.program` like git does
+    pub(crate) fn signing_program(&self, format: SigningFormat) -> String {
+        let config = self.0.config().ok();
+        let get = |key: &str| {
+            config
+                .as_ref()
+                .and_then(|config| config.get_string(key).ok())
+        };
+
+        match format {
+            SigningFormat::OpenPgp => get("gpg.openpgp.program")
+                .or_else(|| get("gpg.program"))
+                .unwrap_or_else(|| "gpg".to_string()),
+            SigningFormat::Ssh => {
+                get("gpg.ssh.program").unwrap_or_else(|| "ssh-keygen".to_string())
+            }
+        }
+    }
+
+    /// Sign a raw git object (commit or tag) and return the armored signature
+    pub(crate) fn sign_buffer(&self, content: &str) -> Result<String, Git2Error> {
+        let key = self.signin_key().ok();
+        let format = self.signing_format();
+        let program = self.signing_program(format);
+        match format {
+            SigningFormat::OpenPgp => gpg_sign_string(&program, key, content),
+            SigningFormat::Ssh => {
+                let key = key.ok_or_else(|| {
+                    Git2Error::GpgError("user.signingKey is required for ssh signing".to_string())
+                })?;
+                ssh_sign_string(&program, &key, content)
+            }
+        }
+    }
+}
+
+fn gpg_sign_string(program: &str, key: Option<String>, content: &str) -> Result<String, Git2Error> {
+    let mut command = Command::new(program);
+    command.args(["--armor", "--detach-sig"]);
+
+    if let Some(key) = &key {
+        command.args(["--default-key", key]);
+    }
+
+    run_signing_program(command, content)
+}
+
+// Mirrors git: a literal public key is resolved through the ssh-agent, otherwise the key is a file
+fn ssh_sign_string(program: &str, key: &str, content: &str) -> Result<String, Git2Error> {
+    let mut command = Command::new(program);
+    command.args(["-Y", "sign", "-n", "git"]);
+
+    let literal_key = key.strip_prefix("key::").or_else(|| {
+        if key.starts_with("ssh-") {
+            Some(key)
+        } else {
+            None
+        }
+    });
+
+    // Keep the temporary public key alive until the signature is produced
+    let _key_file = match literal_key {
+        Some(literal_key) => {
+            let mut key_file = NamedTempFile::new()?;
+            writeln!(key_file, "{}", literal_key)?;
+            command.arg("-U").arg("-f").arg(key_file.path());
+            Some(key_file)
+        }
+        None => {
+            command.arg("-f").arg(shellexpand_home(key));
+            None
+        }
+    };
+
+    run_signing_program(command, content)
+}
+
+fn shellexpand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(path), Ok(home)) => format!("{}/{}", home, path),
+        _ => path.to_string(),
+    }
+}
+
+fn run_signing_program(mut command: Command, content: &str) -> Result<String, Git2Error> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| Git2Error::GpgError(format!("failed to run `{}`: {}", program, err)))?;
+
+    // The program may exit before reading its input, its failure is reported first
+    let write_result = child
+        .stdin
+        .take()
+        .expect("signing program stdin is piped")
+        .write_all(content.as_bytes());
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(Git2Error::GpgError(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    write_result?;
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::git::repository::Repository;
+    use crate::git::sign::SigningFormat;
+    use anyhow::Result;
+    use cmd_lib::run_cmd;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    #[sealed_test]
+    fn signing_program_from_git_config() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        run_cmd!(
+            git config --local gpg.program gpg2;
+            git config --local gpg.ssh.program /opt/ssh-keygen;
+        )?;
+
+        // Act
+        let gpg = repo.signing_program(SigningFormat::OpenPgp);
+        let ssh = repo.signing_program(SigningFormat::Ssh);
+
+        // Assert
+        assert_that!(gpg.as_str()).is_equal_to("gpg2");
+        assert_that!(ssh.as_str()).is_equal_to("/opt/ssh-keygen");
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn sign_with_configured_program() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        std::fs::write(
+            "fake-gpg",
+            "#!/bin/sh\ncat > /dev/null\necho FAKE SIGNATURE\n",
+        )?;
+        run_cmd!(
+            chmod +x fake-gpg;
+            git config --local gpg.program ./fake-gpg;
+        )?;
+
+        // Act
+        let signature = repo.sign_buffer("tree 1234")?;
+
+        // Assert
+        assert_that!(signature.as_str()).is_equal_to("FAKE SIGNATURE\n");
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::git::error::Git2Error;
 use crate::git::repository::Repository;
+use git2::{Oid, StashFlags};
 
 impl Repository {
     pub(crate) fn stash_failed_version(&mut self, version: &str) -> Result<(), Git2Error> {
@@ -10,13 +11,40 @@ impl Repository {
             .map(|_| ())
             .map_err(Git2Error::StashError)
     }
+
+    /// Stash the changes found before a bump, untracked files included, and return the stash id
+    pub(crate) fn stash_dirty_changes(&mut self, version: &str) -> Result<Oid, Git2Error> {
+        let sig = self.0.signature()?;
+        let message = &format!("cog_bump_dirty_{}", version);
+        self.0
+            .stash_save(&sig, message, Some(StashFlags::INCLUDE_UNTRACKED))
+            .map_err(Git2Error::StashError)
+    }
+
+    /// Apply then drop the stash `oid`, wherever later stashes moved it in the stash list
+    pub(crate) fn pop_stash(&mut self, oid: Oid) -> Result<(), Git2Error> {
+        let mut index = None;
+        self.0
+            .stash_foreach(|stash_index, _, stash_oid| {
+                if *stash_oid == oid {
+                    index = Some(stash_index);
+                }
+                index.is_none()
+            })
+            .map_err(Git2Error::StashError)?;
+
+        let index = index.ok_or_else(|| {
+            Git2Error::StashError(git2::Error::from_str(&format!("stash {} not found", oid)))
+        })?;
+        self.0.stash_pop(index, None).map_err(Git2Error::StashError)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::git::repository::Repository;
     use anyhow::Result;
-    use cmd_lib::run_cmd;
+    use cmd_lib::{run_cmd, run_fun};
     use sealed_test::prelude::*;
     use speculoos::prelude::*;
 
@@ -41,4 +69,31 @@ mod test {
         assert_that!(statuses).is_empty();
         Ok(())
     }
+
+    #[sealed_test]
+    fn should_stash_and_restore_dirty_changes() -> Result<()> {
+        // Arrange
+        let mut repo = Repository::init(".")?;
+        run_cmd!(
+            echo "tracked" > tracked;
+            git add .;
+            git commit -m "Initial commit";
+            echo "changes" > tracked;
+            echo "untracked" > untracked;
+        )?;
+
+        // Act
+        let stash = repo.stash_dirty_changes("1.0.0")?;
+        let stashed = repo.get_statuses()?.0;
+        run_cmd!(echo "failed" > tracked;)?;
+        repo.stash_failed_version("1.0.0")?;
+        repo.pop_stash(stash)?;
+
+        // Assert
+        assert_that!(stashed).is_empty();
+        assert_that!(repo.get_statuses()?.0).has_length(2);
+        assert_that!(run_fun!(git stash list --format=%s)?)
+            .is_equal_to("On master: cog_bump_1.0.0".to_string());
+        Ok(())
+    }
 }
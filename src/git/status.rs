@@ -42,7 +42,22 @@ pub enum Changes {
     Modified(String),
 }
 
+impl Status {
+    /// The changed path, relative to the working tree root
+    pub(crate) fn path(&self) -> &str {
+        match self {
+            Status::Untracked(changes) | Status::UnCommitted(changes) => changes.path(),
+        }
+    }
+}
+
 impl Changes {
+    pub(crate) fn path(&self) -> &str {
+        match self {
+            New(path) | Renamed(path) | Deleted(path) | TypeChange(path) | Modified(path) => path,
+        }
+    }
+
     pub(crate) fn to_string(&self, color: &str) -> String {
         match &self {
             New(p) => format!("{}: {}", "new".color(color), p),
@@ -105,7 +120,7 @@ impl From<Git2Statuses<'_>> for Statuses {
     }
 }
 
-impl<'a, 'b: 'a> From<Git2StatusEntry<'b>> for Status {
+impl<'b> From<Git2StatusEntry<'b>> for Status {
     fn from(status: Git2StatusEntry<'b>) -> Self {
         let path = status.path().unwrap_or("invalid utf8 path").to_string();
         match status.status() {
@@ -184,7 +199,7 @@ mod test {
 
         // Assert
         assert_that!(statuses.iter())
-            .contains(&super::Status::Untracked(Changes::New("file".into())));
+            .contains(super::Status::Untracked(Changes::New("file".into())));
         assert_that!(statuses).has_length(1);
         Ok(())
     }
@@ -1,21 +1,24 @@
 use crate::git::error::{Git2Error, TagError};
 use crate::git::repository::Repository;
-use crate::SETTINGS;
-use git2::string_array::StringArray;
-use git2::Oid;
-use git2::Tag as Git2Tag;
+use git2::{ObjectType, Oid};
 use semver::Version;
 use std::cmp::Ordering;
-use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Formatter;
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// An unreachable remote must not hold a bump for long
+const REMOTE_TAG_TIMEOUT: Duration = Duration::from_secs(10);
 
 impl Repository {
     /// Given a tag name return a [`Tag`], this will fail if the requested
     /// tag (without configured prefix) is not semver compliant or if the tag
     /// does not exist.
     pub fn resolve_tag(&self, tag: &str) -> Result<Tag, TagError> {
-        let without_prefix = Tag::strip_prefix(tag)?;
+        let without_prefix = Tag::strip_prefix(tag, self.tag_prefix_of(tag))?;
 
         // Ensure the tag is SemVer compliant
         Version::parse(without_prefix).map_err(|err| TagError::semver(without_prefix, err))?;
@@ -24,41 +27,190 @@ impl Repository {
     }
 
     /// Resolve a tag from a given `&str`, return an error if the tag is not found.
+    /// Annotated tags are peeled to the commit they point to.
     fn resolve_lightweight_tag(&self, tag: &str) -> Result<Tag, TagError> {
         self.0
             .resolve_reference_from_short_name(tag)
+            .and_then(|reference| reference.peel_to_commit())
             .map_err(|err| TagError::not_found(tag, err))
-            .map(|reference| reference.target().unwrap())
-            .map(|oid| Tag::new(tag, Some(oid)))?
+            .map(|commit| Tag::new(tag, Some(commit.id()), self.tag_prefix_of(tag)))?
     }
 
-    pub(crate) fn create_tag(&self, name: &str) -> Result<(), Git2Error> {
+    /// Tag HEAD, `force` moves the tag when it already exists
+    pub(crate) fn create_tag(&self, name: &str, sign: bool, force: bool) -> Result<(), Git2Error> {
         if self.get_diff(true).is_some() {
             let statuses = self.get_statuses()?;
             return Err(Git2Error::ChangesNeedToBeCommitted(statuses));
         }
 
         let head = self.get_head_commit().unwrap();
+        if sign {
+            return self.create_signed_tag(name, head.id(), name, force);
+        }
+
         self.0
-            .tag_lightweight(name, &head.into_object(), false)
-            .map(|_| ())
+            .tag_lightweight(name, &head.into_object(), force)
+            .map(|_| self.invalidate_tag_index())
             .map_err(Git2Error::from)
     }
 
-    pub(crate) fn get_latest_tag(&self) -> Result<Tag, TagError> {
-        let tags: Vec<Tag> = self.all_tags()?;
+    /// Tag HEAD with an annotated tag carrying `message`, `force` moves the tag when it already exists
+    pub(crate) fn create_annotated_tag(
+        &self,
+        name: &str,
+        message: &str,
+        sign: bool,
+        force: bool,
+    ) -> Result<(), Git2Error> {
+        let head = self.get_head_commit()?;
+        if sign {
+            return self.create_signed_tag(name, head.id(), message, force);
+        }
+
+        let tagger = self.0.signature()?;
+        self.0
+            .tag(name, &head.into_object(), &tagger, message, force)
+            .map(|_| self.invalidate_tag_index())
+            .map_err(Git2Error::from)
+    }
+
+    /// Tag a past commit with a lightweight tag, an existing tag is never moved
+    pub(crate) fn tag_commit(&self, name: &str, target: Oid) -> Result<(), Git2Error> {
+        let commit = self.0.find_commit(target)?;
+        self.0
+            .tag_lightweight(name, commit.as_object(), false)
+            .map(|_| self.invalidate_tag_index())
+            .map_err(Git2Error::from)
+    }
+
+    /// Whether a tag with this exact name exists locally
+    pub(crate) fn tag_exists(&self, name: &str) -> bool {
+        self.0
+            .find_reference(&format!("refs/tags/{}", name))
+            .is_ok()
+    }
+
+    /// Whether the default remote has a tag with this exact name, `None` when there is no remote
+    /// or it cannot be reached in time. libgit2 needs credentials to list remote refs, `git` is
+    /// used instead, with credential and host key prompts disabled.
+    pub(crate) fn remote_tag_exists(&self, name: &str) -> Option<(String, bool)> {
+        let remote = self.default_remote()?;
+        let mut child = Command::new("git")
+            .arg("--git-dir")
+            .arg(self.0.path())
+            .args(["ls-remote", "--tags", "--exit-code", &remote])
+            .arg(format!("refs/tags/{}", name))
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        let start = Instant::now();
+        let status = loop {
+            match child.try_wait().ok()? {
+                Some(status) => break status,
+                None if start.elapsed() >= REMOTE_TAG_TIMEOUT => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return None;
+                }
+                None => thread::sleep(Duration::from_millis(50)),
+            }
+        };
+
+        // `--exit-code` exits with 2 when no ref matched
+        match status.code() {
+            Some(0) => Some((remote, true)),
+            Some(2) => Some((remote, false)),
+            _ => None,
+        }
+    }
+
+    // git2 cannot sign tags, the annotated tag object is written by hand with its signature appended
+    fn create_signed_tag(
+        &self,
+        name: &str,
+        target: Oid,
+        message: &str,
+        force: bool,
+    ) -> Result<(), Git2Error> {
+        let tagger = self.0.signature()?;
+        let offset = tagger.when().offset_minutes();
+        let tagger = format!(
+            "{} <{}> {} {}{:02}{:02}",
+            String::from_utf8_lossy(tagger.name_bytes()),
+            String::from_utf8_lossy(tagger.email_bytes()),
+            tagger.when().seconds(),
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 60,
+            offset.abs() % 60
+        );
+
+        let content = format!(
+            "object {}\ntype commit\ntag {}\ntagger {}\n\n{}\n",
+            target,
+            name,
+            tagger,
+            message.trim_end()
+        );
+
+        let signature = self.sign_buffer(&content)?;
+        let tag = format!("{}{}", content, signature);
+        let oid = self.0.odb()?.write(ObjectType::Tag, tag.as_bytes())?;
+        self.0.reference(
+            &format!("refs/tags/{}", name),
+            oid,
+            force,
+            &format!("tag: {}", name),
+        )?;
 
-        tags.into_iter().max().ok_or(TagError::NoTag)
+        self.invalidate_tag_index();
+        Ok(())
     }
 
-    pub(crate) fn all_tags(&self) -> Result<Vec<Tag>, TagError> {
-        Ok(self
+    pub(crate) fn get_latest_tag(&self) -> Result<Tag, TagError> {
+        self.tag_index()?.latest().cloned().ok_or(TagError::NoTag)
+    }
+
+    /// The index of the repository tags, resolved once and shared by every tag lookup
+    pub(crate) fn tag_index(&self) -> Result<Rc<TagIndex>, TagError> {
+        if let Some(index) = self.2.borrow().as_ref() {
+            return Ok(Rc::clone(index));
+        }
+
+        let mut tags: Vec<Tag> = self
             .tags()?
             .iter()
-            .flatten()
+            .filter(|tag| !self.is_ignored_tag(tag))
             .map(|tag| self.resolve_lightweight_tag(tag))
             .filter_map(Result::ok)
-            .collect())
+            .filter(|tag| match (self.version_ceiling(), tag.to_version()) {
+                (Some(ceiling), Ok(version)) => version < *ceiling,
+                _ => true,
+            })
+            .collect();
+
+        // Among the tags of a version, the canonical prefix wins over the legacy ones
+        let is_canonical = |tag: &Tag| tag.prefix.as_deref() == self.tag_prefix();
+        tags.sort_by(|tag, other| {
+            tag.cmp(other)
+                .then_with(|| is_canonical(tag).cmp(&is_canonical(other)))
+                .then_with(|| {
+                    tag.to_string_with_prefix()
+                        .cmp(&other.to_string_with_prefix())
+                })
+        });
+
+        let index = Rc::new(TagIndex { tags });
+        *self.2.borrow_mut() = Some(Rc::clone(&index));
+        Ok(index)
+    }
+
+    fn invalidate_tag_index(&self) {
+        self.2.borrow_mut().take();
     }
 
     pub(crate) fn get_latest_tag_oid(&self) -> Result<Oid, TagError> {
@@ -66,33 +218,62 @@ impl Repository {
             .map(|tag| tag.oid_unchecked().to_owned())
     }
 
-    fn tags(&self) -> Result<StringArray, TagError> {
-        let pattern = SETTINGS
-            .tag_prefix
-            .as_ref()
-            .map(|prefix| format!("{}*", prefix));
+    // Tags starting with the canonical or one of the legacy prefixes, all tags without prefix
+    fn tags(&self) -> Result<Vec<String>, TagError> {
+        let patterns: Vec<Option<String>> = match self.tag_prefix() {
+            None => vec![None],
+            Some(prefix) => std::iter::once(prefix)
+                .chain(self.legacy_tag_prefixes().iter().map(String::as_str))
+                .map(|prefix| Some(format!("{}*", prefix)))
+                .collect(),
+        };
+
+        let mut tags = vec![];
+        for pattern in patterns {
+            let names = self
+                .0
+                .tag_names(pattern.as_deref())
+                .map_err(|err| TagError::NoMatchFound { pattern, err })?;
+            tags.extend(names.iter().flatten().map(str::to_string));
+        }
 
-        self.0
-            .tag_names(pattern.as_deref())
-            .map_err(|err| TagError::NoMatchFound { pattern, err })
+        tags.sort();
+        tags.dedup();
+        Ok(tags)
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Tag {
-    tag: String,
-    oid: Option<Oid>,
+/// The tags matching the repository tag prefix, resolved to their commit and sorted by version,
+/// tags that are not versions first. When several tags name the same version, the one with the
+/// canonical prefix sorts last, then tags sort by name: lookups returning the last match are
+/// deterministic.
+#[derive(Debug, Default)]
+pub(crate) struct TagIndex {
+    tags: Vec<Tag>,
 }
 
-impl TryFrom<Git2Tag<'_>> for Tag {
-    type Error = TagError;
+impl TagIndex {
+    pub(crate) fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+
+    pub(crate) fn latest(&self) -> Option<&Tag> {
+        self.tags.last()
+    }
 
-    fn try_from(tag: Git2Tag) -> std::result::Result<Self, Self::Error> {
-        let name = tag.name().expect("Unexpected unnamed tag");
-        Self::new(name, Some(tag.id()))
+    /// The greatest version tagged on the given commit, preferring the canonical prefix
+    pub(crate) fn find_by_oid(&self, oid: &Oid) -> Option<&Tag> {
+        self.tags.iter().rev().find(|tag| tag.oid() == Some(oid))
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Tag {
+    tag: String,
+    oid: Option<Oid>,
+    prefix: Option<String>,
+}
+
 impl Tag {
     // Tag always contains an oid unless it was created before the tag exist.
     // The only case where we do that is while creating the changelog during `cog bump`.
@@ -106,13 +287,21 @@ impl Tag {
         self.oid.as_ref()
     }
 
-    pub(crate) fn new(name: &str, oid: Option<Oid>) -> Result<Tag, TagError> {
-        let tag = Tag::strip_prefix(name)?.to_string();
-        Ok(Tag { tag, oid })
+    pub(crate) fn new(name: &str, oid: Option<Oid>, prefix: Option<&str>) -> Result<Tag, TagError> {
+        let tag = Tag::strip_prefix(name, prefix)?.to_string();
+        Ok(Tag {
+            tag,
+            oid,
+            prefix: prefix.map(str::to_string),
+        })
+    }
+
+    pub(crate) fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
     }
 
     pub(crate) fn to_string_with_prefix(&self) -> String {
-        match SETTINGS.tag_prefix.as_ref() {
+        match self.prefix.as_ref() {
             None => self.tag.to_string(),
             Some(prefix) => format!("{}{}", prefix, self.tag),
         }
@@ -122,8 +311,8 @@ impl Tag {
         Version::parse(&self.tag).map_err(|err| TagError::semver(&self.tag, err))
     }
 
-    fn strip_prefix(tag: &str) -> Result<&str, TagError> {
-        match SETTINGS.tag_prefix.as_ref() {
+    fn strip_prefix<'a>(tag: &'a str, prefix: Option<&str>) -> Result<&'a str, TagError> {
+        match prefix {
             None => Ok(tag),
             Some(prefix) => tag
                 .strip_prefix(prefix)
@@ -143,24 +332,65 @@ impl fmt::Display for Tag {
 
 impl Ord for Tag {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.to_version().ok().cmp(&other.to_version().ok())
     }
 }
 
 impl PartialOrd<Tag> for Tag {
     fn partial_cmp(&self, other: &Tag) -> Option<Ordering> {
-        Some(self.to_version().ok().cmp(&other.to_version().ok()))
+        Some(self.cmp(other))
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::git::repository::Repository;
+    use crate::git::tag::Tag;
     use anyhow::Result;
-    use cmd_lib::run_cmd;
+    use cmd_lib::{run_cmd, run_fun};
+    use globset::{Glob, GlobSet, GlobSetBuilder};
     use sealed_test::prelude::*;
     use speculoos::prelude::*;
 
+    #[sealed_test]
+    fn resolve_annotated_tag_to_commit() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        run_cmd!(
+            git commit --allow-empty -m "first commit";
+            git tag -a the_tag -m "annotated";
+        )?;
+        let commit = run_fun!(git rev-parse HEAD)?;
+
+        // Act
+        let tag = repo.resolve_lightweight_tag("the_tag")?;
+
+        // Assert
+        assert_that!(tag.oid().map(|oid| oid.to_string())).is_equal_to(Some(commit));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn create_ssh_signed_tag() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        run_cmd!(
+            ssh-keygen -q -t ed25519 -N "" -C "test@cocogitto.org" -f signing_key;
+            git config --local gpg.format ssh;
+            git config --local user.signingKey signing_key;
+            git commit --allow-empty -m "first commit";
+        )?;
+
+        // Act
+        repo.create_tag("1.0.0", true, false)?;
+
+        // Assert
+        let tag = run_fun!(git cat-file tag 1.0.0)?;
+        assert_that!(tag).contains("-----BEGIN SSH SIGNATURE-----");
+        assert_that!(repo.resolve_lightweight_tag("1.0.0")).is_ok();
+        Ok(())
+    }
+
     #[sealed_test]
     fn resolve_lightweight_tag_ok() -> Result<()> {
         // Arrange
@@ -260,4 +490,137 @@ mod test {
         assert_that!(tag).is_err();
         Ok(())
     }
+
+    #[sealed_test]
+    fn tag_index_is_refreshed_on_tag_creation() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        run_cmd!(
+            git commit --allow-empty -m "first commit";
+            git tag 0.1.0;
+            git tag 0.1.1;
+            git commit --allow-empty -m "second commit";
+        )?;
+        let first_commit = repo.get_latest_tag_oid()?;
+
+        // Act
+        repo.create_tag("0.2.0", false, false)?;
+
+        // Assert
+        let index = repo.tag_index()?;
+        assert_that!(index.latest().map(Tag::to_string_with_prefix))
+            .is_equal_to(Some("0.2.0".to_string()));
+        assert_that!(index
+            .find_by_oid(&first_commit)
+            .map(Tag::to_string_with_prefix))
+        .is_equal_to(Some("0.1.1".to_string()));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn create_tag_refuses_existing_tag_unless_forced() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?;
+        run_cmd!(
+            git commit --allow-empty -m "first commit";
+            git tag 0.1.0;
+            git commit --allow-empty -m "second commit";
+        )?;
+        let head = repo.get_head_commit_oid()?;
+
+        // Act
+        let refused = repo.create_tag("0.1.0", false, false);
+        repo.create_tag("0.1.0", false, true)?;
+
+        // Assert
+        assert_that!(refused).is_err();
+        assert_that!(repo.tag_exists("0.1.0")).is_true();
+        assert_that!(repo.resolve_tag("0.1.0")?.oid()).is_equal_to(Some(&head));
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn get_latest_tag_with_legacy_prefix() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?.with_tag_rules(
+            Some("v".to_string()),
+            vec!["release-".to_string(), "version-".to_string()],
+            GlobSet::empty(),
+        );
+        run_cmd!(
+            git commit --allow-empty -m "first commit";
+            git tag v0.1.0;
+            git commit --allow-empty -m "second commit";
+            git tag version-0.2.0;
+            git commit --allow-empty -m "third commit";
+            git tag release-0.3.0;
+            git tag other-1.0.0;
+        )?;
+
+        // Act
+        let tag = repo.get_latest_tag()?;
+
+        // Assert
+        assert_that!(tag.to_string_with_prefix()).is_equal_to("release-0.3.0".to_string());
+        assert_that!(tag.to_version()?.to_string()).is_equal_to("0.3.0".to_string());
+        assert_that!(repo.resolve_tag("version-0.2.0")).is_ok();
+        assert_that!(repo.tag_index()?.tags().len()).is_equal_to(3);
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn get_latest_tag_skips_ignored_tags() -> Result<()> {
+        // Arrange
+        let ignored_tags = GlobSetBuilder::new()
+            .add(Glob::new("*-nightly*")?)
+            .add(Glob::new("9.*")?)
+            .build()?;
+        let repo = Repository::init(".")?.with_tag_rules(None, vec![], ignored_tags);
+        run_cmd!(
+            git commit --allow-empty -m "first commit";
+            git tag 0.1.0;
+            git commit --allow-empty -m "second commit";
+            git tag 0.2.0-nightly.1;
+            git tag 9.0.0;
+        )?;
+
+        // Act
+        let tag = repo.get_latest_tag()?;
+
+        // Assert
+        assert_that!(tag.to_string_with_prefix()).is_equal_to("0.1.0".to_string());
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn several_tags_on_one_commit_prefer_the_canonical_prefix() -> Result<()> {
+        // Arrange
+        let repo = Repository::init(".")?.with_tag_rules(
+            Some("v".to_string()),
+            vec!["release-".to_string(), "version-".to_string()],
+            GlobSet::empty(),
+        );
+        run_cmd!(
+            git commit --allow-empty -m "first commit";
+            git tag v1.1.0;
+            git commit --allow-empty -m "second commit";
+            git tag version-1.2.0;
+            git tag v1.2.0;
+            git tag release-1.2.0;
+            git tag vnext;
+        )?;
+        let head = repo.get_head_commit_oid()?;
+
+        // Act
+        let index = repo.tag_index()?;
+
+        // Assert
+        assert_that!(index.latest().map(Tag::to_string_with_prefix))
+            .is_equal_to(Some("v1.2.0".to_string()));
+        assert_that!(index.find_by_oid(&head).map(Tag::to_string_with_prefix))
+            .is_equal_to(Some("v1.2.0".to_string()));
+        assert_that!(index.tags().first().map(Tag::to_string_with_prefix))
+            .is_equal_to(Some("vnext".to_string()));
+        Ok(())
+    }
 }
@@ -1,7 +1,9 @@
 use crate::hook;
+use crate::hook::HookOutput;
 use serde::de::StdError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::process::ExitStatus;
 
 #[derive(Debug)]
 pub struct HookParseError {
@@ -32,3 +34,18 @@ impl Display for HookParseError {
 }
 
 impl StdError for HookParseError {}
+
+/// A hook that exited with a failure status, along with the output it produced
+#[derive(Debug)]
+pub(crate) struct FailedHook {
+    pub(crate) status: ExitStatus,
+    pub(crate) output: HookOutput,
+}
+
+impl Display for FailedHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "hook failed with status {}", self.status)
+    }
+}
+
+impl StdError for FailedHook {}
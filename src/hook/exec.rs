@@ -0,0 +1,133 @@
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+
+use crate::settings::HookShell;
+
+// Set while cog's stdout is reserved to a machine readable document
+static STDOUT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Send what hooks write to stdout to stderr instead, e.g. when cog prints JSON to stdout.
+/// Captured output is always reported through the [`reporter`](crate::reporter).
+pub fn redirect_stdout_to_stderr(enabled: bool) {
+    STDOUT_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
+/// Exit status and, when captured, output of a single process run
+pub(super) struct Execution {
+    pub(super) status: ExitStatus,
+    pub(super) stdout: Option<String>,
+    pub(super) stderr: Option<String>,
+}
+
+/// Build the process running `script` with the given shell, `HookShell::None` splits the
+/// script into a program and its arguments instead
+pub(crate) fn shell_command(shell: HookShell, script: &str) -> Result<Command> {
+    let (program, args) = match shell {
+        HookShell::Sh => ("sh", vec!["-c"]),
+        HookShell::Bash => ("bash", vec!["-c"]),
+        HookShell::Cmd => ("cmd", vec!["/C"]),
+        HookShell::Powershell => ("powershell", vec!["-NoProfile", "-Command"]),
+        HookShell::None => {
+            let words = shell_words::split(script)?;
+            let Some((program, args)) = words.split_first() else {
+                bail!("hook must not be an empty command");
+            };
+
+            let mut command = Command::new(program);
+            command.args(args);
+            return Ok(command);
+        }
+    };
+
+    let mut command = Command::new(program);
+    command.args(args).arg(script);
+    Ok(command)
+}
+
+/// Run the given command to completion, killing it once `timeout` is elapsed.
+/// When `capture` is set, stdout and stderr are piped and returned instead of being inherited.
+pub(super) fn execute(
+    mut command: Command,
+    timeout: Option<Duration>,
+    capture: bool,
+) -> Result<Execution> {
+    if capture {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    } else if STDOUT_TO_STDERR.load(Ordering::Relaxed) {
+        command.stdout(std::io::stderr());
+    }
+
+    // A hook leads its own process group when it can time out, so the processes it started are
+    // killed along with it. Others stay in cog's group to keep reading from the terminal.
+    #[cfg(unix)]
+    if timeout.is_some() {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn()?;
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+
+    let status = match timeout {
+        None => child.wait()?,
+        Some(timeout) => wait_timeout(&mut child, timeout)?,
+    };
+
+    Ok(Execution {
+        status,
+        stdout: stdout.map(join_output),
+        stderr: stderr.map(join_output),
+    })
+}
+
+fn wait_timeout(child: &mut Child, timeout: Duration) -> Result<ExitStatus> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        if start.elapsed() >= timeout {
+            kill(child)?;
+            child.wait()?;
+            bail!("hook timed out after {}s", timeout.as_secs());
+        }
+
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(unix)]
+fn kill(child: &mut Child) -> std::io::Result<()> {
+    // SAFETY: `kill` has no memory safety requirement, the child was spawned as its group leader
+    if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } == 0 {
+        Ok(())
+    } else {
+        child.kill()
+    }
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) -> std::io::Result<()> {
+    child.kill()
+}
+
+// Pipes are drained on their own thread so a verbose process never blocks on a full pipe
+fn read_to_end<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut output = vec![];
+        let _ = pipe.read_to_end(&mut output);
+        output
+    })
+}
+
+fn join_output(handle: JoinHandle<Vec<u8>>) -> String {
+    let output = handle.join().unwrap_or_default();
+    String::from_utf8_lossy(&output).to_string()
+}
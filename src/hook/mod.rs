@@ -1,18 +1,28 @@
 mod error;
+mod exec;
 mod parser;
 
+pub(crate) use error::FailedHook;
+pub use exec::redirect_stdout_to_stderr;
+pub(crate) use exec::shell_command;
+
 use std::collections::VecDeque;
 use std::fmt;
 use std::ops::Range;
-use std::process::Command;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
-use semver::Version;
+use semver::{BuildMetadata, Prerelease, Version};
 
-use crate::SETTINGS;
-use parser::Token;
+use crate::error::CocogittoError;
+use crate::settings::{HookConfig, HookShell};
+use parser::{Component, Filter, Token};
 
-use anyhow::{anyhow, ensure, Result};
+use crate::reporter::{debug, info, warn};
+use anyhow::{anyhow, bail, ensure, Result};
+use globset::Glob;
+use serde::Serialize;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct VersionSpan {
@@ -22,16 +32,24 @@ pub struct VersionSpan {
 
 pub(crate) struct HookVersion {
     pub prefixed_tag: String,
+    tag_prefix: Option<String>,
 }
 
 impl HookVersion {
     pub(crate) fn new(tag: &str) -> Self {
         HookVersion {
             prefixed_tag: tag.to_string(),
+            tag_prefix: None,
         }
     }
+
+    pub(crate) fn with_tag_prefix(mut self, tag_prefix: Option<&str>) -> Self {
+        self.tag_prefix = tag_prefix.map(str::to_string);
+        self
+    }
+
     pub(crate) fn to_version(&self) -> Result<Version> {
-        match SETTINGS.tag_prefix.as_ref() {
+        match self.tag_prefix.as_ref() {
             Some(prefix) => {
                 if self.prefixed_tag.starts_with(prefix) {
                     let version = self.prefixed_tag.strip_prefix(prefix);
@@ -67,6 +85,8 @@ impl VersionSpan {
         }?;
 
         let mut amount = 1;
+        let mut component = None;
+        let mut output = None;
 
         while let Some(token) = self.tokens.pop_front() {
             match token {
@@ -88,11 +108,41 @@ impl VersionSpan {
                 // set  build metadata and prerelease
                 Token::PreRelease(pre_release) => version.pre = pre_release,
                 Token::BuildMetadata(build) => version.build = build,
+                // select a single version component, rendered once all version filters are applied
+                Token::Component(selected) => component = Some(selected),
+                // version filters, the parser only accepts them before any string filter
+                Token::Filter(Filter::StripPreRelease) => version.pre = Prerelease::EMPTY,
+                Token::Filter(Filter::StripBuildMetadata) => version.build = BuildMetadata::EMPTY,
+                // string filters
+                Token::Filter(filter) => {
+                    let value = output.unwrap_or_else(|| render_component(&version, component));
+                    output = Some(apply_filter(filter, value));
+                }
                 _ => unreachable!("Unexpected parsing error"),
             }
         }
 
-        Ok(version.to_string())
+        Ok(output.unwrap_or_else(|| render_component(&version, component)))
+    }
+}
+
+fn render_component(version: &Version, component: Option<Component>) -> String {
+    match component {
+        None => version.to_string(),
+        Some(Component::Major) => version.major.to_string(),
+        Some(Component::Minor) => version.minor.to_string(),
+        Some(Component::Patch) => version.patch.to_string(),
+        Some(Component::PreRelease) => version.pre.to_string(),
+        Some(Component::BuildMetadata) => version.build.to_string(),
+    }
+}
+
+fn apply_filter(filter: Filter, value: String) -> String {
+    match filter {
+        Filter::Upper => value.to_uppercase(),
+        Filter::Lower => value.to_lowercase(),
+        Filter::Quote => shell_words::quote(&value).to_string(),
+        Filter::StripPreRelease | Filter::StripBuildMetadata => value,
     }
 }
 
@@ -121,40 +171,261 @@ impl HookSpan {
 }
 
 #[derive(Debug)]
-pub struct Hook(String);
+pub struct Hook {
+    command: String,
+    cwd: Option<PathBuf>,
+    env: Vec<(&'static str, String)>,
+    shell: HookShell,
+    name: Option<String>,
+    capture_output: bool,
+    pub(crate) policy: HookPolicy,
+}
+
+/// The outcome of a hook run, `stdout` and `stderr` are only available when the output was captured
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct HookOutput {
+    pub command: String,
+    pub status: Option<i32>,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+    /// The hook failed, and its policy allowed the failure
+    pub failed: bool,
+}
+
+/// How a hook execution is supervised: timeout, retries and failure tolerance
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct HookPolicy {
+    pub timeout: Option<Duration>,
+    pub retries: u32,
+    pub allow_failure: bool,
+}
+
+impl fmt::Display for HookPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.timeout {
+            Some(timeout) => write!(f, "timeout: {}s", timeout.as_secs())?,
+            None => write!(f, "timeout: none")?,
+        };
+
+        write!(
+            f,
+            ", retries: {}, allow_failure: {}",
+            self.retries, self.allow_failure
+        )
+    }
+}
 
 impl FromStr for Hook {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         ensure!(!s.is_empty(), "hook must not be an empty string");
-        Ok(Hook(s.to_string()))
+        Ok(Hook {
+            command: s.to_string(),
+            cwd: None,
+            env: vec![],
+            shell: HookShell::default(),
+            name: None,
+            capture_output: false,
+            policy: HookPolicy::default(),
+        })
     }
 }
 
 impl fmt::Display for Hook {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(&self.0)
+        f.write_str(&self.command)
     }
 }
 
+// `{{message}}` is quoted with POSIX rules, `cmd` and `powershell` cannot safely receive
+// arbitrary text in their command line, hooks run with them read `COG_COMMIT_MESSAGE` instead
+fn ensure_message_quotable(shell: HookShell) -> Result<()> {
+    let shell = match shell {
+        HookShell::Sh | HookShell::Bash | HookShell::None => return Ok(()),
+        HookShell::Cmd => "cmd",
+        HookShell::Powershell => "powershell",
+    };
+
+    bail!(
+        "`{{{{message}}}}` cannot be quoted for {}, read the COG_COMMIT_MESSAGE environment \
+         variable instead",
+        shell
+    )
+}
+
 impl Hook {
+    /// Build a hook from its configuration, a relative `cwd` is resolved against `repo_dir`
+    /// and `default_shell` is used unless the hook sets its own shell
+    pub(crate) fn from_config(
+        config: &HookConfig,
+        repo_dir: Option<&Path>,
+        default_shell: HookShell,
+    ) -> Result<Self> {
+        let mut hook = Hook::from_str(&config.command)?;
+        hook.shell = config.shell.unwrap_or(default_shell);
+        hook.cwd = config.cwd().map(|cwd| match repo_dir {
+            Some(repo_dir) if cwd.is_relative() => repo_dir.join(cwd),
+            _ => cwd.to_path_buf(),
+        });
+        hook.name = config.name.clone();
+        hook.capture_output = config.capture_output;
+        hook.policy = HookPolicy {
+            timeout: config.timeout.map(Duration::from_secs),
+            retries: config.retries,
+            allow_failure: config.allow_failure,
+        };
+
+        Ok(hook)
+    }
+
+    /// Check a hook configuration without running it: the command must not be empty, its
+    /// arguments must be parsable when run without shell, bump hooks version placeholders
+    /// must be valid and commit hooks can only use `{{message}}` with a POSIX shell
+    pub(crate) fn validate(
+        config: &HookConfig,
+        default_shell: HookShell,
+        bump: bool,
+    ) -> Result<()> {
+        let hook = Hook::from_config(config, None, default_shell)?;
+        if bump {
+            parser::parse(&hook.command)?;
+        }
+
+        if hook.shell == HookShell::None {
+            shell_words::split(&hook.command)?;
+        }
+
+        if !bump && hook.command.contains("{{message}}") {
+            ensure_message_quotable(hook.shell)?;
+        }
+
+        for pattern in &config.only_branches {
+            Glob::new(pattern)?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn insert_versions(
         &mut self,
         current_version: Option<&HookVersion>,
         next_version: &HookVersion,
     ) -> Result<()> {
-        let mut parts = parser::parse(&self.0)?;
-        self.0 = parts.replace_versions(next_version, current_version)?;
+        let mut parts = parser::parse(&self.command)?;
+        self.command = parts.replace_versions(next_version, current_version)?;
+
+        if let Some(current_version) = current_version {
+            let version = current_version.to_version()?;
+            self.env.push(("COG_CURRENT_VERSION", version.to_string()));
+        }
+
+        let version = next_version.to_version()?;
+        self.env.push(("COG_NEXT_VERSION", version.to_string()));
+        self.env
+            .push(("COG_TAG", next_version.prefixed_tag.to_string()));
 
         Ok(())
     }
 
-    pub fn run(&self) -> Result<()> {
-        let status = Command::new("sh").arg("-c").arg(&self.0).status()?;
-        ensure!(status.success(), "hook failed with status {}", status);
+    /// Whether the command reads `COG_SNAPSHOT_VERSION`, the snapshot version is only computed then
+    pub(crate) fn uses_snapshot_version(&self) -> bool {
+        self.command.contains("COG_SNAPSHOT_VERSION")
+    }
+
+    /// Export the version built since the latest tag, see `cog get-version --snapshot`
+    pub(crate) fn insert_snapshot_version(&mut self, snapshot: &Version) {
+        self.env
+            .push(("COG_SNAPSHOT_VERSION", snapshot.to_string()));
+    }
+
+    /// Replace `{{message}}` with the shell quoted commit message, also exported as `COG_COMMIT_MESSAGE`
+    pub(crate) fn insert_message(&mut self, message: &str) -> Result<()> {
+        if self.command.contains("{{message}}") {
+            ensure_message_quotable(self.shell)?;
+            self.command = self
+                .command
+                .replace("{{message}}", &shell_words::quote(message));
+        }
+
+        self.env.push(("COG_COMMIT_MESSAGE", message.to_string()));
         Ok(())
     }
+
+    /// Run the hook, retrying it according to its policy
+    pub fn run(&self) -> Result<HookOutput> {
+        let mut attempt = 0;
+        loop {
+            match self.run_once() {
+                Err(err) if attempt < self.policy.retries => {
+                    attempt += 1;
+                    warn!(
+                        "hook `{}` failed: {}, retrying ({}/{})",
+                        self.command, err, attempt, self.policy.retries
+                    );
+                }
+                result => return result,
+            }
+        }
+    }
+
+    pub(crate) fn failure(&self, cause: anyhow::Error) -> CocogittoError {
+        CocogittoError::HookFailed {
+            command: self.command.clone(),
+            policy: self.policy.clone(),
+            cause,
+        }
+    }
+
+    fn run_once(&self) -> Result<HookOutput> {
+        debug!("Running hook `{}`", self.command);
+        let mut command = exec::shell_command(self.shell, &self.command)?;
+        command.envs(self.env.iter().map(|(key, value)| (key, value)));
+
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+
+        let execution = exec::execute(command, self.policy.timeout, self.capture_output)?;
+        let output = HookOutput {
+            command: self.command.clone(),
+            status: execution.status.code(),
+            stdout: execution.stdout,
+            stderr: execution.stderr,
+            failed: false,
+        };
+
+        if self.capture_output {
+            self.echo(&output);
+        }
+
+        if !execution.status.success() {
+            return Err(FailedHook {
+                status: execution.status,
+                output,
+            }
+            .into());
+        }
+
+        Ok(output)
+    }
+
+    // Replay captured output, each line prefixed with the hook name
+    fn echo(&self, output: &HookOutput) {
+        let prefix = self.name.as_deref().unwrap_or_else(|| {
+            self.command
+                .split_whitespace()
+                .next()
+                .unwrap_or(&self.command)
+        });
+
+        // Reported rather than printed, stdout is kept for the command output
+        let stdout = output.stdout.iter().flat_map(|stdout| stdout.lines());
+        let stderr = output.stderr.iter().flat_map(|stderr| stderr.lines());
+        stdout
+            .chain(stderr)
+            .for_each(|line| info!("[{}] {}", prefix, line));
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +433,8 @@ mod test {
     use git2::Repository;
     use std::str::FromStr;
 
+    use crate::hook::HookOutput;
+    use crate::settings::{HookConfig, HookShell};
     use crate::{Hook, HookVersion, Result};
 
     use sealed_test::prelude::*;
@@ -176,7 +449,7 @@ mod test {
     #[test]
     fn parse_valid_string() -> Result<()> {
         let hook = Hook::from_str("cargo bump {{version}}")?;
-        assert_that!(hook.0.as_str()).is_equal_to("cargo bump {{version}}");
+        assert_that!(hook.command.as_str()).is_equal_to("cargo bump {{version}}");
         Ok(())
     }
 
@@ -186,7 +459,7 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("cargo bump 1.0.0");
+        assert_that!(hook.command.as_str()).is_equal_to("cargo bump 1.0.0");
         Ok(())
     }
 
@@ -196,7 +469,7 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.0.0");
+        assert_that!(hook.command.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.0.0");
         Ok(())
     }
 
@@ -206,7 +479,8 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("mvn versions:set -DnewVersion=1.1.0-SNAPSHOT");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("mvn versions:set -DnewVersion=1.1.0-SNAPSHOT");
         Ok(())
     }
 
@@ -216,7 +490,7 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"Hello World\"");
+        assert_that!(hook.command.as_str()).is_equal_to("echo \"Hello World\"");
         Ok(())
     }
 
@@ -226,7 +500,7 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"1.0.0\"");
+        assert_that!(hook.command.as_str()).is_equal_to("echo \"1.0.0\"");
         Ok(())
     }
 
@@ -237,7 +511,8 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("cog commit chore 'bump snapshot to 1.1.0-pre'");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("cog commit chore 'bump snapshot to 1.1.0-pre'");
         Ok(())
     }
 
@@ -248,7 +523,7 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str())
+        assert_that!(hook.command.as_str())
             .is_equal_to("cog commit chore \"bump snapshot to 1.1.0-pre\"");
         Ok(())
     }
@@ -259,7 +534,8 @@ mod test {
         hook.insert_versions(Some(&HookVersion::new("0.5.9")), &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"the latest 0.5.9, the greatest 1.0.0\"");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("echo \"the latest 0.5.9, the greatest 1.0.0\"");
         Ok(())
     }
 
@@ -271,7 +547,8 @@ mod test {
         hook.insert_versions(Some(&HookVersion::new("0.5.9")), &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str()).is_equal_to("echo \"the latest 3.1.0, the greatest 1.0.2\"");
+        assert_that!(hook.command.as_str())
+            .is_equal_to("echo \"the latest 3.1.0, the greatest 1.0.2\"");
         Ok(())
     }
 
@@ -282,7 +559,7 @@ mod test {
         hook.insert_versions(None, &HookVersion::new("1.0.0"))
             .unwrap();
 
-        assert_that!(hook.0.as_str())
+        assert_that!(hook.command.as_str())
             .is_equal_to("echo \"the latest 2.0.0-pre.alpha-bravo+build.42\"");
         Ok(())
     }
@@ -302,4 +579,246 @@ mod test {
 
         Ok(())
     }
+
+    #[sealed_test]
+    fn hook_env_contains_versions() -> Result<()> {
+        let mut hook = Hook::from_str(
+            "echo \"$COG_CURRENT_VERSION $COG_NEXT_VERSION $COG_TAG\" > versions.txt",
+        )?;
+        hook.insert_versions(Some(&HookVersion::new("0.5.9")), &HookVersion::new("1.0.0"))?;
+
+        hook.run()?;
+
+        let versions = std::fs::read_to_string("versions.txt")?;
+        assert_that!(versions.as_str()).is_equal_to("0.5.9 1.0.0 1.0.0\n");
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn hook_runs_in_configured_cwd() -> Result<()> {
+        std::fs::create_dir("scripts")?;
+        let repo_dir = std::env::current_dir()?;
+        let config = HookConfig {
+            cwd: Some("scripts".into()),
+            ..HookConfig::new("touch ran")
+        };
+
+        let hook = Hook::from_config(&config, Some(&repo_dir), HookShell::Sh)?;
+        hook.run()?;
+
+        assert_that!(repo_dir.join("scripts/ran")).exists();
+        Ok(())
+    }
+
+    #[test]
+    fn replace_version_components() -> Result<()> {
+        let mut hook = Hook::from_str(
+            "echo {{version.major}}.{{version.minor}} {{latest+1patch.patch}} {{version.pre}}",
+        )?;
+        hook.insert_versions(
+            Some(&HookVersion::new("0.5.9")),
+            &HookVersion::new("1.2.3-rc.1"),
+        )?;
+
+        assert_that!(hook.command.as_str()).is_equal_to("echo 1.2 10 rc.1");
+        Ok(())
+    }
+
+    #[test]
+    fn replace_version_with_filters() -> Result<()> {
+        let mut hook = Hook::from_str(
+            "docker tag app:{{version|strip_pre}} && echo {{version-rc.1 | upper | quote}}",
+        )?;
+        hook.insert_versions(None, &HookVersion::new("1.2.3-alpha+build.1"))?;
+
+        assert_that!(hook.command.as_str())
+            .is_equal_to("docker tag app:1.2.3+build.1 && echo 1.2.3-RC.1+BUILD.1");
+        Ok(())
+    }
+
+    #[test]
+    fn quote_filter_escapes_version() -> Result<()> {
+        let mut hook =
+            Hook::from_str("echo {{version|strip_build|quote}} {{version.build|quote}}")?;
+        hook.insert_versions(None, &HookVersion::new("1.2.3+build"))?;
+
+        assert_that!(hook.command.as_str()).is_equal_to("echo 1.2.3 build");
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn hook_is_retried() -> Result<()> {
+        let config = HookConfig {
+            retries: 2,
+            ..HookConfig::new("echo attempt >> attempts; test $(wc -l < attempts) -eq 3")
+        };
+
+        let hook = Hook::from_config(&config, None, HookShell::Sh)?;
+        let outcome = hook.run();
+
+        assert_that!(outcome).is_ok();
+        Ok(())
+    }
+
+    #[sealed_test]
+    fn hook_fails_after_retries() -> Result<()> {
+        let config = HookConfig {
+            retries: 1,
+            ..HookConfig::new("echo attempt >> attempts; exit 1")
+        };
+
+        let hook = Hook::from_config(&config, None, HookShell::Sh)?;
+        let outcome = hook.run();
+
+        assert_that!(outcome).is_err();
+        assert_that!(std::fs::read_to_string("attempts")?.lines().count()).is_equal_to(2);
+        Ok(())
+    }
+
+    #[test]
+    fn hook_times_out() -> Result<()> {
+        let config = HookConfig {
+            timeout: Some(1),
+            ..HookConfig::new("sleep 10")
+        };
+
+        let hook = Hook::from_config(&config, None, HookShell::Sh)?;
+        let outcome = hook.run();
+
+        assert_that!(outcome.unwrap_err().to_string())
+            .is_equal_to("hook timed out after 1s".to_string());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[sealed_test]
+    fn hook_timeout_kills_its_subprocesses() -> Result<()> {
+        // Arrange
+        let config = HookConfig {
+            timeout: Some(1),
+            ..HookConfig::new("sleep 30 & echo $! > pid; wait")
+        };
+        let hook = Hook::from_config(&config, None, HookShell::Sh)?;
+
+        // Act
+        let outcome = hook.run();
+
+        // Assert, the orphaned `sleep` is either reaped or left a zombie
+        assert_that!(outcome.unwrap_err().to_string())
+            .is_equal_to("hook timed out after 1s".to_string());
+        let pid = std::fs::read_to_string("pid")?;
+        let ps = std::process::Command::new("ps")
+            .args(["-o", "stat=", "-p", pid.trim()])
+            .output()?;
+        let state = String::from_utf8_lossy(&ps.stdout);
+        assert_that!(state.trim().is_empty() || state.starts_with('Z')).is_true();
+        Ok(())
+    }
+
+    #[test]
+    fn hook_output_is_captured() -> Result<()> {
+        let config = HookConfig {
+            capture_output: true,
+            ..HookConfig::new("echo out; echo err >&2")
+        };
+
+        let hook = Hook::from_config(&config, None, HookShell::Sh)?;
+        let output = hook.run()?;
+
+        assert_that!(output).is_equal_to(HookOutput {
+            command: "echo out; echo err >&2".to_string(),
+            status: Some(0),
+            stdout: Some("out\n".to_string()),
+            stderr: Some("err\n".to_string()),
+            failed: false,
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn hook_output_is_not_captured_by_default() -> Result<()> {
+        let hook = Hook::from_str("true")?;
+        let output = hook.run()?;
+
+        assert_that!(output.stdout).is_none();
+        assert_that!(output.stderr).is_none();
+        Ok(())
+    }
+
+    #[test]
+    fn detect_snapshot_version_use() -> Result<()> {
+        let hook = Hook::from_str("echo $COG_SNAPSHOT_VERSION")?;
+        let other = Hook::from_str("echo {{version}}")?;
+
+        assert_that!(hook.uses_snapshot_version()).is_true();
+        assert_that!(other.uses_snapshot_version()).is_false();
+        Ok(())
+    }
+
+    #[test]
+    fn insert_commit_message() -> Result<()> {
+        let mut hook = Hook::from_str("echo {{message}}")?;
+        hook.insert_message("feat: it's a feature")?;
+
+        assert_that!(hook.command.as_str()).is_equal_to("echo 'feat: it'\\''s a feature'");
+        assert_that!(hook.env).contains(("COG_COMMIT_MESSAGE", "feat: it's a feature".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn refuse_commit_message_placeholder_for_cmd() -> Result<()> {
+        // Arrange
+        let config = HookConfig {
+            command: "echo {{message}}".to_string(),
+            shell: Some(HookShell::Cmd),
+            ..HookConfig::default()
+        };
+        let mut hook = Hook::from_config(&config, None, HookShell::Sh)?;
+
+        // Act
+        let result = hook.insert_message("feat: a feature & echo injected");
+
+        // Assert
+        assert_that!(result).is_err();
+        assert_that!(Hook::validate(&config, HookShell::Sh, false)).is_err();
+        assert_that!(Hook::validate(
+            &HookConfig {
+                command: "echo %COG_COMMIT_MESSAGE%".to_string(),
+                ..config
+            },
+            HookShell::Sh,
+            false
+        ))
+        .is_ok();
+        Ok(())
+    }
+
+    #[test]
+    fn hook_runs_without_shell() -> Result<()> {
+        let config = HookConfig {
+            shell: Some(HookShell::None),
+            capture_output: true,
+            ..HookConfig::new("echo '$HOME' \"a b\"")
+        };
+
+        let hook = Hook::from_config(&config, None, HookShell::Sh)?;
+        let output = hook.run()?;
+
+        assert_that!(output.stdout).is_equal_to(Some("$HOME a b\n".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn hook_uses_default_shell() -> Result<()> {
+        let config = HookConfig {
+            capture_output: true,
+            ..HookConfig::new("[[ -n \"$BASH_VERSION\" ]] && echo bash")
+        };
+
+        let hook = Hook::from_config(&config, None, HookShell::Bash)?;
+        let output = hook.run()?;
+
+        assert_that!(output.stdout).is_equal_to(Some("bash\n".to_string()));
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@ use std::collections::VecDeque;
 use crate::hook::{HookSpan, VersionSpan};
 
 use crate::hook::error::HookParseError;
+use pest::error::ErrorVariant;
 use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use pest_derive::Parser as ParserDerive;
@@ -24,6 +25,28 @@ pub enum Token {
     Patch,
     PreRelease(semver::Prerelease),
     BuildMetadata(semver::BuildMetadata),
+    Component(Component),
+    Filter(Filter),
+}
+
+/// A single part of a version, selected with `{{version.major}}`
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Component {
+    Major,
+    Minor,
+    Patch,
+    PreRelease,
+    BuildMetadata,
+}
+
+/// A filter applied to a version placeholder, e.g. `{{version|strip_pre|quote}}`
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Filter {
+    StripPreRelease,
+    StripBuildMetadata,
+    Upper,
+    Lower,
+    Quote,
 }
 
 pub fn parse(hook: &str) -> Result<HookSpan, HookParseError> {
@@ -67,6 +90,47 @@ fn parse_version(pair: Pair<Rule>) -> Result<VersionSpan, HookParseError> {
                 let semver_build_meta = BuildMetadata::new(identifiers.as_str())?;
                 tokens.push_back(Token::BuildMetadata(semver_build_meta));
             }
+            Rule::component => {
+                let component = match pair.into_inner().next().unwrap().as_rule() {
+                    Rule::component_major => Component::Major,
+                    Rule::component_minor => Component::Minor,
+                    Rule::component_patch => Component::Patch,
+                    Rule::component_pre => Component::PreRelease,
+                    Rule::component_build => Component::BuildMetadata,
+                    _ => unreachable!("Unexpected version component"),
+                };
+                tokens.push_back(Token::Component(component));
+            }
+            Rule::filter => {
+                let filter_span = pair.as_span();
+                let filter = match pair.into_inner().next().unwrap().as_rule() {
+                    Rule::strip_pre => Filter::StripPreRelease,
+                    Rule::strip_build => Filter::StripBuildMetadata,
+                    Rule::upper => Filter::Upper,
+                    Rule::lower => Filter::Lower,
+                    Rule::quote => Filter::Quote,
+                    _ => unreachable!("Unexpected filter"),
+                };
+
+                // String filters render the version, it cannot be changed afterward
+                let after_string_filter = tokens.iter().any(|token| {
+                    matches!(
+                        token,
+                        Token::Filter(Filter::Upper | Filter::Lower | Filter::Quote)
+                    )
+                });
+                if after_string_filter
+                    && matches!(filter, Filter::StripPreRelease | Filter::StripBuildMetadata)
+                {
+                    let variant = ErrorVariant::CustomError {
+                        message: "version filters must come before upper, lower and quote"
+                            .to_string(),
+                    };
+                    return Err(pest::error::Error::new_from_span(variant, filter_span).into());
+                }
+
+                tokens.push_back(Token::Filter(filter));
+            }
             _ => (),
         }
     }
@@ -99,7 +163,7 @@ fn parse_operator(
 mod test {
     use std::collections::VecDeque;
 
-    use crate::hook::parser::Token;
+    use crate::hook::parser::{Component, Filter, Token};
     use crate::hook::{parser, VersionSpan};
 
     use semver::Prerelease;
@@ -145,4 +209,36 @@ mod test {
 
         assert_that!(result).is_err();
     }
+
+    #[test]
+    fn parse_version_with_component_and_filters() {
+        let result = parser::parse("docker tag app:{{version.major | upper|quote}}");
+        assert_that!(result)
+            .is_ok()
+            .map(|span| &span.version_spans)
+            .contains(&VersionSpan {
+                range: 15..46,
+                tokens: VecDeque::from(vec![
+                    Token::Version,
+                    Token::Component(Component::Major),
+                    Token::Filter(Filter::Upper),
+                    Token::Filter(Filter::Quote),
+                ]),
+            });
+    }
+
+    #[test]
+    fn version_filter_after_string_filter_is_err() {
+        let result = parser::parse("echo {{version|upper|strip_pre}}");
+
+        assert_that!(result.unwrap_err().to_string())
+            .contains("version filters must come before upper, lower and quote");
+    }
+
+    #[test]
+    fn unknown_filter_is_err() {
+        let result = parser::parse("{{version|reverse}}");
+
+        assert_that!(result).is_err();
+    }
 }
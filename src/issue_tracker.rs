@@ -0,0 +1,268 @@
+use std::env;
+use std::iter;
+
+use anyhow::{anyhow, Context, Result};
+use conventional_commit_parser::commit::ConventionalCommit;
+use itertools::Itertools;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::conventional::lint::footer_line;
+use crate::curl::CurlRequest;
+use crate::settings::{IssueTracker, IssueTrackerKind};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// A call to the issue tracker API
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct IssueRequest {
+    pub method: &'static str,
+    pub url: String,
+    pub body: Value,
+}
+
+impl IssueTracker {
+    /// The regex matching issue keys, see `integrations.issue_tracker.pattern`
+    pub fn pattern(&self) -> Result<Regex, regex::Error> {
+        let default = match self.kind {
+            IssueTrackerKind::Jira => r"\b[A-Z][A-Z0-9_]+-\d+\b",
+            IssueTrackerKind::Github => r"#(\d+)\b",
+        };
+
+        Regex::new(self.pattern.as_deref().unwrap_or(default))
+    }
+
+    fn token_env(&self) -> &str {
+        match (&self.token_env, self.kind) {
+            (Some(token_env), _) => token_env,
+            (None, IssueTrackerKind::Jira) => "JIRA_TOKEN",
+            (None, IssueTrackerKind::Github) => "GITHUB_TOKEN",
+        }
+    }
+
+    /// The calls commenting on the issue `key` and transitioning it, nothing is
+    /// commented when `comment` is empty
+    pub fn requests(&self, key: &str, comment: &str) -> Result<Vec<IssueRequest>> {
+        let mut requests = vec![];
+        match self.kind {
+            IssueTrackerKind::Jira => {
+                let url = self.url.as_deref().ok_or_else(|| {
+                    anyhow!("integrations.issue_tracker.url is required for Jira")
+                })?;
+                let issue = format!("{}/rest/api/2/issue/{}", url.trim_end_matches('/'), key);
+                if !comment.is_empty() {
+                    requests.push(IssueRequest {
+                        method: "POST",
+                        url: format!("{}/comment", issue),
+                        body: json!({ "body": comment }),
+                    });
+                }
+
+                if let Some(transition) = &self.transition {
+                    requests.push(IssueRequest {
+                        method: "POST",
+                        url: format!("{}/transitions", issue),
+                        body: json!({ "transition": { "id": transition } }),
+                    });
+                }
+            }
+            IssueTrackerKind::Github => {
+                let url = self.url.as_deref().unwrap_or(GITHUB_API_URL);
+                let repository = self.repository.as_deref().ok_or_else(|| {
+                    anyhow!("integrations.issue_tracker.repository is required for GitHub")
+                })?;
+                let issue = format!(
+                    "{}/repos/{}/issues/{}",
+                    url.trim_end_matches('/'),
+                    repository,
+                    key.trim_start_matches('#')
+                );
+                if !comment.is_empty() {
+                    requests.push(IssueRequest {
+                        method: "POST",
+                        url: format!("{}/comments", issue),
+                        body: json!({ "body": comment }),
+                    });
+                }
+
+                if let Some(state) = &self.transition {
+                    requests.push(IssueRequest {
+                        method: "PATCH",
+                        url: issue,
+                        body: json!({ "state": state }),
+                    });
+                }
+            }
+        }
+
+        Ok(requests)
+    }
+
+    /// Send the request using curl, authenticated with the token read from `token_env`
+    pub fn send(&self, request: &IssueRequest) -> Result<()> {
+        let token_env = self.token_env();
+        let token = env::var(token_env).with_context(|| format!("{} is not set", token_env))?;
+        let body = request.body.to_string();
+        let mut curl = CurlRequest::new(request.method, &request.url)
+            .header("Content-Type: application/json")
+            .body(body);
+
+        curl = match (self.kind, &self.user_env) {
+            (IssueTrackerKind::Jira, Some(user_env)) => {
+                let user =
+                    env::var(user_env).with_context(|| format!("{} is not set", user_env))?;
+                curl.user(format!("{}:{}", user, token))
+            }
+            _ => curl.header(format!("Authorization: Bearer {}", token)),
+        };
+
+        if self.kind == IssueTrackerKind::Github {
+            curl = curl.header("Accept: application/vnd.github+json");
+        }
+
+        curl.send()?;
+        Ok(())
+    }
+}
+
+/// The distinct issue keys found in the commit summaries, bodies and footers, in order of
+/// appearance. The key is the first group of `pattern`, or the whole match without groups.
+pub fn issue_keys<'a>(
+    commits: impl IntoIterator<Item = &'a ConventionalCommit>,
+    pattern: &Regex,
+) -> Vec<String> {
+    commits
+        .into_iter()
+        .flat_map(|commit| {
+            let footers = commit.footers.iter().map(footer_line);
+            iter::once(commit.summary.clone())
+                .chain(commit.body.clone())
+                .chain(footers)
+                .collect_vec()
+        })
+        .flat_map(|text| {
+            pattern
+                .captures_iter(&text)
+                .filter_map(|captures| captures.get(1).or_else(|| captures.get(0)))
+                .map(|key| key.as_str().to_string())
+                .collect_vec()
+        })
+        .unique()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::issue_tracker::{issue_keys, IssueRequest};
+    use crate::settings::{IssueTracker, IssueTrackerKind};
+
+    use anyhow::Result;
+    use conventional_commit_parser::parse;
+    use serde_json::json;
+    use speculoos::prelude::*;
+
+    fn tracker(kind: IssueTrackerKind) -> IssueTracker {
+        IssueTracker {
+            kind,
+            url: None,
+            repository: None,
+            pattern: None,
+            token_env: None,
+            user_env: None,
+            comment: "Released in {{ version }}".to_string(),
+            transition: None,
+        }
+    }
+
+    #[test]
+    fn should_find_jira_keys() -> Result<()> {
+        // Arrange
+        let commits = [
+            parse("feat(api): PAY-12 add refunds\n\nAlso covers PAY-13.\n\nRefs: OPS-7, PAY-12")?,
+            parse("fix: crash on empty cart")?,
+        ];
+        let pattern = tracker(IssueTrackerKind::Jira).pattern()?;
+
+        // Act
+        let keys = issue_keys(&commits, &pattern);
+
+        // Assert
+        assert_that!(keys).is_equal_to(vec![
+            "PAY-12".to_string(),
+            "PAY-13".to_string(),
+            "OPS-7".to_string(),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_find_github_issue_numbers() -> Result<()> {
+        // Arrange
+        let commits = [parse("fix: handle #3 properly\n\nCloses #12")?];
+        let pattern = tracker(IssueTrackerKind::Github).pattern()?;
+
+        // Act
+        let keys = issue_keys(&commits, &pattern);
+
+        // Assert
+        assert_that!(keys).is_equal_to(vec!["3".to_string(), "12".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn jira_requests() -> Result<()> {
+        // Arrange
+        let tracker = IssueTracker {
+            url: Some("https://example.atlassian.net/".to_string()),
+            transition: Some("31".to_string()),
+            ..tracker(IssueTrackerKind::Jira)
+        };
+
+        // Act
+        let requests = tracker.requests("PAY-12", "Released in v1.4.0")?;
+
+        // Assert
+        assert_that!(requests).is_equal_to(vec![
+            IssueRequest {
+                method: "POST",
+                url: "https://example.atlassian.net/rest/api/2/issue/PAY-12/comment".to_string(),
+                body: json!({ "body": "Released in v1.4.0" }),
+            },
+            IssueRequest {
+                method: "POST",
+                url: "https://example.atlassian.net/rest/api/2/issue/PAY-12/transitions"
+                    .to_string(),
+                body: json!({ "transition": { "id": "31" } }),
+            },
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn github_requests() -> Result<()> {
+        // Arrange
+        let tracker = IssueTracker {
+            repository: Some("cocogitto/cocogitto".to_string()),
+            transition: Some("closed".to_string()),
+            ..tracker(IssueTrackerKind::Github)
+        };
+
+        // Act
+        let requests = tracker.requests("12", "")?;
+
+        // Assert
+        assert_that!(requests).is_equal_to(vec![IssueRequest {
+            method: "PATCH",
+            url: "https://api.github.com/repos/cocogitto/cocogitto/issues/12".to_string(),
+            body: json!({ "state": "closed" }),
+        }]);
+        Ok(())
+    }
+
+    #[test]
+    fn jira_requires_url() {
+        let requests = tracker(IssueTrackerKind::Jira).requests("PAY-12", "Released");
+
+        assert_that!(requests).is_err();
+    }
+}
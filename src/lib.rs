@@ -1,67 +1,171 @@
-use ::log::{error, info, warn};
+// Crate internals shared with the repository commands are unused without them
+#![cfg_attr(not(feature = "git"), allow(dead_code, unused_imports, unused_macros))]
+
 use std::collections::HashMap;
-use std::fmt::Write as FmtWrite;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
-use std::process::{exit, Command, Stdio};
-
-use anyhow::{anyhow, bail, ensure, Context, Result};
-use colored::*;
-use conventional_commit_parser::commit::{CommitType, ConventionalCommit};
-use conventional_commit_parser::parse_footers;
-use git2::{Oid, RebaseOptions};
-use globset::Glob;
-use itertools::Itertools;
-use lazy_static::lazy_static;
-use semver::{Prerelease, Version};
-use tempfile::TempDir;
-
-use crate::log::filter::CommitFilters;
-use conventional::commit::{verify, Commit, CommitConfig};
-use conventional::version::VersionIncrement;
-use error::{CogCheckReport, PreHookError};
-use git::repository::Repository;
-use hook::Hook;
-use settings::{HookType, Settings};
-
-use crate::conventional::changelog::release::Release;
-use crate::conventional::changelog::template::Template;
-use crate::git::error::{Git2Error, TagError};
-use crate::git::oid::OidOf;
-use crate::git::revspec::RevspecPattern;
-use crate::git::tag::Tag;
-use crate::hook::HookVersion;
+
+use conventional::commit::CommitConfig;
+use conventional_commit_parser::commit::CommitType;
+
+// Everything below works on a repository
+#[cfg(feature = "git")]
+use {
+    crate::conventional::cache::{parse_commit, CommitCache},
+    crate::conventional::changelog,
+    crate::conventional::changelog::error::ChangelogError,
+    crate::conventional::changelog::overrides::{ChangelogOverrides, OVERRIDES_PATH},
+    crate::conventional::changelog::release::{Release, ReleaseBuilder},
+    crate::conventional::changelog::template::{RepositoryContext, Template},
+    crate::git::commit::CommitOptions,
+    crate::git::error::{Git2Error, TagError},
+    crate::git::hook::HookKind,
+    crate::git::identity::Identity,
+    crate::git::oid::OidOf,
+    crate::git::revspec::{CommitRange, RevspecPattern},
+    crate::git::status::{Changes, Status, Statuses},
+    crate::git::tag::Tag,
+    crate::hook::HookVersion,
+    crate::log::filter::CommitFilters,
+    crate::log::format::{LogFormat, LogOptions},
+    crate::manifest::{ManifestRange, ReleaseManifest},
+    crate::milestone::Milestones,
+    crate::reporter::{error, info, warn, Progress, Task},
+    anyhow::{anyhow, Context},
+    chrono::Utc,
+    colored::*,
+    conventional::commit::{format_summary, verify, Commit, CommitIter},
+    conventional::error::{BumpError, ConventionalCommitError},
+    conventional::hint::EditHint,
+    conventional::lint::Linter,
+    conventional::manifest::{parse_manifest_version, read_manifest_version},
+    conventional::rewrite::{Rewriter, RewrittenCommit},
+    conventional::version::{Increment, VersionIncrement},
+    conventional_commit_parser::commit::{ConventionalCommit, Footer, Separator},
+    conventional_commit_parser::parse_footers,
+    error::{CocogittoError, CogCheckReport, PreHookError},
+    git::repository::Repository,
+    git2::{Oid, RebaseOptions},
+    globset::{Glob, GlobSetBuilder},
+    hook::{FailedHook, Hook, HookOutput},
+    itertools::Itertools,
+    notify::ReleaseNotification,
+    report::CheckedCommit,
+    semver::{BuildMetadata, Prerelease, Version},
+    serde::Serialize,
+    settings::import::ImportSource,
+    settings::preset::Preset,
+    settings::{
+        BranchPattern, Dedupe, DirtyPolicy, DuplicateKey, DuplicateMode, HookConfig, HookType,
+        Settings, Severity,
+    },
+    stats::CommitStats,
+    std::collections::HashSet,
+    std::fmt::Write as FmtWrite,
+    std::fs,
+    std::io::Write,
+    std::path::{Path, PathBuf},
+};
 
 pub mod conventional;
+#[cfg(feature = "git")]
+mod curl;
+#[cfg(feature = "git")]
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "git")]
 pub mod git;
+#[cfg(feature = "git")]
 pub mod hook;
+#[cfg(feature = "git")]
+pub mod issue_tracker;
+#[cfg(feature = "git")]
 pub mod log;
+#[cfg(feature = "git")]
+pub mod manifest;
+#[cfg(feature = "git")]
+pub mod milestone;
+#[cfg(feature = "git")]
+pub mod notify;
+#[cfg(feature = "git")]
+pub mod publish;
+#[cfg(feature = "git")]
+pub mod report;
+pub mod reporter;
 pub mod settings;
+#[cfg(feature = "git")]
+pub mod stats;
 
 pub type CommitsMetadata = HashMap<CommitType, CommitConfig>;
 
 pub const CONFIG_PATH: &str = "cog.toml";
 
-lazy_static! {
-    pub static ref SETTINGS: Settings = {
-        if let Ok(repo) = Repository::open(".") {
-            Settings::get(&repo).unwrap_or_default()
-        } else {
-            Settings::default()
+#[cfg(feature = "git")]
+type Result<T, E = CocogittoError> = std::result::Result<T, E>;
+
+// The line git cuts edited commit messages at
+#[cfg(feature = "git")]
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+#[cfg(feature = "git")]
+const SCISSORS_HINT: &str = "# Do not modify or remove the line above.\n\
+                             # Everything below it will be ignored.";
+#[cfg(feature = "git")]
+type ParsedCommit = Result<Commit, Box<ConventionalCommitError>>;
+
+/// What `cog init` writes besides the git repository, the default writes the default settings
+#[cfg(feature = "git")]
+#[derive(Debug, Default)]
+pub struct InitOptions {
+    pub preset: Option<Preset>,
+    /// Tool whose configuration, found in the initialized directory, overrides the tag prefix
+    /// and commit types
+    pub from: Option<ImportSource>,
+    pub tag_prefix: Option<String>,
+    /// Remote, owner and repository of the `remote` changelog template, used when all are set
+    pub remote: Option<String>,
+    pub owner: Option<String>,
+    pub repository: Option<String>,
+    /// Install the cog `commit-msg` and `pre-push` git hooks
+    pub install_hooks: bool,
+    /// Create an empty changelog at the configured path
+    pub changelog: bool,
+}
+
+#[cfg(feature = "git")]
+impl InitOptions {
+    fn settings(&self, path: &Path) -> anyhow::Result<Settings> {
+        let mut settings = self
+            .preset
+            .map(|preset| preset.settings())
+            .unwrap_or_default();
+
+        if let Some(source) = self.from {
+            source.import(path, &mut settings)?;
         }
-    };
 
-    // This cannot be carried by `Cocogitto` struct since we need it to be available in `Changelog`,
-    // `Commit` etc. Be ensure that `CocoGitto::new` is called before using this  in order to bypass
-    // unwrapping in case of error.
-    pub static ref COMMITS_METADATA: CommitsMetadata = {
-        SETTINGS.commit_types()
-    };
+        if let Some(prefix) = &self.tag_prefix {
+            settings.tag_prefix = Some(prefix.clone()).filter(|prefix| !prefix.is_empty());
+        }
+
+        if let (Some(remote), Some(owner), Some(repository)) =
+            (&self.remote, &self.owner, &self.repository)
+        {
+            settings.changelog.template = Some("remote".to_string());
+            settings.changelog.remote = Some(remote.clone());
+            settings.changelog.owner = Some(owner.clone());
+            settings.changelog.repository = Some(repository.clone());
+        }
+
+        Ok(settings)
+    }
 }
 
+#[cfg(feature = "git")]
 pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> Result<()> {
+    init_with_options(path, &InitOptions::default())
+}
+
+#[cfg(feature = "git")]
+pub fn init_with_options<S: AsRef<Path> + ?Sized>(path: &S, options: &InitOptions) -> Result<()> {
     let path = path.as_ref();
 
     if !path.exists() {
@@ -88,11 +192,10 @@ pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> Result<()> {
         },
     };
 
-    let settings = Settings::default();
+    let settings = options.settings(path)?;
     let settings_path = path.join(CONFIG_PATH);
     if settings_path.exists() {
-        eprint!("Found {} in {:?}, Nothing to do", CONFIG_PATH, &path);
-        exit(1);
+        return Err(anyhow!("Found {} in {:?}, nothing to do", CONFIG_PATH, &path).into());
     } else {
         std::fs::write(
             &settings_path,
@@ -108,6 +211,11 @@ pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> Result<()> {
         })?;
     }
 
+    let changelog_path = path.join(&settings.changelog.path);
+    if options.changelog && !changelog_path.exists() {
+        changelog::write_empty_markdown(&changelog_path)?;
+    }
+
     repository.add_all()?;
 
     if is_init_commit {
@@ -115,457 +223,1807 @@ pub fn init<S: AsRef<Path> + ?Sized>(path: &S) -> Result<()> {
         repository.commit("chore: initial commit", sign)?;
     }
 
+    if options.install_hooks {
+        CocoGitto::open(path)?.install_hook(HookKind::All)?;
+    }
+
     Ok(())
 }
 
+/// What a version bump did, as returned by [`CocoGitto::create_version`].
+/// On dry runs nothing is written: there is no changelog path, hook output nor commit.
+#[cfg(feature = "git")]
+#[derive(Debug, Clone, Serialize)]
+pub struct BumpOutcome {
+    pub previous_tag: Option<String>,
+    pub new_tag: String,
+    pub increment: Increment,
+    pub changelog_path: Option<PathBuf>,
+    /// The release notes inserted in the changelog
+    pub changelog: Option<String>,
+    pub pre_bump_hooks: Vec<HookOutput>,
+    pub post_bump_hooks: Vec<HookOutput>,
+    pub commit: Option<String>,
+    /// The release record written when the `bump.manifest` setting is set
+    pub manifest_path: Option<PathBuf>,
+    /// The publication commands run when the `publish.on_bump` setting is set
+    pub published: Vec<HookOutput>,
+}
+
+// The changes found in the working tree before a bump, as allowed by the `bump.dirty` policy
+#[cfg(feature = "git")]
+enum DirtyWorktree {
+    Clean,
+    /// Stashed during the release
+    Stash,
+    /// Untracked paths left out of the release commit
+    Untracked(Vec<String>),
+}
+
+/// A historical version tag, as created by [`CocoGitto::backfill_tags`]
+#[cfg(feature = "git")]
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfilledTag {
+    pub tag: String,
+    /// The commit the version was declared in
+    pub oid: String,
+}
+
+#[cfg(feature = "git")]
 #[derive(Debug)]
 pub struct CocoGitto {
     repository: Repository,
+    settings: Settings,
+    cache: Option<CommitCache>,
+    author: Option<Identity>,
+    sign_off: bool,
+    allow_empty: bool,
+    force_tag: bool,
+    ignore_branch_protection: bool,
+    acknowledge_breaking_changes: bool,
+    overrides: ChangelogOverrides,
 }
 
+#[cfg(feature = "git")]
 impl CocoGitto {
+    /// Open the repository containing the current directory
     pub fn get() -> Result<Self> {
-        let repository = Repository::open(&std::env::current_dir()?)?;
-        let _settings = Settings::get(&repository)?;
-        let _changelog_path = settings::changelog_path();
+        Self::open(&std::env::current_dir()?)
+    }
 
-        Ok(CocoGitto { repository })
+    /// Open the repository at `path` or in one of its parent directories
+    pub fn open<S: AsRef<Path> + ?Sized>(path: &S) -> Result<Self> {
+        let repository = Repository::open(path)?;
+        let settings = Settings::get(&repository)?;
+        Self::with_settings(repository, settings)
     }
 
-    pub fn get_committer(&self) -> Result<String, Git2Error> {
-        self.repository.get_author()
+    /// Replace the settings with their `[profile.<name>]` overlay
+    pub fn with_profile(self, name: &str) -> Result<Self> {
+        let settings = self.settings.with_profile(name)?;
+        let repository = self.repository;
+        Ok(CocoGitto {
+            author: self.author,
+            sign_off: self.sign_off,
+            allow_empty: self.allow_empty,
+            force_tag: self.force_tag,
+            ignore_branch_protection: self.ignore_branch_protection,
+            acknowledge_breaking_changes: self.acknowledge_breaking_changes,
+            ..Self::with_settings(repository, settings)?
+        })
     }
 
-    pub fn get_repo_tag_name(&self) -> Option<String> {
-        let repo_path = self.repository.get_repo_dir()?.iter().last()?;
-        let mut repo_tag_name = repo_path.to_str()?.to_string();
+    fn with_settings(repository: Repository, settings: Settings) -> Result<Self> {
+        let ignored_tags = settings
+            .ignored_tags()
+            .map_err(|err| anyhow!("invalid tag ignore pattern: {}", err))?;
+        let repository = repository.with_tag_rules(
+            settings.tag_prefix.clone(),
+            settings.legacy_tag_prefixes.clone(),
+            ignored_tags,
+        );
+        let ceiling = repository
+            .get_branch_shorthand()
+            .and_then(|branch| settings.maintenance_ceiling(&branch))
+            .transpose()
+            .map_err(|err| anyhow!(err))?;
+        let repository = repository.with_version_ceiling(ceiling);
+        let cache = settings
+            .cache
+            .then(|| CommitCache::load(repository.0.path(), &settings.commit_types()));
+        let overrides = Self::load_overrides(&repository)?;
+
+        Ok(CocoGitto {
+            repository,
+            settings,
+            cache,
+            author: None,
+            sign_off: false,
+            allow_empty: false,
+            force_tag: false,
+            ignore_branch_protection: false,
+            acknowledge_breaking_changes: false,
+            overrides,
+        })
+    }
 
-        if let Some(branch_shorthand) = self.repository.get_branch_shorthand() {
-            write!(&mut repo_tag_name, " on {}", branch_shorthand).unwrap();
+    // Bare repositories have no working tree, the committed overrides are read instead
+    fn load_overrides(repository: &Repository) -> Result<ChangelogOverrides> {
+        let content = match repository.get_repo_dir() {
+            Some(repo_dir) => fs::read_to_string(repo_dir.join(OVERRIDES_PATH)).ok(),
+            None => repository.get_head_file_content(OVERRIDES_PATH),
+        };
+
+        match content {
+            Some(content) => ChangelogOverrides::parse(&content)
+                .map_err(|err| ChangelogError::InvalidOverrides(err).into()),
+            None => Ok(ChangelogOverrides::default()),
         }
+    }
 
-        if let Ok(latest_tag) = self.repository.get_latest_tag() {
-            write!(&mut repo_tag_name, " {}", latest_tag).unwrap();
-        };
+    /// Author the commits created by cog as `author` rather than the git config identity.
+    /// Release commits are also committed by `author`, instead of the `release_author` setting.
+    pub fn with_author(self, author: Identity) -> Self {
+        CocoGitto {
+            author: Some(author),
+            ..self
+        }
+    }
 
-        Some(repo_tag_name)
+    /// Append a `Signed-off-by` trailer to the commits created by cog, even when the
+    /// `commit.sign_off` setting is disabled
+    pub fn with_sign_off(self) -> Self {
+        CocoGitto {
+            sign_off: true,
+            ..self
+        }
     }
 
-    pub fn check_and_edit(&self, from_latest_tag: bool) -> Result<()> {
-        let commits = if from_latest_tag {
-            self.repository
-                .get_commit_range(&RevspecPattern::default())?
-        } else {
-            self.repository.all_commits()?
-        };
+    /// Write the bump changelog to `path`, relative to the working tree root, instead of
+    /// the `changelog.path` setting
+    pub fn with_changelog_path(mut self, path: PathBuf) -> Self {
+        self.settings.changelog.path = path;
+        self
+    }
 
-        let editor = std::env::var("EDITOR")
-            .map_err(|_err| anyhow!("the 'EDITOR' environment variable was not found"))?;
+    /// Create the commits even when there are no changes to commit, like `git commit --allow-empty`.
+    /// Releases bumping no version file can then be committed on a clean tree.
+    pub fn with_allow_empty(self) -> Self {
+        CocoGitto {
+            allow_empty: true,
+            ..self
+        }
+    }
 
-        let dir = TempDir::new()?;
+    /// Move the release tag when it already exists instead of refusing to bump
+    pub fn with_force_tag(self) -> Self {
+        CocoGitto {
+            force_tag: true,
+            ..self
+        }
+    }
 
-        let errored_commits: Vec<Oid> = commits
-            .commits
-            .iter()
-            .map(|commit| {
-                let conv_commit = Commit::from_git_commit(commit);
-                (commit.id(), conv_commit)
-            })
-            .filter(|commit| commit.1.is_err())
-            .map(|commit| commit.0)
-            .collect();
+    /// Bump, or commit with the `commit.check_branch` setting, from branches the
+    /// `branch_whitelist` and `branch_denylist` settings forbid
+    pub fn with_ignore_branch_protection(self) -> Self {
+        CocoGitto {
+            ignore_branch_protection: true,
+            ..self
+        }
+    }
 
-        // Get the last commit oid on the list as a starting point for our rebase
-        let last_errored_commit = errored_commits.last();
-        if let Some(last_errored_commit) = last_errored_commit {
-            let commit = self
-                .repository
-                .0
-                .find_commit(last_errored_commit.to_owned())?;
+    /// Stabilize to 1.0.0 even though breaking changes were made since the latest version
+    pub fn with_acknowledged_breaking_changes(self) -> Self {
+        CocoGitto {
+            acknowledge_breaking_changes: true,
+            ..self
+        }
+    }
 
-            let rebase_start = if commit.parent_count() == 0 {
-                commit.id()
-            } else {
-                commit.parent_id(0)?
-            };
+    // Refuse to `action` from a branch out of the whitelist or matching the denylist
+    fn ensure_branch_allowed(&self, action: &'static str) -> Result<()> {
+        if self.ignore_branch_protection {
+            return Ok(());
+        }
 
-            let commit = self.repository.0.find_annotated_commit(rebase_start)?;
-            let mut options = RebaseOptions::new();
+        let Some(branch) = self.repository.get_branch_shorthand() else {
+            return Ok(());
+        };
 
-            let mut rebase =
-                self.repository
-                    .0
-                    .rebase(None, Some(&commit), None, Some(&mut options))?;
-
-            while let Some(op) = rebase.next() {
-                if let Ok(rebase_operation) = op {
-                    let oid = rebase_operation.id();
-                    let original_commit = self.repository.0.find_commit(oid)?;
-                    if errored_commits.contains(&oid) {
-                        warn!("Found errored commits:{}", &oid.to_string()[0..7]);
-                        let file_path = dir.path().join(&commit.id().to_string());
-                        let mut file = File::create(&file_path)?;
-
-                        let hint = format!(
-                            "# Editing commit {}\
-                        \n# Replace this message with a conventional commit compliant one\
-                        \n# Save and exit to edit the next errored commit\n",
-                            original_commit.id()
-                        );
-
-                        let mut message_bytes: Vec<u8> = hint.clone().into();
-                        message_bytes.extend_from_slice(original_commit.message_bytes());
-                        file.write_all(&message_bytes)?;
-
-                        Command::new(&editor)
-                            .arg(&file_path)
-                            .stdout(Stdio::inherit())
-                            .stdin(Stdio::inherit())
-                            .stderr(Stdio::inherit())
-                            .output()?;
-
-                        let new_message: String = std::fs::read_to_string(&file_path)?
-                            .lines()
-                            .filter(|line| !line.starts_with('#'))
-                            .filter(|line| !line.trim().is_empty())
-                            .collect();
-
-                        rebase.commit(None, &original_commit.committer(), Some(&new_message))?;
-                        let ignore_merge_commit = SETTINGS.ignore_merge_commits;
-                        match verify(
-                            self.repository.get_author().ok(),
-                            &new_message,
-                            ignore_merge_commit,
-                        ) {
-                            Ok(_) => {
-                                info!("Changed commit message to:\"{}\"", &new_message.trim_end())
-                            }
-                            Err(err) => error!(
-                                "Error: {}\n\t{}",
-                                "Edited message is still not compliant".red(),
-                                err
-                            ),
-                        }
-                    } else {
-                        rebase.commit(None, &original_commit.committer(), None)?;
-                    }
-                } else {
-                    error!("{:?}", op);
-                }
-            }
+        let matches = |pattern: &String| -> Result<bool> {
+            let pattern = BranchPattern::new(pattern)
+                .map_err(|err| anyhow!("invalid branch pattern '{}': {}", pattern, err))?;
+            Ok(pattern.is_match(&branch))
+        };
 
-            rebase.finish(None)?;
-        } else {
-            info!("{}", "No errored commit, skipping rebase".green());
+        let whitelist = &self.settings.branch_whitelist;
+        let mut allowed = whitelist.is_empty();
+        for pattern in whitelist {
+            allowed |= matches(pattern)?;
+        }
+
+        if !allowed {
+            return Err(CocogittoError::BranchNotAllowed {
+                branch,
+                patterns: whitelist.clone(),
+                action,
+            });
+        }
+
+        for pattern in &self.settings.branch_denylist {
+            if matches(pattern)? {
+                return Err(CocogittoError::BranchDenied {
+                    branch,
+                    pattern: pattern.clone(),
+                    action,
+                });
+            }
         }
 
         Ok(())
     }
 
-    pub fn check(&self, check_from_latest_tag: bool, ignore_merge_commits: bool) -> Result<()> {
-        let commit_range = if check_from_latest_tag {
-            self.repository
-                .get_commit_range(&RevspecPattern::default())?
-        } else {
-            self.repository.all_commits()?
-        };
+    // The `Signed-off-by` trailer of the commits authored by `author`, `None` when disabled
+    fn sign_off_trailer(&self, author: Option<&Identity>) -> Result<Option<Footer>> {
+        if !(self.sign_off || self.settings.commit.sign_off) {
+            return Ok(None);
+        }
 
-        let errors: Vec<_> = if ignore_merge_commits {
-            commit_range
-                .commits
-                .iter()
-                .filter(|commit| !commit.message().unwrap_or("").starts_with("Merge "))
-                .map(Commit::from_git_commit)
-                .filter_map(Result::err)
-                .collect()
-        } else {
-            commit_range
-                .commits
-                .iter()
-                .map(Commit::from_git_commit)
-                .filter_map(Result::err)
-                .collect()
+        let identity = match author {
+            Some(author) => author.clone(),
+            None => self.repository.get_identity()?,
         };
 
-        if errors.is_empty() {
-            let msg = "No errored commits".green();
-            info!("{}", msg);
-            Ok(())
-        } else {
-            let report = CogCheckReport {
-                from: commit_range.from,
-                errors: errors.into_iter().map(|err| *err).collect(),
-            };
-            Err(anyhow!("{}", report))
-        }
+        Ok(Some(Footer {
+            token: "Signed-off-by".to_string(),
+            content: identity.to_string(),
+            token_separator: Separator::Colon,
+        }))
     }
 
-    pub fn get_log(&self, filters: CommitFilters) -> Result<String> {
-        let commits = self.repository.all_commits()?;
-        let logs = commits
-            .commits
-            .iter()
-            // Remove merge commits
-            .filter(|commit| !commit.message().unwrap_or("").starts_with("Merge"))
-            .filter(|commit| filters.filter_git2_commit(commit))
-            .map(Commit::from_git_commit)
-            // Apply filters
-            .filter(|commit| match commit {
-                Ok(commit) => filters.filters(commit),
-                Err(_) => filters.no_error(),
-            })
-            // Format
-            .map(|commit| match commit {
-                Ok(commit) => commit.get_log(),
-                Err(err) => err.to_string(),
-            })
-            .collect::<Vec<String>>()
-            .join("\n");
+    // The identity of the release commits and of the commits rewritten by `cog edit`
+    fn release_author(&self) -> Result<Option<Identity>> {
+        match &self.author {
+            Some(author) => Ok(Some(author.clone())),
+            None => self
+                .settings
+                .release_author()
+                .transpose()
+                .map_err(|err| anyhow!(err).into()),
+        }
+    }
 
-        Ok(logs)
+    /// The settings loaded from the repository `cog.toml`
+    pub fn settings(&self) -> &Settings {
+        &self.settings
     }
 
-    /// Tries to get a commit message conforming to the Conventional Commit spec.
-    /// If the commit message does _not_ conform, `None` is returned instead.
-    pub fn get_conventional_message(
-        commit_type: &str,
-        scope: Option<String>,
-        summary: String,
-        body: Option<String>,
-        footer: Option<String>,
-        is_breaking_change: bool,
-    ) -> Result<String> {
-        // Ensure commit type is known
-        let commit_type = CommitType::from(commit_type);
+    pub fn get_committer(&self) -> Result<String, Git2Error> {
+        self.repository.get_author()
+    }
 
-        // Ensure footers are correctly formatted
-        let footers = match footer {
-            Some(footers) => parse_footers(&footers)?,
-            None => Vec::with_capacity(0),
-        };
+    pub fn get_repo_tag_name(&self) -> Option<String> {
+        let repo_path = self.repository.get_repo_dir()?.iter().next_back()?;
+        let mut repo_tag_name = repo_path.to_str()?.to_string();
 
-        let conventional_message = ConventionalCommit {
-            commit_type,
-            scope,
-            body,
-            footers,
-            summary,
-            is_breaking_change,
+        if let Some(branch_shorthand) = self.repository.get_branch_shorthand() {
+            write!(&mut repo_tag_name, " on {}", branch_shorthand).unwrap();
         }
-        .to_string();
 
-        // Validate the message
-        conventional_commit_parser::parse(&conventional_message)?;
+        if let Ok(latest_tag) = self.repository.get_latest_tag() {
+            write!(&mut repo_tag_name, " {}", latest_tag).unwrap();
+        };
 
-        Ok(conventional_message)
+        Some(repo_tag_name)
     }
 
-    #[allow(clippy::too_many_arguments)] // FIXME
-    pub fn conventional_commit(
-        &self,
-        commit_type: &str,
-        scope: Option<String>,
-        summary: String,
-        body: Option<String>,
-        footer: Option<String>,
-        is_breaking_change: bool,
-        sign: bool,
-    ) -> Result<()> {
-        // Ensure commit type is known
-        let commit_type = CommitType::from(commit_type);
+    /// Interactively reword the non compliant commits of `range`, the whole history when `None`.
+    /// Commits are rebased from the oldest reworded one, the range must end in HEAD's history.
+    /// Rebased commits are signed again when `sign` is set, it defaults to `commit.gpgSign`.
+    pub fn check_and_edit(&self, range: Option<&RevspecPattern>, sign: Option<bool>) -> Result<()> {
+        let commits = self.rewritable_commits(range)?;
+        let editor = self.repository.editor();
+        let commit_types = self.settings.commit_types();
+        let linter = self.linter()?;
+
+        let mut progress = Progress::start(Task::CheckCommits, Some(commits.commits.len()));
+        // The errored commits along with why they were rejected
+        let errored_commits: Vec<(Oid, String)> = commits
+            .commits
+            .iter()
+            .inspect(|_| progress.tick())
+            .filter_map(
+                |commit| match self.check_commit(commit, &commit_types, &linter) {
+                    Err(err) if err.severity() == Severity::Error => {
+                        Some((commit.id(), err.cause()))
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+        drop(progress);
 
-        // Ensure footers are correctly formatted
-        let footers = match footer {
-            Some(footers) => parse_footers(&footers)?,
-            None => Vec::with_capacity(0),
+        let errored_commits = self.skip_merged_commits(errored_commits)?;
+        let Some((oldest, _)) = errored_commits.last() else {
+            info!("{}", "No errored commit, skipping rebase".green());
+            return Ok(());
         };
 
-        let conventional_message = ConventionalCommit {
-            commit_type,
-            scope,
-            body,
-            footers,
-            summary,
-            is_breaking_change,
+        self.reword_commits(*oldest, sign, |commit| {
+            let Some((_, cause)) = errored_commits.iter().find(|(oid, _)| *oid == commit.id())
+            else {
+                return Ok(None);
+            };
+
+            warn!("Found errored commits:{}", &commit.id().to_string()[0..7]);
+            let message = String::from_utf8_lossy(commit.message_bytes());
+            let oid = commit.id().to_string();
+            let hint = EditHint::new(&oid, &message, cause, &commit_types).render()?;
+            let content = format!("{}{}", hint, message);
+            let new_message: String = editor
+                .edit(&content, "COMMIT_EDITMSG")?
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter(|line| !line.trim().is_empty())
+                .collect();
+
+            let ignore_merge_commit = self.settings.ignore_merge_commits;
+            match verify(
+                self.repository.get_author().ok(),
+                &new_message,
+                ignore_merge_commit,
+                &commit_types,
+                &linter,
+            ) {
+                Ok(_) => info!("Changed commit message to:\"{}\"", &new_message.trim_end()),
+                Err(err) => error!(
+                    "Error: {}\n\t{}",
+                    "Edited message is still not compliant".red(),
+                    err
+                ),
+            }
+
+            Ok(Some(new_message))
+        })
+    }
+
+    /// Rewrite the non compliant commits of `range`, the whole history when `None`, with the
+    /// first matching `import_history` rule. Nothing is rewritten when a rewritten message is
+    /// still not compliant, nor when `dry_run` is set. Commits are rebased like
+    /// [`CocoGitto::check_and_edit`] does.
+    pub fn import_history(
+        &self,
+        range: Option<&RevspecPattern>,
+        dry_run: bool,
+        sign: Option<bool>,
+    ) -> Result<Vec<RewrittenCommit>> {
+        let rewriter = Rewriter::new(&self.settings.import_history.rules)?;
+        if rewriter.is_empty() {
+            return Err(anyhow!("no rewrite rule, add some to `import_history.rules`").into());
         }
-        .to_string();
 
-        // Validate the message
-        conventional_commit_parser::parse(&conventional_message)?;
+        let commits = self.rewritable_commits(range)?;
+        let commit_types = self.settings.commit_types();
+        let linter = self.linter()?;
+
+        let mut rewritten = vec![];
+        let mut failures = String::new();
+        let mut unmatched = String::new();
+        let mut progress = Progress::start(Task::CheckCommits, Some(commits.commits.len()));
+        for commit in &commits.commits {
+            progress.tick();
+            if !matches!(
+                self.check_commit(commit, &commit_types, &linter),
+                Err(err) if err.severity() == Severity::Error
+            ) {
+                continue;
+            }
 
-        // Git commit
-        let sign = sign || self.repository.gpg_sign();
-        let oid = self.repository.commit(&conventional_message, sign)?;
+            let oid = commit.id();
+            let message = commit.message().unwrap_or_default();
+            let Some(new_message) = rewriter.rewrite(message) else {
+                let summary = commit.summary().unwrap_or_default();
+                write!(unmatched, "\n\t{} {}", &oid.to_string()[0..7], summary).unwrap();
+                continue;
+            };
 
-        // Pretty print a conventional commit summary
-        let commit = self.repository.0.find_commit(oid)?;
-        let commit = Commit::from_git_commit(&commit)?;
-        info!("{}", commit);
+            let ignore_merge_commits = self.settings.ignore_merge_commits;
+            let author = commit.author().name().map(str::to_string);
+            if let Err(err) = verify(
+                author,
+                &new_message,
+                ignore_merge_commits,
+                &commit_types,
+                &linter,
+            ) {
+                write!(
+                    failures,
+                    "\n\t{} {}\n\t  {}",
+                    &oid.to_string()[0..7],
+                    new_message.lines().next().unwrap_or_default(),
+                    err.cause()
+                )
+                .unwrap();
+                continue;
+            }
 
-        Ok(())
-    }
+            let commit = RewrittenCommit {
+                oid: oid.to_string(),
+                original: message.to_string(),
+                message: new_message,
+            };
+            rewritten.push((oid, commit));
+        }
+        drop(progress);
 
-    pub fn create_version(
-        &mut self,
-        increment: VersionIncrement,
-        pre_release: Option<&str>,
-        hooks_config: Option<&str>,
-        dry_run: bool,
-    ) -> Result<()> {
-        if *SETTINGS == Settings::default() {
-            let part1 = "Warning: using".yellow();
-            let part2 = "with the default configuration. \n".yellow();
-            let part3 = "You may want to create a".yellow();
-            let part4 = "file in your project root to configure bumps.\n".yellow();
+        if !unmatched.is_empty() {
             warn!(
-                "{} 'cog bump' {}{} 'cog.toml' {}",
-                part1, part2, part3, part4
+                "No rewrite rule matches these commits, they are kept as is:{}",
+                unmatched
             );
         }
-        let statuses = self.repository.get_statuses()?;
 
-        // Fail if repo contains un-staged or un-committed changes
-        ensure!(statuses.0.is_empty(), "{}", self.repository.get_statuses()?);
-
-        if !SETTINGS.branch_whitelist.is_empty() {
-            if let Some(branch) = self.repository.get_branch_shorthand() {
-                let whitelist = &SETTINGS.branch_whitelist;
-                let is_match = whitelist.iter().any(|pattern| {
-                    let glob = Glob::new(pattern)
-                        .expect("invalid glob pattern")
-                        .compile_matcher();
-                    glob.is_match(&branch)
-                });
+        if !failures.is_empty() {
+            return Err(anyhow!(
+                "these rewritten messages are still not compliant, nothing was rewritten:{}",
+                failures
+            )
+            .into());
+        }
 
-                ensure!(
-                    is_match,
-                    "No patterns matched in {:?} for branch '{}', bump is not allowed",
-                    whitelist,
-                    branch
-                )
-            }
-        };
+        let rewritten = self.skip_merged_commits(rewritten)?;
+        if let (false, Some((oldest, _))) = (dry_run, rewritten.last()) {
+            self.reword_commits(*oldest, sign, |commit| {
+                Ok(rewritten
+                    .iter()
+                    .find(|(oid, _)| *oid == commit.id())
+                    .map(|(_, rewritten)| rewritten.message.clone()))
+            })?;
 
-        let current_tag = self.repository.get_latest_tag();
-        let current_version = match current_tag {
-            Ok(ref tag) => tag.to_version()?,
-            Err(ref err) if err == &TagError::NoTag => {
-                warn!("Failed to get current version, falling back to 0.0.0");
-                Version::new(0, 0, 0)
-            }
-            Err(ref err) => bail!("{}", err),
+            info!("Rewrote {} commit messages", rewritten.len());
+        }
+
+        Ok(rewritten.into_iter().map(|(_, commit)| commit).collect())
+    }
+
+    // The commits of `range`, or the whole history, which must end in HEAD's history to be rebased
+    fn rewritable_commits(&self, range: Option<&RevspecPattern>) -> Result<CommitRange<'_>> {
+        let commits = match range {
+            Some(range) => self.repository.get_commit_range(range)?,
+            None => self.repository.all_commits()?,
         };
 
-        let mut next_version = increment.bump(&current_version, &self.repository)?;
+        if !self.repository.is_reachable_from_head(*commits.to.oid()) {
+            return Err(anyhow!(
+                "cannot edit commits of {}..{}, {} is not in the history of HEAD",
+                commits.from,
+                commits.to,
+                commits.to
+            )
+            .into());
+        }
 
-        if next_version.le(&current_version) || next_version.eq(&current_version) {
-            let comparison = format!("{} <= {}", current_version, next_version).red();
-            let cause_key = "cause:".red();
-            let cause = format!(
-                "{} version MUST be greater than current one: {}",
-                cause_key, comparison
-            );
+        Ok(commits)
+    }
 
-            bail!("{}:\n\t{}\n", "SemVer Error".red().to_string(), cause);
-        };
+    // Rebasing drops merge commits, only the commits more recent than any merge are kept.
+    // `commits` are ordered newest first.
+    fn skip_merged_commits<T>(&self, commits: Vec<(Oid, T)>) -> Result<Vec<(Oid, T)>> {
+        let mut merge = None;
+        let mut editable = vec![];
+        let mut skipped = vec![];
+        for (oid, value) in commits {
+            if merge.is_none() {
+                let commit = self.repository.0.find_commit(oid)?;
+                let parent = commit.parent_id(0).ok();
+                merge = self.repository.newest_merge_since(parent)?;
+            }
 
-        if let Some(pre_release) = pre_release {
-            next_version.pre = Prerelease::new(pre_release)?;
+            match merge {
+                None => editable.push((oid, value)),
+                Some(_) => skipped.push(oid),
+            }
         }
 
-        let version_str = match &SETTINGS.tag_prefix {
-            None => next_version.to_string(),
-            Some(prefix) => format!("{}{}", prefix, next_version),
-        };
+        if let Some(merge) = merge {
+            let mut commits = String::new();
+            for oid in &skipped {
+                let commit = self.repository.0.find_commit(*oid)?;
+                let summary = commit.summary().unwrap_or_default();
+                write!(commits, "\n\t{} {}", &oid.to_string()[0..7], summary).unwrap();
+            }
 
-        if dry_run {
-            print!("{}", version_str);
-            return Ok(());
+            warn!(
+                "Skipping {} errored commits older than merge commit {}, rebasing them would flatten the merged history:{}",
+                skipped.len(),
+                &merge.to_string()[0..7],
+                commits
+            );
         }
 
-        let origin = if current_version == Version::new(0, 0, 0) {
-            self.repository.get_first_commit()?.to_string()
+        Ok(editable)
+    }
+
+    // Rebase HEAD from the parent of `oldest`, rewording the commits `reword` returns a new
+    // message for. Rewritten commits keep their committer unless a release author is configured.
+    fn reword_commits<F>(&self, oldest: Oid, sign: Option<bool>, mut reword: F) -> Result<()>
+    where
+        F: FnMut(&git2::Commit) -> Result<Option<String>>,
+    {
+        let release_committer = self
+            .release_author()?
+            .map(|author| author.signature())
+            .transpose()?;
+
+        let commit = self.repository.0.find_commit(oldest)?;
+        let rebase_start = if commit.parent_count() == 0 {
+            commit.id()
         } else {
-            current_tag?.oid_unchecked().to_string()
+            commit.parent_id(0)?
         };
 
-        let target = self.repository.get_head_commit_oid()?.to_string();
-        let pattern = (origin.as_str(), target.as_str());
-
-        let pattern = RevspecPattern::from(pattern);
-        let changelog = self.get_changelog_with_target_version(pattern, &version_str)?;
-
-        let path = settings::changelog_path();
-        let template = SETTINGS.get_changelog_template()?;
-        changelog.write_to_file(path, template)?;
+        let commit = self.repository.0.find_annotated_commit(rebase_start)?;
+        let mut options = RebaseOptions::new();
 
-        let current = self
+        let mut rebase = self
             .repository
-            .get_latest_tag()
-            .map(|tag| HookVersion::new(&tag.to_string_with_prefix()))
-            .ok();
+            .0
+            .rebase(None, Some(&commit), None, Some(&mut options))?;
+
+        // Rebased commits lose their signature unless they are signed again
+        let sign = sign.unwrap_or_else(|| self.repository.gpg_sign());
+        if !sign {
+            let rebased: Vec<Oid> = (0..rebase.len())
+                .filter_map(|idx| rebase.nth(idx).map(|op| op.id()))
+                .collect();
+            self.warn_invalidated_signatures(&rebased)?;
+        }
 
-        let next_version = HookVersion::new(&Self::prefix_version(next_version.to_string()));
+        let mut progress = Progress::start(Task::Rebase, Some(rebase.len()));
+        while let Some(op) = rebase.next() {
+            progress.tick();
+            let Ok(rebase_operation) = op else {
+                error!("{:?}", op);
+                continue;
+            };
 
-        let hook_result = self.run_hooks(
+            let original_commit = self.repository.0.find_commit(rebase_operation.id())?;
+            let committer = match &release_committer {
+                Some(committer) => committer.clone(),
+                None => original_commit.committer().to_owned(),
+            };
+
+            let message = reword(&original_commit)?;
+            rebase.commit(None, &committer, message.as_deref())?;
+            if sign {
+                self.repository.sign_head_commit()?;
+            }
+        }
+
+        drop(progress);
+        rebase.finish(None)?;
+        Ok(())
+    }
+
+    /// The content of the git `commit.template` file, used to seed commit message bodies
+    pub fn commit_template(&self) -> Option<String> {
+        self.repository.commit_template()
+    }
+
+    /// Let the user edit `template` in the editor git would use, everything below
+    /// the scissors line is dropped. With `commit.verbose`, the staged changes are
+    /// shown below that line.
+    pub fn edit_message(&self, template: &str) -> Result<String> {
+        let mut content = template.to_string();
+        if self.repository.commit_verbose() {
+            if let Some(patch) = self.repository.get_staged_patch() {
+                write!(content, "{}\n{}\n{}", SCISSORS, SCISSORS_HINT, patch).unwrap();
+            }
+        }
+
+        let edited = self.repository.editor().edit(&content, "COMMIT_EDITMSG")?;
+
+        Ok(edited
+            .lines()
+            .take_while(|line| *line != SCISSORS)
+            .join("\n"))
+    }
+
+    /// Check the commits of the range, failing when some of them are not compliant. The report
+    /// of a successful check lists the commits with warnings.
+    pub fn check(
+        &self,
+        check_from_latest_tag: bool,
+        ignore_merge_commits: bool,
+    ) -> Result<CogCheckReport> {
+        let (_, report) = self.check_commits(check_from_latest_tag, ignore_merge_commits)?;
+        Self::check_report(report)
+    }
+
+    /// The result of the check of every commit in the range checked by [`CocoGitto::check`],
+    /// compliant commits included, along with the report of the non compliant ones. The commits
+    /// are walked once, failing the check is left to [`CocoGitto::check_report`].
+    pub fn check_commits(
+        &self,
+        check_from_latest_tag: bool,
+        ignore_merge_commits: bool,
+    ) -> Result<(Vec<CheckedCommit>, CogCheckReport)> {
+        let (from, commits) = self.check_range(check_from_latest_tag, ignore_merge_commits)?;
+        let checked = commits
+            .iter()
+            .map(|(summary, result)| match result {
+                Ok(commit) => CheckedCommit {
+                    oid: commit.oid.clone(),
+                    summary: summary.clone(),
+                    error: None,
+                    severity: None,
+                },
+                Err(err) => CheckedCommit {
+                    oid: err
+                        .commit()
+                        .map(|(oid, _)| oid.to_string())
+                        .unwrap_or_default(),
+                    summary: summary.clone(),
+                    error: Some(err.cause()),
+                    severity: Some(err.severity()),
+                },
+            })
+            .collect();
+
+        let (warnings, errors): (Vec<_>, Vec<_>) = commits
+            .into_iter()
+            .filter_map(|(_, result)| result.err())
+            .map(|err| *err)
+            .partition(|err| err.severity() == Severity::Warning);
+
+        let report = CogCheckReport {
+            from,
+            errors,
+            warnings,
+        };
+
+        Ok((checked, report))
+    }
+
+    /// Fail with the report when it has errors, warn about its warnings otherwise
+    pub fn check_report(report: CogCheckReport) -> Result<CogCheckReport> {
+        if !report.errors.is_empty() {
+            return Err(CocogittoError::NotConventional(Box::new(report)));
+        }
+
+        if !report.warnings.is_empty() {
+            warn!("{}", report);
+        }
+
+        let msg = "No errored commits".green();
+        info!("{}", msg);
+        Ok(report)
+    }
+
+    // List the signed commits among the rebased ones
+    fn warn_invalidated_signatures(&self, rebased: &[Oid]) -> Result<()> {
+        let mut signed = String::new();
+        for oid in rebased {
+            if self.repository.0.extract_signature(oid, None).is_ok() {
+                let commit = self.repository.0.find_commit(*oid)?;
+                let summary = commit.summary().unwrap_or_default();
+                write!(signed, "\n\t{} {}", &oid.to_string()[0..7], summary).unwrap();
+            }
+        }
+
+        if !signed.is_empty() {
+            warn!(
+                "The signature of these rebased commits will be dropped, use `--sign` to sign them again:{}",
+                signed
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Check the message of the commit being authored like `cog verify` does, along with the
+    /// rules depending on the staged files. The message is read from `.git/COMMIT_EDITMSG`
+    /// unless `message_file` is set, e.g. to the argument of a `commit-msg` hook.
+    pub fn check_staged(
+        &self,
+        message_file: Option<&Path>,
+        ignore_merge_commits: bool,
+    ) -> Result<()> {
+        let message_file = match message_file {
+            Some(path) => path.to_path_buf(),
+            None => self.repository.0.path().join("COMMIT_EDITMSG"),
+        };
+        let message = fs::read_to_string(&message_file)
+            .with_context(|| format!("failed to read commit message from {:?}", message_file))?;
+
+        let linter = self
+            .linter()?
+            .with_staged_files(self.repository.get_staged_files());
+        verify(
+            self.repository.get_author().ok(),
+            &message,
+            ignore_merge_commits,
+            &self.settings.commit_types(),
+            &linter,
+        )?;
+
+        Ok(())
+    }
+
+    // The summary and parse result of each checked commit
+    fn check_range(
+        &self,
+        check_from_latest_tag: bool,
+        ignore_merge_commits: bool,
+    ) -> Result<(OidOf, Vec<(String, ParsedCommit)>)> {
+        self.ensure_history(check_from_latest_tag)?;
+
+        let commit_range = if check_from_latest_tag {
+            self.repository
+                .get_commit_range(&RevspecPattern::default())?
+        } else {
+            self.repository.all_commits()?
+        };
+
+        let commit_types = self.settings.commit_types();
+        let linter = self.linter()?;
+        let mut progress = Progress::start(Task::CheckCommits, Some(commit_range.commits.len()));
+        let commits = commit_range
+            .commits
+            .iter()
+            .inspect(|_| progress.tick())
+            .filter(|commit| {
+                !(ignore_merge_commits && commit.message().unwrap_or("").starts_with("Merge "))
+            })
+            .map(|commit| {
+                let summary = commit.summary().unwrap_or_default().to_string();
+                (summary, self.check_commit(commit, &commit_types, &linter))
+            })
+            .collect();
+
+        Ok((commit_range.from, commits))
+    }
+
+    /// The `lint` rules checked on top of the conventional commit format
+    pub fn linter(&self) -> Result<Linter> {
+        Ok(Linter::new(&self.settings)?)
+    }
+
+    // Parse a commit like `cog check` does, lint rules included
+    fn check_commit(
+        &self,
+        commit: &git2::Commit,
+        commit_types: &CommitsMetadata,
+        linter: &Linter,
+    ) -> ParsedCommit {
+        let message = commit.message().unwrap_or_default();
+        let commit = parse_commit(self.cache.as_ref(), commit, commit_types)?;
+        let violations = linter.lint(&commit.message, message);
+        if violations.is_empty() {
+            return Ok(commit);
+        }
+
+        Err(Box::new(ConventionalCommitError::Lint {
+            summary: format_summary(&commit.message),
+            oid: commit.oid,
+            author: commit.author,
+            violations,
+        }))
+    }
+
+    /// Lazily iterate over the conventional commits in the given range, newest first.
+    /// Unlike [`CocoGitto::check`] the range is never collected, iteration can stop early.
+    pub fn commit_iter(&self, pattern: &RevspecPattern) -> Result<CommitIter<'_>> {
+        let (_, _, revwalk) = self.repository.get_range_revwalk(pattern)?;
+
+        Ok(CommitIter::new(
+            &self.repository,
+            revwalk,
+            self.settings.commit_types(),
+            self.cache.as_ref(),
+        ))
+    }
+
+    // Lazily walk the commits in `pattern`, or every commit reachable from HEAD
+    fn walk_commits(
+        &self,
+        pattern: Option<&RevspecPattern>,
+    ) -> Result<impl Iterator<Item = Result<git2::Commit<'_>>> + '_> {
+        let revwalk = match pattern {
+            Some(pattern) => self.repository.get_range_revwalk(pattern)?.2,
+            None => {
+                let mut revwalk = self.repository.0.revwalk()?;
+                revwalk.push_head()?;
+                revwalk
+            }
+        };
+
+        Ok(revwalk
+            // Shallow clones end with a missing parent
+            .take_while(|oid| !matches!(oid, Err(err) if err.code() == git2::ErrorCode::NotFound))
+            .map(|oid| {
+                oid.and_then(|oid| self.repository.0.find_commit(oid))
+                    .map_err(Into::into)
+            }))
+    }
+
+    /// Count the commits in `pattern` (or in the whole history) by type, scope and author.
+    /// Merge commits are ignored.
+    pub fn stats(&self, pattern: Option<&RevspecPattern>) -> Result<CommitStats> {
+        let commit_types = self.settings.commit_types();
+        let mut stats = CommitStats::default();
+
+        for git_commit in self.walk_commits(pattern)? {
+            let git_commit = git_commit?;
+            if git_commit.message().unwrap_or("").starts_with("Merge") {
+                continue;
+            }
+
+            let commit = parse_commit(self.cache.as_ref(), &git_commit, &commit_types);
+            let author = git_commit.author().name().unwrap_or_default().to_string();
+            stats.add(commit.ok().as_ref(), author);
+        }
+
+        Ok(stats)
+    }
+
+    /// The scopes of the `limit` most recent conventional commits, most used first
+    pub fn recent_scopes(&self, limit: usize) -> Result<Vec<String>> {
+        let commit_types = self.settings.commit_types();
+        let mut scopes: HashMap<String, usize> = HashMap::new();
+
+        for git_commit in self.walk_commits(None)?.take(limit) {
+            let git_commit = git_commit?;
+            let commit = parse_commit(self.cache.as_ref(), &git_commit, &commit_types);
+            if let Some(scope) = commit.ok().and_then(|commit| commit.message.scope) {
+                *scopes.entry(scope).or_default() += 1;
+            }
+        }
+
+        Ok(scopes
+            .into_iter()
+            .sorted_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)))
+            .map(|(scope, _)| scope)
+            .collect())
+    }
+
+    /// The version tags of the repository, latest first
+    pub fn version_tags(&self) -> Result<Vec<String>> {
+        let index = self.repository.tag_index()?;
+        Ok(index
+            .tags()
+            .iter()
+            .rev()
+            .map(|tag| tag.to_string_with_prefix())
+            .collect())
+    }
+
+    /// The version tags of the repository, oldest first. With `prefix`, only the tags having
+    /// this prefix are kept, an empty prefix keeping the tags without one.
+    pub fn list_tags(&self, prefix: Option<&str>) -> Result<Vec<String>> {
+        let index = self.repository.tag_index()?;
+        Ok(index
+            .tags()
+            .iter()
+            .filter(|tag| tag.to_version().is_ok())
+            .filter(|tag| prefix.is_none_or(|prefix| tag.prefix().unwrap_or_default() == prefix))
+            .map(Tag::to_string_with_prefix)
+            .collect())
+    }
+
+    /// The latest version tag, prefix included
+    pub fn latest_tag(&self) -> Result<String> {
+        Ok(self.repository.get_latest_tag()?.to_string_with_prefix())
+    }
+
+    /// Tag HEAD with `version`, the configured tag prefix is added unless `version` already has it.
+    /// The version must be greater than the current one and its tag must not exist yet, unless
+    /// `force` is set. The tag is annotated with `message` when given and signed according to
+    /// `sign`, or to the `tag.gpgSign` git config when `None`. Returns the created tag.
+    pub fn create_version_tag(
+        &self,
+        version: &str,
+        message: Option<&str>,
+        sign: Option<bool>,
+        force: bool,
+    ) -> Result<String> {
+        let version = self
+            .settings
+            .tag_prefix
+            .as_deref()
+            .and_then(|prefix| version.strip_prefix(prefix))
+            .unwrap_or(version);
+        let version = Version::parse(version).map_err(BumpError::from)?;
+
+        match self.current_version() {
+            Ok(current) if version <= current => {
+                return Err(CocogittoError::SemverViolation {
+                    current,
+                    next: version,
+                })
+            }
+            Ok(_) | Err(CocogittoError::NoTag) => {}
+            Err(err) => return Err(err),
+        }
+
+        let tag = self.prefixed_version(&version);
+        if !force {
+            self.ensure_tag_is_free(&tag)?;
+        }
+
+        let sign = sign.unwrap_or_else(|| self.repository.tag_sign());
+        match message {
+            Some(message) => self
+                .repository
+                .create_annotated_tag(&tag, message, sign, force)?,
+            None => self.repository.create_tag(&tag, sign, force)?,
+        }
+
+        Ok(tag)
+    }
+
+    /// Tag the first-parent commits changing the version declared in `manifest`, a path relative
+    /// to the repository root, or in the `version_source` setting when `None`. Repositories
+    /// adopting cocogitto get the tags of their past releases this way. Versions already tagged
+    /// or lower than a previous one are skipped, nothing is tagged on dry runs.
+    pub fn backfill_tags(
+        &self,
+        manifest: Option<&Path>,
+        dry_run: bool,
+    ) -> Result<Vec<BackfilledTag>> {
+        let manifest = manifest
+            .or(self.settings.version_source.as_deref())
+            .ok_or_else(|| {
+                anyhow!("no manifest to read the versions from, give one or set `version_source`")
+            })?;
+        let tagged: Vec<Version> = self
+            .repository
+            .tag_index()?
+            .tags()
+            .iter()
+            .filter_map(|tag| tag.to_version().ok())
+            .collect();
+
+        let mut revwalk = self.repository.0.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.simplify_first_parent()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+        let mut latest: Option<Version> = None;
+        let mut backfilled = vec![];
+        for oid in revwalk {
+            let commit = self.repository.0.find_commit(oid?)?;
+            let version = self
+                .repository
+                .get_file_content_at(&commit, manifest)
+                .and_then(|content| parse_manifest_version(manifest, &content).ok());
+
+            let Some(version) = version else {
+                continue;
+            };
+
+            if latest.as_ref().is_some_and(|latest| version <= *latest) {
+                continue;
+            }
+
+            latest = Some(version.clone());
+            if tagged.contains(&version) {
+                continue;
+            }
+
+            let tag = self.prefixed_version(&version);
+            if !dry_run {
+                self.repository.tag_commit(&tag, commit.id())?;
+                info!("Tagged {} with {}", commit.id(), tag);
+            }
+
+            backfilled.push(BackfilledTag {
+                tag,
+                oid: commit.id().to_string(),
+            });
+        }
+
+        Ok(backfilled)
+    }
+
+    /// Repository metadata exposed to changelog templates
+    pub fn repository_context(&self) -> Result<RepositoryContext> {
+        let index = self.repository.tag_index()?;
+        Ok(RepositoryContext {
+            remote_url: self.repository.remote_url(),
+            default_branch: self.repository.default_branch(),
+            tag_prefix: self.repository.tag_prefix().map(str::to_string),
+            commit_count: self.repository.commit_count()?,
+            tags: index
+                .tags()
+                .iter()
+                .map(|tag| tag.to_string_with_prefix())
+                .collect(),
+        })
+    }
+
+    pub fn get_log(&self, filters: CommitFilters) -> Result<String> {
+        let mut logs = vec![];
+        self.write_log(&mut logs, &filters, &LogOptions::default())?;
+
+        Ok(String::from_utf8_lossy(&logs).to_string())
+    }
+
+    /// Write the log of the commits matching `filters` to `out`.
+    /// Commits are walked and rendered one at a time, newest first, merge commits are skipped.
+    pub fn write_log<W: Write + ?Sized>(
+        &self,
+        out: &mut W,
+        filters: &CommitFilters,
+        options: &LogOptions,
+    ) -> Result<()> {
+        let commit_types = self.settings.commit_types();
+        let mut count = 0;
+        for git_commit in self.walk_commits(options.pattern.as_ref())? {
+            if options.max_count.is_some_and(|max| count >= max) {
+                break;
+            }
+
+            let git_commit = git_commit?;
+            if git_commit.message().unwrap_or("").starts_with("Merge")
+                || !filters.filter_git2_commit(&git_commit)
+            {
+                continue;
+            }
+
+            let commit = parse_commit(self.cache.as_ref(), &git_commit, &commit_types);
+            let keep = match &commit {
+                Ok(commit) => filters.filters(commit),
+                Err(_) => filters.no_error(),
+            };
+
+            if !keep {
+                continue;
+            }
+
+            let entry = options.format.render(&git_commit, &commit);
+            match options.format {
+                // Full entries span several lines, they are joined by a newline
+                LogFormat::Full if count > 0 => write!(out, "\n{}", entry)?,
+                LogFormat::Full => write!(out, "{}", entry)?,
+                LogFormat::Oneline | LogFormat::Json => writeln!(out, "{}", entry)?,
+            }
+
+            count += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Tries to get a commit message conforming to the Conventional Commit spec.
+    /// If the commit message does _not_ conform, `None` is returned instead.
+    pub fn get_conventional_message(
+        commit_type: &str,
+        scope: Option<String>,
+        summary: String,
+        body: Option<String>,
+        footer: Option<String>,
+        is_breaking_change: bool,
+    ) -> Result<String> {
+        // Ensure commit type is known
+        let commit_type = CommitType::from(commit_type);
+
+        // Ensure footers are correctly formatted
+        let footers = match footer {
+            Some(footers) => parse_footers(&footers)?,
+            None => Vec::with_capacity(0),
+        };
+
+        let conventional_message = ConventionalCommit {
+            commit_type,
+            scope,
+            body,
+            footers,
+            summary,
+            is_breaking_change,
+        }
+        .to_string();
+
+        // Validate the message
+        conventional_commit_parser::parse(&conventional_message)?;
+
+        Ok(conventional_message)
+    }
+
+    #[allow(clippy::too_many_arguments)] // FIXME
+    pub fn conventional_commit(
+        &self,
+        commit_type: &str,
+        scope: Option<String>,
+        summary: String,
+        body: Option<String>,
+        footer: Option<String>,
+        is_breaking_change: bool,
+        sign: Option<bool>,
+    ) -> Result<()> {
+        if self.settings.commit.check_branch {
+            self.ensure_branch_allowed("commit")?;
+        }
+
+        // Ensure commit type is known, aliases are written with their canonical type
+        let commit_type = self.settings.canonical_commit_type(commit_type);
+        let commit_type = CommitType::from(commit_type.as_str());
+
+        // Ensure footers are correctly formatted
+        let mut footers = match footer {
+            Some(footers) => parse_footers(&footers)?,
+            None => Vec::with_capacity(0),
+        };
+
+        if let Some(trailer) = self.sign_off_trailer(self.author.as_ref())? {
+            if !footers.contains(&trailer) {
+                footers.push(trailer);
+            }
+        }
+
+        let conventional_message = ConventionalCommit {
+            commit_type,
+            scope,
+            body,
+            footers,
+            summary,
+            is_breaking_change,
+        }
+        .to_string();
+
+        // Validate the message
+        let parsed = conventional_commit_parser::parse(&conventional_message)?;
+        let violations = self.linter()?.lint(&parsed, &conventional_message);
+        if !violations.is_empty() {
+            let err = ConventionalCommitError::Lint {
+                oid: "not committed".to_string(),
+                summary: format_summary(&parsed),
+                author: self
+                    .get_committer()
+                    .unwrap_or_else(|_| "Unknown".to_string()),
+                violations,
+            };
+
+            if err.severity() == Severity::Error {
+                return Err(CocogittoError::InvalidCommit(Box::new(err)));
+            }
+
+            warn!("{}", err);
+        }
+
+        self.run_commit_hooks(HookType::PreCommit, &conventional_message)?;
+
+        // Git commit
+        // Signing defaults to the `commit.gpgSign` git config unless explicitly set
+        let sign = sign.unwrap_or_else(|| self.repository.gpg_sign());
+        let oid = self.repository.commit_with(
+            &conventional_message,
+            &CommitOptions {
+                sign,
+                author: self.author.as_ref(),
+                committer: None,
+                allow_empty: self.allow_empty,
+            },
+        )?;
+
+        self.run_commit_hooks(HookType::PostCommit, &conventional_message)?;
+
+        // Pretty print a conventional commit summary
+        let commit = self.repository.0.find_commit(oid)?;
+        let commit = Commit::from_git_commit(&commit, &self.settings.commit_types())?;
+        info!("{}", commit);
+
+        Ok(())
+    }
+
+    pub fn create_version(
+        &mut self,
+        increment: VersionIncrement,
+        pre_release: Option<&str>,
+        hooks_config: Option<&str>,
+        dry_run: bool,
+        sign: Option<bool>,
+    ) -> Result<BumpOutcome> {
+        if self.settings == Settings::default() {
+            let part1 = "Warning: using".yellow();
+            let part2 = "with the default configuration. \n".yellow();
+            let part3 = "You may want to create a".yellow();
+            let part4 = "file in your project root to configure bumps.\n".yellow();
+            warn!(
+                "{} 'cog bump' {}{} 'cog.toml' {}",
+                part1, part2, part3, part4
+            );
+        }
+        let repo_dir = self
+            .repository
+            .get_repo_dir()
+            .map(Path::to_path_buf)
+            .ok_or(CocogittoError::BareRepository)?;
+
+        self.ensure_history(true)?;
+
+        let statuses = self.repository.get_statuses()?;
+        let dirty = self.check_dirty_worktree(statuses)?;
+
+        self.ensure_branch_allowed("bump")?;
+
+        let stabilize = increment == VersionIncrement::Stabilize;
+        let (current_version, next_version) = self.next_version(increment, pre_release)?;
+        let increment = Increment::between(&current_version, &next_version);
+
+        let version_str = match &self.settings.tag_prefix {
+            None => next_version.to_string(),
+            Some(prefix) => format!("{}{}", prefix, next_version),
+        };
+
+        if dry_run {
+            return Ok(BumpOutcome {
+                previous_tag: self
+                    .repository
+                    .get_latest_tag()
+                    .ok()
+                    .map(|tag| tag.to_string()),
+                new_tag: version_str,
+                increment,
+                changelog_path: None,
+                changelog: None,
+                pre_bump_hooks: vec![],
+                post_bump_hooks: vec![],
+                commit: None,
+                manifest_path: None,
+                published: vec![],
+            });
+        }
+
+        if !self.force_tag {
+            self.ensure_tag_is_free(&version_str)?;
+        }
+
+        if self.settings.bump.require_up_to_date {
+            self.ensure_up_to_date()?;
+        }
+
+        if stabilize && !self.acknowledge_breaking_changes {
+            self.ensure_no_breaking_change()?;
+        }
+
+        let stash = match dirty {
+            DirtyWorktree::Stash => Some(self.repository.stash_dirty_changes(&version_str)?),
+            _ => None,
+        };
+
+        let outcome = self.release(
+            &repo_dir,
+            next_version,
+            increment,
+            hooks_config,
+            sign,
+            &dirty,
+            stabilize,
+        );
+
+        // Restored here only, a failed release may have stashed its own changes on top
+        if let Some(stash) = stash {
+            self.restore_dirty_changes(&version_str, stash);
+        }
+
+        let mut outcome = outcome?;
+        if self.settings.publish.on_bump {
+            outcome.published = self.publish(false)?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Publish the current version with the `publish.providers`, in order. On dry runs the
+    /// providers check the publication when they can, the others are skipped.
+    pub fn publish(&self, dry_run: bool) -> Result<Vec<HookOutput>> {
+        let version = self.current_version()?;
+        let tag = self.prefixed_version(&version);
+        let version = version.to_string();
+
+        let mut outputs = vec![];
+        for provider in &self.settings.publish.providers {
+            let commands = provider.commands(&version, &tag, dry_run);
+            if dry_run && !provider.supports_dry_run() {
+                for command in commands {
+                    info!("Dry run, skipping `{}`", command.command);
+                    outputs.push(HookOutput {
+                        command: command.command,
+                        ..HookOutput::default()
+                    });
+                }
+
+                continue;
+            }
+
+            let hooks = commands
+                .iter()
+                .map(|command| {
+                    Hook::from_config(
+                        command,
+                        self.repository.get_repo_dir(),
+                        self.settings.hook_shell,
+                    )
+                })
+                .try_collect()?;
+            info!("Publishing {} with {}", tag, provider);
+            outputs.extend(Self::execute_hooks(hooks)?);
+        }
+
+        Ok(outputs)
+    }
+
+    // Write the changelog, run the hooks, commit and tag the release
+    #[allow(clippy::too_many_arguments)]
+    fn release(
+        &mut self,
+        repo_dir: &Path,
+        next_version: Version,
+        increment: Increment,
+        hooks_config: Option<&str>,
+        sign: Option<bool>,
+        dirty: &DirtyWorktree,
+        stabilize: bool,
+    ) -> Result<BumpOutcome> {
+        let version_str = self.prefix_version(next_version.to_string());
+
+        let origin = self.release_origin()?;
+        let target = self.repository.get_head_commit_oid()?.to_string();
+        let pattern = (origin.as_str(), target.as_str());
+
+        let pattern = RevspecPattern::from(pattern);
+        let mut changelog = self.get_changelog_with_target_version(pattern, &version_str)?;
+        if stabilize {
+            changelog.notes = self.settings.changelog.stabilize_note.clone();
+        }
+
+        // Read before anything is written, an invalid pattern must not leave a half done release
+        let issues = match &self.settings.integrations.issue_tracker {
+            Some(tracker) => {
+                let pattern = tracker.pattern().map_err(|err| {
+                    anyhow!("invalid integrations.issue_tracker.pattern: {}", err)
+                })?;
+                let messages = changelog
+                    .commits
+                    .iter()
+                    .map(|commit| &commit.commit.message);
+                issue_tracker::issue_keys(messages, &pattern)
+            }
+            None => vec![],
+        };
+
+        let manifest = self.settings.bump.manifest.is_some().then(|| {
+            ReleaseManifest::new(
+                &changelog,
+                ManifestRange {
+                    from: origin.clone(),
+                    to: target.clone(),
+                },
+            )
+        });
+
+        // The changelog path is relative to the working tree root, not to the current directory
+        let repository_context = self.repository_context()?;
+        self.write_changelog_files(&changelog, repo_dir, &repository_context)?;
+        let template = self
+            .settings
+            .get_changelog_template()?
+            .with_repository(repository_context);
+        let changelog = changelog.into_markdown(template)?;
+        let path = if self.settings.changelog.enabled {
+            let path = repo_dir.join(&self.settings.changelog.path);
+            changelog::write_markdown(&path, &changelog)?;
+            Some(path)
+        } else {
+            None
+        };
+
+        let current = self
+            .repository
+            .get_latest_tag()
+            .map(|tag| self.hook_version(&tag.to_string_with_prefix()))
+            .ok();
+
+        let next_version = self.hook_version(&self.prefix_version(next_version.to_string()));
+
+        let hook_result = self.run_hooks(
             HookType::PreBump,
             current.as_ref(),
             &next_version,
+            increment,
             hooks_config,
         );
 
-        self.repository.add_all()?;
+        self.stage_release(repo_dir, dirty)?;
 
         // Hook failed, we need to stop here and reset
         // the repository to a clean state
-        if let Err(err) = hook_result {
-            self.repository.stash_failed_version(&version_str)?;
-            error!(
-                "{}",
-                PreHookError {
+        let pre_bump_hooks = match hook_result {
+            Ok(outputs) => outputs,
+            Err(err) => {
+                self.repository.stash_failed_version(&version_str)?;
+
+                let policy = match &err {
+                    CocogittoError::HookFailed { policy, .. } => Some(policy.clone()),
+                    _ => None,
+                };
+                return Err(PreHookError {
                     cause: err.to_string(),
                     version: version_str,
                     stash_number: 0,
+                    policy,
                 }
-            );
+                .into());
+            }
+        };
 
-            exit(1);
-        }
+        // An explicit signing choice applies to both the release commit and its tag
+        let sign_commit = sign.unwrap_or_else(|| self.repository.gpg_sign());
+        let sign_tag = sign.unwrap_or_else(|| self.repository.tag_sign());
 
-        let version_str = Self::prefix_version(version_str);
-        let sign = self.repository.gpg_sign();
+        let release_author = self.release_author()?;
+        let mut message = format!("chore(version): {}", next_version.prefixed_tag);
+        if let Some(trailer) = self.sign_off_trailer(release_author.as_ref())? {
+            write!(message, "\n\n{}: {}", trailer.token, trailer.content).unwrap();
+        }
 
-        self.repository.commit(
-            &format!("chore(version): {}", next_version.prefixed_tag),
-            sign,
+        let commit = self.repository.commit_with(
+            &message,
+            &CommitOptions {
+                sign: sign_commit,
+                author: release_author.as_ref(),
+                committer: release_author.as_ref(),
+                allow_empty: self.allow_empty,
+            },
         )?;
 
-        self.repository.create_tag(&version_str)?;
+        self.repository
+            .create_tag(&version_str, sign_tag, self.force_tag)?;
 
-        self.run_hooks(
+        let post_bump_hooks = self.run_hooks(
             HookType::PostBump,
             current.as_ref(),
             &next_version,
+            increment,
             hooks_config,
         )?;
 
-        let current = current
-            .map(|current| current.prefixed_tag)
-            .unwrap_or_else(|| "...".to_string());
-        let bump = format!("{} -> {}", current, next_version.prefixed_tag).green();
+        let previous_version = current.as_ref().map(|current| current.prefixed_tag.clone());
+        let manifest_path = match (manifest, &self.settings.bump.manifest) {
+            (Some(mut manifest), Some(settings)) => {
+                let artifacts = settings
+                    .artifacts()
+                    .map_err(|err| anyhow!("invalid bump.manifest.artifacts pattern: {}", err))?;
+                manifest.version = next_version.to_version()?.to_string();
+                manifest.tag = next_version.prefixed_tag.clone();
+                manifest.previous_tag = previous_version.clone();
+                manifest.commit = commit.to_string();
+                manifest.pre_bump_hooks = pre_bump_hooks.clone();
+                manifest.post_bump_hooks = post_bump_hooks.clone();
+                manifest.artifacts = manifest::find_artifacts(repo_dir, &artifacts)?;
+
+                let path = repo_dir.join(&settings.path);
+                manifest.write(&path)?;
+                Some(path)
+            }
+            _ => None,
+        };
+
+        self.notify(&ReleaseNotification {
+            compare_url: self.settings.get_template_context().and_then(|context| {
+                let from = previous_version.as_ref()?;
+                Some(context.compare_url(from, &next_version.prefixed_tag))
+            }),
+            version: next_version.prefixed_tag.clone(),
+            previous_version: previous_version.clone(),
+            changelog: changelog.clone(),
+        });
+        self.update_issues(
+            &issues,
+            &next_version.prefixed_tag,
+            previous_version.as_deref(),
+        );
+        self.update_milestones(&next_version);
+
+        let bump = format!(
+            "{} -> {}",
+            previous_version.as_deref().unwrap_or("..."),
+            next_version.prefixed_tag
+        )
+        .green();
         info!("Bumped version: {}", bump);
 
+        Ok(BumpOutcome {
+            previous_tag: previous_version,
+            new_tag: next_version.prefixed_tag,
+            increment,
+            changelog_path: path,
+            changelog: Some(changelog),
+            pre_bump_hooks,
+            post_bump_hooks,
+            commit: Some(commit.to_string()),
+            manifest_path,
+            published: vec![],
+        })
+    }
+
+    // Write the release to the additional changelog files, keeping the commit types each one asks for
+    fn write_changelog_files(
+        &self,
+        release: &Release,
+        repo_dir: &Path,
+        repository_context: &RepositoryContext,
+    ) -> Result<()> {
+        for file in &self.settings.changelog.files {
+            let mut release = release.clone();
+            release
+                .retain_commits(|commit| file.includes(commit.commit.message.commit_type.as_ref()));
+            self.dedupe(&mut release, file.dedupe);
+
+            let template = self
+                .settings
+                .get_changelog_file_template(file)?
+                .with_repository(repository_context.clone());
+            let changelog = release.into_markdown(template)?;
+            changelog::write_markdown(repo_dir.join(&file.path), &changelog)?;
+        }
+
+        Ok(())
+    }
+
+    // Mark or collapse the commits applying the same changes as an older commit of the changelog
+    fn dedupe(&self, release: &mut Release, dedupe: Option<Dedupe>) {
+        let Some(dedupe) = dedupe else {
+            return;
+        };
+
+        match dedupe.by {
+            DuplicateKey::OriginalSha => {
+                release.mark_duplicates(|commit| Some(commit.original_oid().to_string()))
+            }
+            DuplicateKey::PatchId => release.mark_duplicates(|commit| {
+                self.repository
+                    .patch_id(&commit.commit.oid)
+                    .map(|patch_id| patch_id.to_string())
+            }),
+            DuplicateKey::Summary => release.mark_duplicates(|commit| {
+                let message = &commit.commit.message;
+                Some(format!(
+                    "{}({}): {}",
+                    message.commit_type,
+                    message.scope.as_deref().unwrap_or_default(),
+                    message.summary
+                ))
+            }),
+        }
+
+        if dedupe.mode == DuplicateMode::Collapse {
+            release.retain_commits(|commit| commit.duplicate_of.is_none());
+        }
+    }
+
+    // Stage the release changes, limited to the `bump.commit_paths` and the changelogs when set.
+    // Untracked files allowed by the `bump.dirty` policy are left out.
+    fn stage_release(&self, repo_dir: &Path, dirty: &DirtyWorktree) -> Result<()> {
+        let commit_paths = self
+            .settings
+            .commit_paths()
+            .map_err(|err| anyhow!("invalid bump.commit_paths pattern: {}", err))?;
+        let changelogs: Vec<PathBuf> = std::iter::once(&self.settings.changelog.path)
+            .filter(|_| self.settings.changelog.enabled)
+            .chain(self.settings.changelog.files.iter().map(|file| &file.path))
+            .map(|path| path.strip_prefix(repo_dir).unwrap_or(path).to_path_buf())
+            .collect();
+        let untracked: &[String] = match dirty {
+            DirtyWorktree::Untracked(paths) => paths,
+            _ => &[],
+        };
+
+        self.repository.add_all_matching(|path| {
+            let is_untracked = untracked.iter().any(|untracked| {
+                untracked == path || (untracked.ends_with('/') && path.starts_with(untracked))
+            });
+            let is_release_path = commit_paths.as_ref().is_none_or(|commit_paths| {
+                commit_paths.is_match(path)
+                    || changelogs
+                        .iter()
+                        .any(|changelog| changelog == Path::new(path))
+            });
+
+            is_release_path && !is_untracked
+        })?;
+
         Ok(())
     }
 
+    // Apply the `bump.dirty` policy to the changes found before a bump
+    fn check_dirty_worktree(&self, statuses: Statuses) -> Result<DirtyWorktree> {
+        if statuses.0.is_empty() {
+            return Ok(DirtyWorktree::Clean);
+        }
+
+        match self.settings.bump.dirty {
+            DirtyPolicy::Error => Err(CocogittoError::DirtyRepository(statuses)),
+            DirtyPolicy::Stash => Ok(DirtyWorktree::Stash),
+            DirtyPolicy::AllowUntracked => {
+                let (untracked, uncommitted): (Vec<_>, Vec<_>) = statuses
+                    .0
+                    .into_iter()
+                    .partition(|status| matches!(status, Status::Untracked(Changes::New(_))));
+
+                if !uncommitted.is_empty() {
+                    return Err(CocogittoError::DirtyRepository(Statuses(uncommitted)));
+                }
+
+                Ok(DirtyWorktree::Untracked(
+                    untracked
+                        .iter()
+                        .map(|status| status.path().to_string())
+                        .collect(),
+                ))
+            }
+        }
+    }
+
+    // Put back the changes stashed before the bump
+    fn restore_dirty_changes(&mut self, version: &str, stash: Oid) {
+        if let Err(err) = self.repository.pop_stash(stash) {
+            warn!(
+                "Could not restore the changes stashed before the bump: {}\n\
+                \tthey are kept in the stash `cog_bump_dirty_{}`",
+                err, version
+            );
+        }
+    }
+
+    // The commit the next release starts from. Without tag the current version is
+    // either 0.0.0 or read from the version source
+    fn release_origin(&self) -> Result<String> {
+        match self.repository.get_latest_tag() {
+            Ok(tag) => Ok(tag.oid_unchecked().to_string()),
+            Err(TagError::NoTag) => Ok(self.repository.get_first_commit()?.to_string()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    // Refuse to stabilize while breaking changes since the latest version were not reviewed
+    fn ensure_no_breaking_change(&self) -> Result<()> {
+        // Without tag the whole history is checked, the first commit included
+        let commits = match self.repository.get_latest_tag() {
+            Ok(tag) => {
+                let pattern = format!("{}..", tag.oid_unchecked());
+                self.repository
+                    .get_commit_range(&RevspecPattern::from(pattern.as_str()))?
+                    .commits
+            }
+            Err(TagError::NoTag) => self.repository.all_commits()?.commits,
+            Err(err) => return Err(err.into()),
+        };
+        let commit_types = self.settings.commit_types();
+        let breaking_changes: Vec<String> = commits
+            .iter()
+            .filter_map(|commit| Commit::from_git_commit(commit, &commit_types).ok())
+            .filter(|commit| commit.message.is_breaking_change)
+            .map(|commit| format!("{} {}", commit.shorthand(), commit.message.summary))
+            .collect();
+
+        if breaking_changes.is_empty() {
+            Ok(())
+        } else {
+            Err(CocogittoError::UnacknowledgedBreakingChanges {
+                commits: breaking_changes,
+            })
+        }
+    }
+
+    // Refuse to release from a branch missing commits of its upstream
+    fn ensure_up_to_date(&self) -> Result<()> {
+        match self.repository.fetch_upstream()? {
+            Some(upstream) if upstream.behind > 0 => Err(CocogittoError::BranchNotUpToDate {
+                branch: upstream.branch,
+                upstream: upstream.name,
+                ahead: upstream.ahead,
+                behind: upstream.behind,
+            }),
+            Some(_) => Ok(()),
+            None => {
+                warn!("The current branch tracks no remote branch, it may not be up to date");
+                Ok(())
+            }
+        }
+    }
+
+    // Refuse a release tag created by hand, locally or on the default remote
+    fn ensure_tag_is_free(&self, tag: &str) -> Result<()> {
+        if self.repository.tag_exists(tag) {
+            return Err(CocogittoError::TagAlreadyExists {
+                tag: tag.to_string(),
+                remote: None,
+            });
+        }
+
+        if !self.settings.tag.check_remote {
+            return Ok(());
+        }
+
+        match self.repository.remote_tag_exists(tag) {
+            Some((remote, true)) => Err(CocogittoError::TagAlreadyExists {
+                tag: tag.to_string(),
+                remote: Some(remote),
+            }),
+            Some((_, false)) => Ok(()),
+            None => {
+                if let Some(remote) = self.repository.default_remote() {
+                    warn!("Could not check whether tag {} exists on {}", tag, remote);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The changelog entry a commit message would get once committed, without the release heading
+    pub fn preview_changelog_entry(&self, message: &str) -> Result<String> {
+        let message = conventional::commit::strip_comments(message);
+        let message = conventional_commit_parser::parse(message.trim())
+            .map_err(|err| Box::new(ConventionalCommitError::ParseError(err)))?;
+
+        let commit = Commit {
+            oid: Oid::zero().to_string(),
+            message,
+            author: self.repository.get_author()?,
+            date: Utc::now().naive_utc(),
+        };
+
+        let builder = ReleaseBuilder::new(&self.settings);
+        let release = Release {
+            version: OidOf::Head(Oid::zero()),
+            from: OidOf::Other(Oid::zero()),
+            date: commit.date,
+            commits: vec![builder.changelog_commit(commit)],
+            notes: None,
+            previous: None,
+        };
+
+        let template = self
+            .settings
+            .get_changelog_template()?
+            .with_repository(self.repository_context()?);
+        let entry = release.into_markdown(template)?;
+        Ok(changelog::strip_release_heading(&entry))
+    }
+
     pub fn get_changelog_at_tag(&self, tag: &str, template: Template) -> Result<String> {
         let pattern = format!("..{}", tag);
         let pattern = RevspecPattern::from(pattern.as_str());
         let changelog = self.get_changelog(pattern, false)?;
 
-        changelog
-            .into_markdown(template)
-            .map_err(|err| anyhow!(err))
+        changelog.into_markdown(template).map_err(Into::into)
+    }
+
+    /// The notes of a released version without its heading, rendered from its commits
+    /// or, with `from_changelog`, read from the changelog file
+    pub fn get_release_notes(
+        &self,
+        tag: &str,
+        template: Template,
+        from_changelog: bool,
+    ) -> Result<String> {
+        if !from_changelog {
+            let release = self.get_changelog_at_tag(tag, template)?;
+            return Ok(changelog::strip_release_heading(&release));
+        }
+
+        let repo_dir = self
+            .repository
+            .get_repo_dir()
+            .ok_or(CocogittoError::BareRepository)?;
+        let path = repo_dir.join(&self.settings.changelog.path);
+        let content = fs::read_to_string(&path)
+            .map_err(|err| ChangelogError::ReadError(path.clone(), err))?;
+
+        changelog::extract_release_notes(&content, tag)
+            .ok_or_else(|| ChangelogError::ReleaseNotFound(tag.to_string(), path).into())
     }
 
     /// Used for cog bump. the target version
@@ -574,11 +2032,18 @@ impl CocoGitto {
         &self,
         pattern: RevspecPattern,
         target_version: &str,
-    ) -> Result<Release> {
+    ) -> Result<Release<'_>> {
         let commit_range = self.repository.get_commit_range(&pattern)?;
 
-        let mut release = Release::from(commit_range);
-        release.version = OidOf::Tag(Tag::new(target_version, None)?);
+        let mut release =
+            Release::from_cached_commit_range(commit_range, &self.settings, self.cache.as_ref());
+        release.version = OidOf::Tag(Tag::new(
+            target_version,
+            None,
+            self.repository.tag_prefix(),
+        )?);
+        self.overrides.apply(&mut release);
+        self.dedupe(&mut release, self.settings.changelog.dedupe);
         Ok(release)
     }
 
@@ -589,15 +2054,332 @@ impl CocoGitto {
         &self,
         pattern: RevspecPattern,
         with_child_releases: bool,
-    ) -> Result<Release> {
-        if with_child_releases {
+    ) -> Result<Release<'_>> {
+        let mut release = if with_child_releases {
             self.repository
-                .get_release_range(pattern)
-                .map_err(Into::into)
+                .get_release_range(pattern, &self.settings, self.cache.as_ref())?
         } else {
             let commit_range = self.repository.get_commit_range(&pattern)?;
+            Release::from_cached_commit_range(commit_range, &self.settings, self.cache.as_ref())
+        };
+
+        self.overrides.apply(&mut release);
+        self.dedupe(&mut release, self.settings.changelog.dedupe);
+        Ok(release)
+    }
+
+    /// Keep only the commits of `release` and its previous releases touching `paths`, glob
+    /// patterns relative to the repository root. A directory matches all the files below it.
+    pub fn retain_paths(&self, release: &mut Release, paths: &[String]) -> Result<()> {
+        let mut builder = GlobSetBuilder::new();
+        for path in paths {
+            let path = path.trim_end_matches('/');
+            for glob in [path.to_string(), format!("{}/**", path)] {
+                builder.add(Glob::new(&glob).with_context(|| format!("invalid path `{}`", path))?);
+            }
+        }
+        let paths = builder.build().context("invalid paths")?;
+
+        let mut touching = HashSet::new();
+        for release in release.iter() {
+            for commit in &release.commits {
+                let oid = Oid::from_str(&commit.commit.oid)?;
+                if self.repository.commit_touches(oid, &paths)? {
+                    touching.insert(commit.commit.oid.clone());
+                }
+            }
+        }
+
+        release.retain_commits(|commit| touching.contains(&commit.commit.oid));
+        Ok(())
+    }
+
+    /// The version of the latest tag, without the tag prefix.
+    /// Until the first tag, the version is read from the configured `version_source` if any.
+    pub fn current_version(&self) -> Result<Version> {
+        match (
+            self.repository.get_latest_tag(),
+            &self.settings.version_source,
+        ) {
+            (Ok(tag), _) => Ok(tag.to_version()?),
+            (Err(TagError::NoTag), Some(source)) => {
+                let path = match self.repository.get_repo_dir() {
+                    Some(repo_dir) => repo_dir.join(source),
+                    None => source.clone(),
+                };
+
+                let version = read_manifest_version(&path)?;
+                info!("No tag found, using version {} from {:?}", version, source);
+                Ok(version)
+            }
+            (Err(err), _) => Err(err.into()),
+        }
+    }
 
-            Ok(Release::from(commit_range))
+    /// The version `cog bump --auto` would create, nothing is written to the repository
+    pub fn next_auto_version(&self) -> Result<Version> {
+        let (_, next_version) = self.next_version(VersionIncrement::Auto, None)?;
+        Ok(next_version)
+    }
+
+    /// A version between releases, in the manner of `git describe`: the current version followed
+    /// by the number of commits since the latest tag and the short id of HEAD, e.g.
+    /// `1.2.3-dev.5+gabcdef1`. The current version is returned as is when HEAD is tagged.
+    pub fn snapshot_version(&self) -> Result<Version> {
+        let mut version = match self.current_version() {
+            Ok(version) => version,
+            Err(CocogittoError::NoTag) => Version::new(0, 0, 0),
+            Err(err) => return Err(err),
+        };
+
+        let distance = match self.repository.get_latest_tag() {
+            Ok(tag) => self.repository.commit_count_since(*tag.oid_unchecked())?,
+            Err(TagError::NoTag) => self.repository.commit_count()?,
+            Err(err) => return Err(err.into()),
+        };
+
+        if distance == 0 {
+            return Ok(version);
+        }
+
+        let pre_release = if version.pre.is_empty() {
+            format!("dev.{}", distance)
+        } else {
+            format!("{}.dev.{}", version.pre, distance)
+        };
+        let head = self.repository.get_head_commit_oid()?.to_string();
+        version.pre = Prerelease::new(&pre_release).map_err(BumpError::from)?;
+        version.build = BuildMetadata::new(&format!("g{}", &head[..7])).map_err(BumpError::from)?;
+        Ok(version)
+    }
+
+    /// Prepend the configured tag prefix to a version
+    pub fn prefixed_version(&self, version: &Version) -> String {
+        self.prefix_version(version.to_string())
+    }
+
+    /// Render the bump hooks with versions substituted, without executing them
+    pub fn render_bump_hooks(
+        &self,
+        increment: VersionIncrement,
+        pre_release: Option<&str>,
+        hooks_config: Option<&str>,
+    ) -> Result<Vec<(HookType, String)>> {
+        let (current_version, next_version) = self.next_version(increment, pre_release)?;
+        let increment = Increment::between(&current_version, &next_version);
+
+        let current = self
+            .repository
+            .get_latest_tag()
+            .map(|tag| self.hook_version(&tag.to_string_with_prefix()))
+            .ok();
+
+        let next_version = self.hook_version(&self.prefix_version(next_version.to_string()));
+
+        let mut rendered = vec![];
+        for hook_type in [HookType::PreBump, HookType::PostBump] {
+            for mut hook in self.load_hooks(hook_type, Some(increment), hooks_config)? {
+                hook.insert_versions(current.as_ref(), &next_version)?;
+                rendered.push((hook_type, hook.to_string()));
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    // Shallow clones miss part of the history: deepen them when configured, otherwise
+    // fail if the latest tag, when required, is not part of the local history
+    fn ensure_history(&self, require_tag: bool) -> Result<()> {
+        if !self.repository.is_shallow() {
+            return Ok(());
+        }
+
+        if self.settings.unshallow {
+            info!("Shallow clone detected, fetching the full history");
+            self.repository.unshallow()?;
+            return Ok(());
+        }
+
+        warn!("Shallow clone detected, commit history may be incomplete");
+        if !require_tag {
+            return Ok(());
+        }
+
+        match self.repository.get_latest_tag() {
+            Ok(tag) if !self.repository.is_reachable_from_head(*tag.oid_unchecked()) => {
+                Err(Git2Error::ShallowClone {
+                    tag: Some(tag.to_string()),
+                }
+                .into())
+            }
+            Err(TagError::NoTag) => Err(Git2Error::ShallowClone { tag: None }.into()),
+            _ => Ok(()),
+        }
+    }
+
+    // Get the current version and the version targeted by the given increment
+    fn next_version(
+        &self,
+        increment: VersionIncrement,
+        pre_release: Option<&str>,
+    ) -> Result<(Version, Version)> {
+        let current_version = match self.current_version() {
+            Ok(version) => version,
+            Err(CocogittoError::NoTag) => {
+                warn!("Failed to get current version, falling back to 0.0.0");
+                Version::new(0, 0, 0)
+            }
+            Err(err) => return Err(err),
+        };
+
+        // Branch channels only apply when neither the version nor the pre-release are given
+        let channel = match (&increment, pre_release) {
+            (VersionIncrement::Manual(_), _) | (_, Some(_)) => None,
+            _ => self
+                .repository
+                .get_branch_shorthand()
+                .and_then(|branch| self.settings.channel(&branch).map(str::to_string)),
+        };
+
+        let mut next_version = increment.bump(
+            &current_version,
+            &self.repository,
+            &self.settings.commit_types(),
+        )?;
+
+        // On a channel, a pre-release is followed by the next pre-release of the same version
+        if channel.is_some() && !current_version.pre.is_empty() {
+            next_version = match Increment::between(&current_version, &next_version) {
+                Increment::Major if current_version.minor == 0 && current_version.patch == 0 => {
+                    release_of(&current_version)
+                }
+                Increment::Minor if current_version.patch == 0 => release_of(&current_version),
+                Increment::Patch => release_of(&current_version),
+                _ => next_version,
+            };
+        }
+
+        if next_version.le(&current_version) || next_version.eq(&current_version) {
+            return Err(CocogittoError::SemverViolation {
+                current: current_version,
+                next: next_version,
+            });
+        };
+
+        if let Some(ceiling) = self.repository.version_ceiling() {
+            if next_version >= *ceiling {
+                return Err(CocogittoError::MaintenanceCeilingExceeded {
+                    branch: self.repository.get_branch_shorthand().unwrap_or_default(),
+                    next: next_version,
+                    ceiling: ceiling.clone(),
+                });
+            }
+        }
+
+        if let Some(pre_release) = pre_release {
+            next_version.pre = Prerelease::new(pre_release).map_err(BumpError::from)?;
+        } else if let Some(channel) = channel {
+            let number = self.latest_channel_number(&next_version, &channel)? + 1;
+            let pre_release = format!("{}.{}", channel, number);
+            next_version.pre = Prerelease::new(&pre_release).map_err(BumpError::from)?;
+        }
+
+        Ok((current_version, next_version))
+    }
+
+    // The greatest `<channel>.<number>` pre-release tagged for this version, 0 when there is none
+    fn latest_channel_number(&self, version: &Version, channel: &str) -> Result<u64> {
+        let number = self
+            .repository
+            .tag_index()?
+            .tags()
+            .iter()
+            .filter_map(|tag| tag.to_version().ok())
+            .filter(|tagged| release_of(tagged) == release_of(version))
+            .filter_map(|tagged| {
+                tagged
+                    .pre
+                    .as_str()
+                    .strip_prefix(channel)?
+                    .strip_prefix('.')?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .max()
+            .unwrap_or(0);
+
+        Ok(number)
+    }
+
+    // Notifications are sent once the release is tagged, a failure must not abort the bump
+    fn notify(&self, notification: &ReleaseNotification) {
+        for webhook in &self.settings.notifications.webhooks {
+            if let Err(err) = notification.send(webhook) {
+                warn!("Failed to send release notification: {}", err);
+            }
+        }
+    }
+
+    // Like notifications, issues are updated once the release is tagged and failures only warn
+    fn update_issues(&self, keys: &[String], version: &str, previous_version: Option<&str>) {
+        let Some(tracker) = &self.settings.integrations.issue_tracker else {
+            return;
+        };
+
+        let mut tracker = tracker.clone();
+        if tracker.repository.is_none() {
+            let changelog = &self.settings.changelog;
+            tracker.repository = changelog
+                .owner
+                .as_ref()
+                .zip(changelog.repository.as_ref())
+                .map(|(owner, repository)| format!("{}/{}", owner, repository));
+        }
+
+        let mut context = tera::Context::new();
+        context.insert("version", version);
+        context.insert("previous_version", &previous_version);
+        let comment = match tera::Tera::one_off(&tracker.comment, &context, false) {
+            Ok(comment) => comment,
+            Err(err) => {
+                warn!("Failed to render the issue comment: {}", err);
+                return;
+            }
+        };
+
+        for key in keys {
+            let result = tracker.requests(key, &comment).and_then(|requests| {
+                requests
+                    .iter()
+                    .try_for_each(|request| tracker.send(request))
+            });
+
+            match result {
+                Ok(()) => info!("Updated issue {}", key),
+                Err(err) => warn!("Failed to update issue {}: {}", key, err),
+            }
+        }
+    }
+
+    // Pre-releases leave the milestones of their version open, failures only warn
+    fn update_milestones(&self, version: &HookVersion) {
+        let Some(settings) = &self.settings.integrations.milestones else {
+            return;
+        };
+
+        let result = version.to_version().and_then(|next| {
+            if !next.pre.is_empty() {
+                return Ok(());
+            }
+
+            let remote = self.settings.get_template_context().ok_or_else(|| {
+                anyhow!("milestones need the changelog remote, owner and repository")
+            })?;
+            Milestones::new(settings, &remote)?.release(&next, &version.prefixed_tag)
+        });
+
+        if let Err(err) = result {
+            warn!("Failed to update milestones: {}", err);
         }
     }
 
@@ -606,18 +2388,57 @@ impl CocoGitto {
         hook_type: HookType,
         current_tag: Option<&HookVersion>,
         next_version: &HookVersion,
+        increment: Increment,
         hook_profile: Option<&str>,
-    ) -> Result<()> {
-        let settings = Settings::get(&self.repository)?;
+    ) -> Result<Vec<HookOutput>> {
+        let mut hooks = self.load_hooks(hook_type, Some(increment), hook_profile)?;
+        // Only computed for the hooks reading it, it never fails the bump
+        let snapshot = if hooks.iter().any(Hook::uses_snapshot_version) {
+            self.snapshot_version()
+                .map_err(|err| warn!("Could not compute the snapshot version: {}", err))
+                .ok()
+        } else {
+            None
+        };
+
+        for hook in hooks.iter_mut() {
+            hook.insert_versions(current_tag, next_version)?;
+            if let Some(snapshot) = &snapshot {
+                hook.insert_snapshot_version(snapshot);
+            }
+        }
+
+        Self::execute_hooks(hooks)
+    }
+
+    fn run_commit_hooks(&self, hook_type: HookType, message: &str) -> Result<Vec<HookOutput>> {
+        let mut hooks = self.load_hooks(hook_type, None, None)?;
+        for hook in hooks.iter_mut() {
+            hook.insert_message(message)?;
+        }
+
+        Self::execute_hooks(hooks)
+    }
+
+    fn load_hooks(
+        &self,
+        hook_type: HookType,
+        increment: Option<Increment>,
+        hook_profile: Option<&str>,
+    ) -> Result<Vec<Hook>> {
+        let settings = &self.settings;
+        let repo_dir = self.repository.get_repo_dir();
+        let branch = self.repository.get_branch_shorthand();
+        let should_run = |hook: &HookConfig| hook.should_run(branch.as_deref(), increment);
 
-        let hooks: Vec<Hook> = match hook_profile {
+        let hooks = match hook_profile {
             Some(profile) => settings
-                .get_profile_hook(profile, hook_type)
+                .get_profile_hook(profile, hook_type)?
                 .iter()
-                .map(|s| s.parse())
                 .enumerate()
-                .map(|(idx, result)| {
-                    result.context(format!(
+                .filter(|(_, hook)| should_run(hook))
+                .map(|(idx, hook)| {
+                    Hook::from_config(hook, repo_dir, settings.hook_shell).context(format!(
                         "Cannot parse bump profile {} hook at index {}",
                         profile, idx
                     ))
@@ -626,22 +2447,51 @@ impl CocoGitto {
             None => settings
                 .get_hooks(hook_type)
                 .iter()
-                .map(|s| s.parse())
                 .enumerate()
-                .map(|(idx, result)| result.context(format!("Cannot parse hook at index {}", idx)))
+                .filter(|(_, hook)| should_run(hook))
+                .map(|(idx, hook)| {
+                    Hook::from_config(hook, repo_dir, settings.hook_shell)
+                        .context(format!("Cannot parse hook at index {}", idx))
+                })
                 .try_collect()?,
         };
 
-        for mut hook in hooks {
-            hook.insert_versions(current_tag, next_version)?;
-            hook.run().context(hook.to_string())?;
+        Ok(hooks)
+    }
+
+    fn execute_hooks(hooks: Vec<Hook>) -> Result<Vec<HookOutput>> {
+        let mut outputs = vec![];
+        for hook in hooks {
+            match hook.run() {
+                Err(err) if hook.policy.allow_failure => {
+                    warn!("hook `{}` failed and was ignored: {}", hook, err);
+                    // Hooks that did not run to completion, such as timed out ones, have no output
+                    let output = match err.downcast::<FailedHook>() {
+                        Ok(failed) => failed.output,
+                        Err(_) => HookOutput {
+                            command: hook.to_string(),
+                            ..HookOutput::default()
+                        },
+                    };
+
+                    outputs.push(HookOutput {
+                        failed: true,
+                        ..output
+                    });
+                }
+                result => outputs.push(result.map_err(|err| hook.failure(err))?),
+            }
         }
 
-        Ok(())
+        Ok(outputs)
     }
 
-    fn prefix_version(version: String) -> String {
-        if let Some(prefix) = SETTINGS.tag_prefix.as_ref() {
+    fn hook_version(&self, tag: &str) -> HookVersion {
+        HookVersion::new(tag).with_tag_prefix(self.repository.tag_prefix_of(tag))
+    }
+
+    fn prefix_version(&self, version: String) -> String {
+        if let Some(prefix) = self.settings.tag_prefix.as_ref() {
             if !version.starts_with(prefix) {
                 format!("{}{}", prefix, version)
             } else {
@@ -652,3 +2502,9 @@ impl CocoGitto {
         }
     }
 }
+
+// The version without pre-release nor build metadata
+#[cfg(feature = "git")]
+fn release_of(version: &Version) -> Version {
+    Version::new(version.major, version.minor, version.patch)
+}
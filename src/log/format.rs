@@ -0,0 +1,103 @@
+use std::fmt;
+use std::str::FromStr;
+
+use colored::Colorize;
+use git2::Commit as Git2Commit;
+use serde_json::json;
+
+use crate::conventional::commit::{format_summary, Commit};
+use crate::conventional::error::ConventionalCommitError;
+use crate::git::revspec::RevspecPattern;
+
+/// How each commit is rendered by `cog log`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum LogFormat {
+    /// Summary, type, scope, author and date of each commit, along with parse errors
+    #[default]
+    Full,
+    /// Abbreviated oid and summary, one commit per line
+    Oneline,
+    /// One JSON object per line
+    Json,
+}
+
+/// The commits listed by [`CocoGitto::write_log`] and how they are rendered
+///
+/// [`CocoGitto::write_log`]: crate::CocoGitto::write_log
+#[derive(Debug, Default)]
+pub struct LogOptions {
+    /// Commit range to walk, all commits reachable from HEAD when `None`
+    pub pattern: Option<RevspecPattern>,
+    pub format: LogFormat,
+    /// Stop after this many commits have been written
+    pub max_count: Option<usize>,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "full" => Ok(LogFormat::Full),
+            "oneline" => Ok(LogFormat::Oneline),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("unknown log format '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            LogFormat::Full => "full",
+            LogFormat::Oneline => "oneline",
+            LogFormat::Json => "json",
+        };
+
+        f.write_str(format)
+    }
+}
+
+impl LogFormat {
+    pub(crate) fn render(
+        &self,
+        git_commit: &Git2Commit,
+        commit: &Result<Commit, Box<ConventionalCommitError>>,
+    ) -> String {
+        let oid = git_commit.id().to_string();
+        let summary = git_commit.summary().unwrap_or_default();
+
+        match (self, commit) {
+            (LogFormat::Full, Ok(commit)) => commit.get_log(),
+            (LogFormat::Full, Err(err)) => err.to_string(),
+            (LogFormat::Oneline, Ok(commit)) => {
+                format!("{} {}", oid[0..7].yellow(), format_summary(&commit.message))
+            }
+            (LogFormat::Oneline, Err(_)) => format!("{} {}", oid[0..7].yellow(), summary.red()),
+            (LogFormat::Json, Ok(commit)) => json!({
+                "oid": oid,
+                "type": commit.message.commit_type.as_ref(),
+                "scope": commit.message.scope,
+                "summary": commit.message.summary,
+                "body": commit.message.body,
+                "breaking_change": commit.message.is_breaking_change,
+                "footers": commit
+                    .message
+                    .footers
+                    .iter()
+                    .map(|footer| json!({ "token": footer.token, "content": footer.content }))
+                    .collect::<Vec<_>>(),
+                "author": commit.author,
+                "date": commit.date,
+            })
+            .to_string(),
+            (LogFormat::Json, Err(err)) => json!({
+                "oid": oid,
+                "summary": summary,
+                "author": git_commit.author().name().unwrap_or_default(),
+                "error": err.cause(),
+            })
+            .to_string(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDateTime, Utc};
+use git2::{ObjectType, Oid};
+use globset::GlobSet;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::conventional::changelog::release::Release;
+use crate::hook::HookOutput;
+
+/// A machine readable record of a release, written by `cog bump` when the
+/// `bump.manifest` setting is set
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub tag: String,
+    pub previous_tag: Option<String>,
+    pub date: NaiveDateTime,
+    /// The release commit
+    pub commit: String,
+    /// The commits of the release, from the previous tag or the first commit
+    pub range: ManifestRange,
+    /// The number of commits of each type
+    pub commit_counts: BTreeMap<String, usize>,
+    /// The authors of the release commits, as usernames when known or signatures
+    pub contributors: Vec<String>,
+    pub pre_bump_hooks: Vec<HookOutput>,
+    pub post_bump_hooks: Vec<HookOutput>,
+    pub artifacts: Vec<ManifestArtifact>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestRange {
+    pub from: String,
+    pub to: String,
+}
+
+/// A file built during the release
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ManifestArtifact {
+    /// Relative to the repository root
+    pub path: String,
+    pub size: u64,
+    /// The id of the file as a git blob, `git hash-object <path>`
+    pub git_blob_id: String,
+}
+
+impl ReleaseManifest {
+    /// The part of the record read from the release commits, versions, hooks and
+    /// artifacts are filled once the release is done
+    pub(crate) fn new(release: &Release, range: ManifestRange) -> Self {
+        let commit_counts = release
+            .commits
+            .iter()
+            .map(|commit| commit.commit.message.commit_type.as_ref().to_string())
+            .counts()
+            .into_iter()
+            .collect();
+
+        let contributors = release
+            .commits
+            .iter()
+            .map(|commit| {
+                commit
+                    .author_username
+                    .map(str::to_string)
+                    .unwrap_or_else(|| commit.commit.author.clone())
+            })
+            .sorted()
+            .dedup()
+            .collect();
+
+        ReleaseManifest {
+            version: String::new(),
+            tag: String::new(),
+            previous_tag: None,
+            date: Utc::now().naive_utc(),
+            commit: String::new(),
+            range,
+            commit_counts,
+            contributors,
+            pre_bump_hooks: vec![],
+            post_bump_hooks: vec![],
+            artifacts: vec![],
+        }
+    }
+
+    /// Write the record as pretty printed JSON
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("failed to write release manifest {}", path.display()))
+    }
+}
+
+/// The files under `root` matching `artifacts`, sorted by path. The `.git` directory is skipped.
+pub(crate) fn find_artifacts(root: &Path, artifacts: &GlobSet) -> Result<Vec<ManifestArtifact>> {
+    let mut found = vec![];
+    if artifacts.is_empty() {
+        return Ok(found);
+    }
+
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                if entry.file_name() != ".git" {
+                    dirs.push(path);
+                }
+
+                continue;
+            }
+
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if artifacts.is_match(&relative) {
+                found.push(ManifestArtifact {
+                    path: relative,
+                    size: entry.metadata()?.len(),
+                    git_blob_id: Oid::hash_file(ObjectType::Blob, &path)?.to_string(),
+                });
+            }
+        }
+    }
+
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(found)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+    use globset::{Glob, GlobSetBuilder};
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    use crate::manifest::{find_artifacts, ManifestArtifact};
+
+    #[sealed_test]
+    fn should_find_artifacts() -> Result<()> {
+        // Arrange
+        fs::create_dir_all("dist/nested")?;
+        fs::write("dist/app.tar.gz", "app")?;
+        fs::write("dist/nested/lib.tar.gz", "lib")?;
+        fs::write("dist/notes.txt", "notes")?;
+        let artifacts = GlobSetBuilder::new()
+            .add(Glob::new("dist/**/*.tar.gz")?)
+            .build()?;
+
+        // Act
+        let found = find_artifacts(&std::env::current_dir()?, &artifacts)?;
+
+        // Assert
+        assert_that!(found).is_equal_to(vec![
+            ManifestArtifact {
+                path: "dist/app.tar.gz".to_string(),
+                size: 3,
+                git_blob_id: "7a0b7f0078361bdeeb90e0b1b96cd55a4c0d14d4".to_string(),
+            },
+            ManifestArtifact {
+                path: "dist/nested/lib.tar.gz".to_string(),
+                size: 3,
+                git_blob_id: "7951405f85a569efbacc12fccfee529ef1866602".to_string(),
+            },
+        ]);
+        Ok(())
+    }
+}
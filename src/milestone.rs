@@ -0,0 +1,259 @@
+use std::env;
+
+use anyhow::{anyhow, Context, Result};
+use semver::Version;
+use serde_json::{json, Value};
+
+use crate::conventional::changelog::template::RemoteContext;
+use crate::conventional::version::{Increment, VersionIncrement};
+use crate::curl::CurlRequest;
+use crate::reporter::{info, warn};
+use crate::settings::{MilestoneSettings, RemoteProvider};
+
+/// A milestone of the remote repository
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Milestone {
+    /// The GitHub milestone number or the GitLab milestone id
+    pub id: u64,
+    pub title: String,
+}
+
+/// The milestone API of the changelog remote repository
+#[derive(Debug)]
+pub struct Milestones<'a> {
+    settings: &'a MilestoneSettings,
+    provider: RemoteProvider,
+    /// The milestones url of the repository
+    url: String,
+}
+
+impl<'a> Milestones<'a> {
+    pub fn new(settings: &'a MilestoneSettings, remote: &RemoteContext) -> Result<Self> {
+        let provider = remote.provider();
+        let url = match provider {
+            RemoteProvider::Github => {
+                let api_url = settings
+                    .api_url
+                    .as_deref()
+                    .unwrap_or("https://api.github.com");
+                format!(
+                    "{}/repos/{}/milestones",
+                    api_url.trim_end_matches('/'),
+                    remote.project()
+                )
+            }
+            RemoteProvider::Gitlab => {
+                let api_url = match &settings.api_url {
+                    Some(api_url) => api_url.trim_end_matches('/').to_string(),
+                    None => format!("https://{}/api/v4", remote.remote()),
+                };
+                format!(
+                    "{}/projects/{}/milestones",
+                    api_url,
+                    remote.project().replace('/', "%2F")
+                )
+            }
+            RemoteProvider::Bitbucket => {
+                return Err(anyhow!("Bitbucket repositories have no milestones"));
+            }
+        };
+
+        Ok(Milestones {
+            settings,
+            provider,
+            url,
+        })
+    }
+
+    /// The milestone titles of the released version and of the next one
+    pub fn titles(&self, version: &Version, tag: &str) -> Result<(String, String)> {
+        let increment = match self.settings.next {
+            Increment::Major => VersionIncrement::Major,
+            Increment::Minor => VersionIncrement::Minor,
+            Increment::Patch => VersionIncrement::Patch,
+        };
+        let next = increment.bump_from_commits(version, &[])?;
+        let next_tag = tag.replacen(&version.to_string(), &next.to_string(), 1);
+
+        Ok((self.title(version, tag)?, self.title(&next, &next_tag)?))
+    }
+
+    fn title(&self, version: &Version, tag: &str) -> Result<String> {
+        let mut context = tera::Context::new();
+        context.insert("version", &version.to_string());
+        context.insert("tag", tag);
+        tera::Tera::one_off(&self.settings.title, &context, false)
+            .context("failed to render the milestone title")
+    }
+
+    /// Close the milestone of the released version and create the next one, as configured
+    pub fn release(&self, version: &Version, tag: &str) -> Result<()> {
+        let (title, next_title) = self.titles(version, tag)?;
+        let open = self.open()?;
+
+        if self.settings.close {
+            match open.iter().find(|milestone| milestone.title == title) {
+                Some(milestone) => {
+                    self.close(milestone)?;
+                    info!("Closed milestone {}", title);
+                }
+                None => warn!("No open milestone {} to close", title),
+            }
+        }
+
+        if self.settings.create_next && !open.iter().any(|milestone| milestone.title == next_title)
+        {
+            self.create(&next_title)?;
+            info!("Created milestone {}", next_title);
+        }
+
+        Ok(())
+    }
+
+    fn open(&self) -> Result<Vec<Milestone>> {
+        let (state, id) = match self.provider {
+            RemoteProvider::Gitlab => ("active", "id"),
+            _ => ("open", "number"),
+        };
+
+        let url = format!("{}?state={}&per_page=100", self.url, state);
+        let milestones = self.request("GET", &url, None)?;
+        let milestones = milestones
+            .as_array()
+            .ok_or_else(|| anyhow!("unexpected milestones response from {}", url))?;
+
+        milestones
+            .iter()
+            .map(|milestone| {
+                Ok(Milestone {
+                    id: milestone[id]
+                        .as_u64()
+                        .ok_or_else(|| anyhow!("milestone without {}", id))?,
+                    title: milestone["title"]
+                        .as_str()
+                        .ok_or_else(|| anyhow!("milestone without title"))?
+                        .to_string(),
+                })
+            })
+            .collect()
+    }
+
+    fn close(&self, milestone: &Milestone) -> Result<()> {
+        let url = format!("{}/{}", self.url, milestone.id);
+        match self.provider {
+            RemoteProvider::Gitlab => {
+                self.request("PUT", &url, Some(json!({ "state_event": "close" })))?
+            }
+            _ => self.request("PATCH", &url, Some(json!({ "state": "closed" })))?,
+        };
+
+        Ok(())
+    }
+
+    fn create(&self, title: &str) -> Result<()> {
+        self.request("POST", &self.url, Some(json!({ "title": title })))?;
+        Ok(())
+    }
+
+    fn token_env(&self) -> &str {
+        match (&self.settings.token_env, self.provider) {
+            (Some(token_env), _) => token_env,
+            (None, RemoteProvider::Gitlab) => "GITLAB_TOKEN",
+            (None, _) => "GITHUB_TOKEN",
+        }
+    }
+
+    // Send a request using curl and parse the JSON response
+    fn request(&self, method: &str, url: &str, body: Option<Value>) -> Result<Value> {
+        let token_env = self.token_env();
+        let token = env::var(token_env).with_context(|| format!("{} is not set", token_env))?;
+        let auth = match self.provider {
+            RemoteProvider::Gitlab => format!("PRIVATE-TOKEN: {}", token),
+            _ => format!("Authorization: Bearer {}", token),
+        };
+
+        let mut curl = CurlRequest::new(method, url)
+            .header(auth)
+            .header("Content-Type: application/json");
+
+        if let Some(body) = body {
+            curl = curl.body(body.to_string());
+        }
+
+        let stdout = curl.send()?;
+        if stdout.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Value::Null);
+        }
+
+        serde_json::from_slice(&stdout)
+            .with_context(|| format!("invalid JSON response from {}", url))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::changelog::template::RemoteContext;
+    use crate::milestone::Milestones;
+    use crate::settings::{MilestoneSettings, RemoteProvider};
+
+    use anyhow::Result;
+    use semver::Version;
+    use speculoos::prelude::*;
+
+    fn remote(host: &str) -> RemoteContext {
+        RemoteContext::try_new(
+            Some(host.to_string()),
+            Some("cog".to_string()),
+            Some("cocogitto".to_string()),
+        )
+        .expect("remote context")
+    }
+
+    #[test]
+    fn should_build_github_milestones_url() -> Result<()> {
+        let settings = MilestoneSettings::default();
+
+        let milestones = Milestones::new(&settings, &remote("github.com"))?;
+
+        assert_that!(milestones.url.as_str())
+            .is_equal_to("https://api.github.com/repos/cocogitto/cog/milestones");
+        Ok(())
+    }
+
+    #[test]
+    fn should_build_gitlab_milestones_url() -> Result<()> {
+        let settings = MilestoneSettings::default();
+
+        let milestones = Milestones::new(&settings, &remote("gitlab.example.org"))?;
+
+        assert_that!(milestones.url.as_str())
+            .is_equal_to("https://gitlab.example.org/api/v4/projects/cocogitto%2Fcog/milestones");
+        Ok(())
+    }
+
+    #[test]
+    fn bitbucket_has_no_milestones() {
+        let settings = MilestoneSettings::default();
+        let remote = remote("example.org").with_provider(RemoteProvider::Bitbucket);
+
+        assert_that!(Milestones::new(&settings, &remote)).is_err();
+    }
+
+    #[test]
+    fn should_render_milestone_titles() -> Result<()> {
+        // Arrange
+        let settings = MilestoneSettings {
+            title: "Release {{ tag }}".to_string(),
+            ..MilestoneSettings::default()
+        };
+        let milestones = Milestones::new(&settings, &remote("github.com"))?;
+
+        // Act
+        let titles = milestones.titles(&Version::new(1, 4, 2), "v1.4.2")?;
+
+        // Assert
+        assert_that!(titles)
+            .is_equal_to(("Release v1.4.2".to_string(), "Release v1.5.0".to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::curl::CurlRequest;
+use crate::settings::{Webhook, WebhookKind};
+
+/// The release announced to webhooks after a bump
+#[derive(Debug, Serialize)]
+pub struct ReleaseNotification {
+    pub version: String,
+    pub previous_version: Option<String>,
+    pub changelog: String,
+    pub compare_url: Option<String>,
+}
+
+impl ReleaseNotification {
+    /// Render the request body sent to the given webhook
+    pub fn payload(&self, webhook: &Webhook) -> Result<String> {
+        if let Some(template) = &webhook.template {
+            let context = tera::Context::from_serialize(self)?;
+            return tera::Tera::one_off(template, &context, false)
+                .context("failed to render webhook template");
+        }
+
+        let payload = match webhook.kind {
+            WebhookKind::Generic => serde_json::to_value(self)?,
+            WebhookKind::Slack => json!({ "text": self.message() }),
+            WebhookKind::Discord => json!({ "content": self.message() }),
+        };
+
+        Ok(payload.to_string())
+    }
+
+    fn message(&self) -> String {
+        let mut message = format!("Released {}", self.version);
+        if let Some(compare_url) = &self.compare_url {
+            message.push_str(&format!(" ({})", compare_url));
+        }
+
+        format!("{}\n\n{}", message, self.changelog.trim())
+    }
+
+    /// Post the notification payload to the webhook url using curl
+    pub fn send(&self, webhook: &Webhook) -> Result<()> {
+        let payload = self.payload(webhook)?;
+        CurlRequest::new("POST", &webhook.url)
+            .header("Content-Type: application/json")
+            .body(payload)
+            .send()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::notify::ReleaseNotification;
+    use crate::settings::{Webhook, WebhookKind};
+
+    use anyhow::Result;
+    use speculoos::prelude::*;
+
+    fn notification() -> ReleaseNotification {
+        ReleaseNotification {
+            version: "1.1.0".to_string(),
+            previous_version: Some("1.0.0".to_string()),
+            changelog: "#### Features\n- \"quoted\" feature\n".to_string(),
+            compare_url: Some(
+                "https://github.com/cocogitto/cocogitto/compare/1.0.0..1.1.0".to_string(),
+            ),
+        }
+    }
+
+    fn webhook(kind: WebhookKind, template: Option<&str>) -> Webhook {
+        Webhook {
+            url: "https://example.org/hook".to_string(),
+            kind,
+            template: template.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn generic_payload() -> Result<()> {
+        let payload = notification().payload(&webhook(WebhookKind::Generic, None))?;
+
+        let payload: serde_json::Value = serde_json::from_str(&payload)?;
+        assert_that!(payload["version"]).is_equal_to(serde_json::json!("1.1.0"));
+        assert_that!(payload["previous_version"]).is_equal_to(serde_json::json!("1.0.0"));
+        Ok(())
+    }
+
+    #[test]
+    fn slack_payload() -> Result<()> {
+        let payload = notification().payload(&webhook(WebhookKind::Slack, None))?;
+
+        assert_that!(payload.as_str()).is_equal_to(
+            r#"{"text":"Released 1.1.0 (https://github.com/cocogitto/cocogitto/compare/1.0.0..1.1.0)\n\n#### Features\n- \"quoted\" feature"}"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn discord_payload() -> Result<()> {
+        let payload = notification().payload(&webhook(WebhookKind::Discord, None))?;
+
+        assert_that!(payload.as_str()).starts_with(r#"{"content":"Released 1.1.0"#);
+        Ok(())
+    }
+
+    #[test]
+    fn templated_payload() -> Result<()> {
+        let template =
+            r#"{"release": {{ version | json_encode() }}, "from": "{{ previous_version }}"}"#;
+        let payload = notification().payload(&webhook(WebhookKind::Slack, Some(template)))?;
+
+        assert_that!(payload.as_str()).is_equal_to(r#"{"release": "1.1.0", "from": "1.0.0"}"#);
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::settings::{HookConfig, HookShell, PublishProvider};
+
+impl fmt::Display for PublishProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublishProvider::Cargo { .. } => f.write_str("cargo"),
+            PublishProvider::Npm { .. } => f.write_str("npm"),
+            PublishProvider::Container { image, .. } => write!(f, "container {}", image),
+        }
+    }
+}
+
+impl PublishProvider {
+    fn program(&self) -> &str {
+        match self {
+            PublishProvider::Cargo { .. } => "cargo",
+            PublishProvider::Npm { .. } => "npm",
+            PublishProvider::Container { engine, .. } => engine,
+        }
+    }
+
+    /// Whether the provider can check a publication without publishing, other
+    /// providers are skipped on dry runs
+    pub fn supports_dry_run(&self) -> bool {
+        !matches!(self, PublishProvider::Container { .. })
+    }
+
+    /// The commands publishing `version`, tagged `tag`, run as hooks from the repository root.
+    /// They run without shell whatever `hook_shell` is, their arguments are never reinterpreted.
+    pub(crate) fn commands(&self, version: &str, tag: &str, dry_run: bool) -> Vec<HookConfig> {
+        let command = |program: &str, args: Vec<&str>, cwd: Option<&Path>| {
+            let words = std::iter::once(program).chain(args);
+            HookConfig {
+                command: shell_words::join(words),
+                shell: Some(HookShell::None),
+                name: Some(self.to_string()),
+                // Relative to the repository root, like hooks
+                cwd: Some(cwd.unwrap_or(Path::new(".")).to_path_buf()),
+                ..HookConfig::default()
+            }
+        };
+
+        match self {
+            PublishProvider::Cargo { args, cwd } | PublishProvider::Npm { args, cwd } => {
+                let mut arguments = vec!["publish"];
+                if dry_run {
+                    arguments.push("--dry-run");
+                }
+
+                arguments.extend(args.iter().map(String::as_str));
+                vec![command(self.program(), arguments, cwd.as_deref())]
+            }
+            PublishProvider::Container {
+                image,
+                source,
+                tags,
+                engine,
+            } => {
+                let source = source.as_deref().unwrap_or(image);
+                tags.iter()
+                    .map(|image_tag| {
+                        let image_tag = image_tag
+                            .replace("{{version}}", version)
+                            .replace("{{tag}}", tag);
+                        format!("{}:{}", image, image_tag)
+                    })
+                    .flat_map(|target| {
+                        [
+                            command(engine, vec!["tag", source, &target], None),
+                            command(engine, vec!["push", &target], None),
+                        ]
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use speculoos::prelude::*;
+
+    use crate::settings::{HookShell, PublishProvider};
+
+    fn commands(provider: &PublishProvider, dry_run: bool) -> Vec<String> {
+        provider
+            .commands("1.2.0", "v1.2.0", dry_run)
+            .into_iter()
+            .map(|hook| hook.command)
+            .collect()
+    }
+
+    #[test]
+    fn cargo_publishes_with_dry_run() {
+        let provider = PublishProvider::Cargo {
+            args: vec!["--package".to_string(), "cog".to_string()],
+            cwd: None,
+        };
+
+        assert_that!(commands(&provider, false))
+            .is_equal_to(vec!["cargo publish --package cog".to_string()]);
+        assert_that!(commands(&provider, true))
+            .is_equal_to(vec!["cargo publish --dry-run --package cog".to_string()]);
+    }
+
+    #[test]
+    fn commands_run_without_shell() {
+        let provider = PublishProvider::Npm {
+            args: vec!["--tag".to_string(), "next & echo injected".to_string()],
+            cwd: None,
+        };
+
+        let hooks = provider.commands("1.2.0", "v1.2.0", false);
+
+        assert_that!(hooks[0].shell).is_equal_to(Some(HookShell::None));
+        assert_that!(shell_words::split(&hooks[0].command).unwrap()).is_equal_to(vec![
+            "npm".to_string(),
+            "publish".to_string(),
+            "--tag".to_string(),
+            "next & echo injected".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn container_tags_and_pushes_each_tag() {
+        let provider = PublishProvider::Container {
+            image: "ghcr.io/cocogitto/cog".to_string(),
+            source: Some("cog:build".to_string()),
+            tags: vec!["{{version}}".to_string(), "latest".to_string()],
+            engine: "podman".to_string(),
+        };
+
+        assert_that!(provider.supports_dry_run()).is_false();
+        assert_that!(commands(&provider, false)).is_equal_to(vec![
+            "podman tag cog:build ghcr.io/cocogitto/cog:1.2.0".to_string(),
+            "podman push ghcr.io/cocogitto/cog:1.2.0".to_string(),
+            "podman tag cog:build ghcr.io/cocogitto/cog:latest".to_string(),
+            "podman push ghcr.io/cocogitto/cog:latest".to_string(),
+        ]);
+    }
+}
@@ -0,0 +1,31 @@
+use serde_json::json;
+
+use crate::report::CheckedCommit;
+use crate::settings::Severity;
+
+/// A GitLab Code Quality report with an issue per non compliant commit. Commits are not files,
+/// issues are located on the `.git` directory. Commits with warnings are minor issues.
+pub(crate) fn report(commits: &[CheckedCommit]) -> String {
+    let issues: Vec<_> = commits
+        .iter()
+        .filter_map(|commit| {
+            let error = commit.error.as_ref()?;
+            let severity = match commit.severity {
+                Some(Severity::Warning) => "minor",
+                _ => "major",
+            };
+            Some(json!({
+                "description": format!("{}: {}", commit.summary, error),
+                "check_name": "conventional-commit",
+                "fingerprint": commit.oid,
+                "severity": severity,
+                "location": {
+                    "path": ".git",
+                    "lines": { "begin": 1 },
+                },
+            }))
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&issues).expect("issues are serializable")
+}
@@ -0,0 +1,115 @@
+use std::fmt::Write as FmtWrite;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+
+use crate::error::CogCheckReport;
+use crate::BumpOutcome;
+
+/// One `::error` workflow command per non compliant commit, and one `::warning` per commit with
+/// warnings. Printed on stdout they are displayed as annotations of the workflow run.
+pub fn annotations(report: &CogCheckReport) -> String {
+    let errors = report.errors.iter().map(|err| ("error", err));
+    let warnings = report.warnings.iter().map(|err| ("warning", err));
+
+    let mut annotations = String::new();
+    for (level, err) in errors.chain(warnings) {
+        let title = match err.commit() {
+            Some((oid, summary)) => format!("Non compliant commit {}: {}", short(oid), summary),
+            None => "Non compliant commit".to_string(),
+        };
+
+        let _ = writeln!(
+            annotations,
+            "::{} title={}::{}",
+            level,
+            escape_property(&title),
+            escape_data(&err.cause())
+        );
+    }
+
+    annotations
+}
+
+/// Append the `version`, `previous_version` and `changelog` step outputs of a bump
+/// to the `$GITHUB_OUTPUT` file, nothing is written outside of GitHub Actions
+pub fn write_bump_outputs(outcome: &BumpOutcome) -> io::Result<()> {
+    let Some(path) = std::env::var_os("GITHUB_OUTPUT") else {
+        return Ok(());
+    };
+
+    let mut outputs = format!(
+        "version={}\nprevious_version={}\n",
+        outcome.new_tag,
+        outcome.previous_tag.as_deref().unwrap_or_default()
+    );
+
+    if let Some(changelog) = &outcome.changelog {
+        // Multiline values are enclosed in a delimiter that must not appear in the value
+        let mut delimiter = format!("cog_changelog_{}", std::process::id());
+        while changelog.contains(&delimiter) {
+            delimiter.push('_');
+        }
+
+        let _ = write!(
+            outputs,
+            "changelog<<{delimiter}\n{}\n{delimiter}\n",
+            changelog.trim_end(),
+            delimiter = delimiter
+        );
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(outputs.as_bytes())
+}
+
+fn short(oid: &str) -> &str {
+    oid.get(..7).unwrap_or(oid)
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::conventional::error::ConventionalCommitError;
+    use crate::error::CogCheckReport;
+    use crate::git::oid::OidOf;
+    use crate::report::github::annotations;
+    use git2::Oid;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn annotate_non_compliant_commits() {
+        // Arrange
+        let report = CogCheckReport {
+            from: OidOf::Other(Oid::zero()),
+            errors: vec![ConventionalCommitError::CommitTypeNotAllowed {
+                oid: "1234567890abcdef".to_string(),
+                summary: "wip: 50%, almost done".to_string(),
+                commit_type: "wip".to_string(),
+                author: "Tom".to_string(),
+            }],
+            warnings: vec![],
+        };
+
+        // Act
+        let annotations = annotations(&report);
+
+        // Assert
+        assert_that!(annotations).is_equal_to(
+            "::error title=Non compliant commit 1234567%3A wip%3A 50%25%2C almost done::Commit type `wip` not allowed\n"
+                .to_string(),
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt::Write;
+
+use crate::report::CheckedCommit;
+use crate::settings::Severity;
+
+/// A JUnit test suite where each commit is a test case, failed when it is not compliant.
+/// The warnings of passed test cases are written to their output.
+pub(crate) fn report(commits: &[CheckedCommit]) -> String {
+    let failures = commits
+        .iter()
+        .filter(|commit| commit.severity == Some(Severity::Error))
+        .count();
+    let mut report = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    let _ = writeln!(
+        report,
+        "  <testsuite name=\"cog check\" tests=\"{}\" failures=\"{}\">",
+        commits.len(),
+        failures
+    );
+
+    for commit in commits {
+        let name = escape(&format!(
+            "{} {}",
+            commit.oid.get(..7).unwrap_or(&commit.oid),
+            commit.summary
+        ));
+
+        match (&commit.error, commit.severity) {
+            (None, _) => {
+                let _ = writeln!(
+                    report,
+                    "    <testcase classname=\"cog check\" name=\"{}\"/>",
+                    name
+                );
+            }
+            (Some(warning), Some(Severity::Warning)) => {
+                let _ = writeln!(
+                    report,
+                    "    <testcase classname=\"cog check\" name=\"{}\">\n      <system-out>{}</system-out>\n    </testcase>",
+                    name,
+                    escape(warning)
+                );
+            }
+            (Some(error), _) => {
+                let _ = writeln!(
+                    report,
+                    "    <testcase classname=\"cog check\" name=\"{}\">\n      <failure message=\"{}\"/>\n    </testcase>",
+                    name,
+                    escape(error)
+                );
+            }
+        }
+    }
+
+    report.push_str("  </testsuite>\n</testsuites>\n");
+    report
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::report::junit::report;
+    use crate::report::CheckedCommit;
+    use crate::settings::Severity;
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn junit_report_with_a_test_case_per_commit() {
+        // Arrange
+        let commits = [
+            CheckedCommit {
+                oid: "1234567890".to_string(),
+                summary: "feat: <b>bold</b> feature".to_string(),
+                error: None,
+                severity: None,
+            },
+            CheckedCommit {
+                oid: "abcdef1234".to_string(),
+                summary: "oops".to_string(),
+                error: Some("Missing commit type separator `:`".to_string()),
+                severity: Some(Severity::Error),
+            },
+            CheckedCommit {
+                oid: "fedcba9876".to_string(),
+                summary: "feat: recieve refunds".to_string(),
+                error: Some("Misspelled word `recieve`".to_string()),
+                severity: Some(Severity::Warning),
+            },
+        ];
+
+        // Act
+        let report = report(&commits);
+
+        // Assert
+        assert_that!(report.as_str()).is_equal_to(indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites>
+              <testsuite name="cog check" tests="3" failures="1">
+                <testcase classname="cog check" name="1234567 feat: &lt;b&gt;bold&lt;/b&gt; feature"/>
+                <testcase classname="cog check" name="abcdef1 oops">
+                  <failure message="Missing commit type separator `:`"/>
+                </testcase>
+                <testcase classname="cog check" name="fedcba9 feat: recieve refunds">
+                  <system-out>Misspelled word `recieve`</system-out>
+                </testcase>
+              </testsuite>
+            </testsuites>
+        "#});
+    }
+}
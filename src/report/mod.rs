@@ -0,0 +1,100 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::settings::Severity;
+
+pub mod codequality;
+pub mod github;
+pub mod junit;
+
+/// A commit checked by [`CocoGitto::check_commits`]
+///
+/// [`CocoGitto::check_commits`]: crate::CocoGitto::check_commits
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct CheckedCommit {
+    pub oid: String,
+    pub summary: String,
+    /// Why the commit is not compliant
+    pub error: Option<String>,
+    /// Whether the commit fails the check or only has warnings, `None` for compliant commits
+    pub severity: Option<Severity>,
+}
+
+/// Reports of `cog check --output`, for CI services test and code quality widgets
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CheckReportFormat {
+    /// A JUnit XML test suite with a test case per commit
+    Junit,
+    /// A GitLab Code Quality JSON report with an issue per non compliant commit
+    CodeQuality,
+}
+
+impl CheckReportFormat {
+    pub fn render(&self, commits: &[CheckedCommit]) -> String {
+        match self {
+            CheckReportFormat::Junit => junit::report(commits),
+            CheckReportFormat::CodeQuality => codequality::report(commits),
+        }
+    }
+}
+
+impl FromStr for CheckReportFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "junit" => Ok(CheckReportFormat::Junit),
+            "codequality" => Ok(CheckReportFormat::CodeQuality),
+            other => Err(format!("unknown check report format '{}'", other)),
+        }
+    }
+}
+
+/// How command results are reported, selected with `--output-format`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum OutputFormat {
+    /// Human readable logs
+    #[default]
+    Text,
+    /// GitHub Actions workflow annotations and step outputs
+    Github,
+    /// A JSON document on stdout, logs are still written to stderr
+    Json,
+}
+
+impl OutputFormat {
+    /// The format matching the CI service cog runs on, `Text` outside of CI
+    pub fn detect() -> Self {
+        match std::env::var("GITHUB_ACTIONS") {
+            Ok(value) if value == "true" => OutputFormat::Github,
+            _ => OutputFormat::Text,
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(OutputFormat::Text),
+            "github" => Ok(OutputFormat::Github),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!("unknown output format '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Github => "github",
+            OutputFormat::Json => "json",
+        };
+
+        f.write_str(format)
+    }
+}
@@ -0,0 +1,272 @@
+//! Messages and progress emitted by cocogitto while running commands.
+//!
+//! Messages are forwarded to the [`log`] crate unless a [`Reporter`] is installed
+//! with [`set_reporter`], library consumers can use it to receive plain text messages
+//! and render them, as well as the progress of long operations, their own way.
+
+use std::fmt;
+use std::io::IsTerminal;
+use std::sync::RwLock;
+
+static REPORTER: RwLock<Option<Box<dyn Reporter>>> = RwLock::new(None);
+
+/// The severity of a reported message
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+/// Operations walking a whole commit history, reported with [`Reporter::progress`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Task {
+    /// Parsing the commits checked by `cog check` or `cog edit`
+    CheckCommits,
+    /// Parsing the commits of every release of a changelog
+    ReadReleases,
+    /// Rewording non compliant commits with `cog edit`
+    Rebase,
+}
+
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let task = match self {
+            Task::CheckCommits => "Checking commits",
+            Task::ReadReleases => "Reading releases",
+            Task::Rebase => "Rebasing commits",
+        };
+
+        write!(f, "{}", task)
+    }
+}
+
+/// Receives the messages emitted by cocogitto
+pub trait Reporter: Send + Sync {
+    fn report(&self, level: Level, message: &str);
+
+    /// `done` items of `task` were processed, out of `total` when known upfront
+    fn progress(&self, _task: Task, _done: u64, _total: Option<u64>) {}
+
+    /// `task` is over, called once even when it failed midway
+    fn finish(&self, _task: Task) {}
+
+    /// Whether messages should contain ANSI color codes
+    fn colors(&self) -> bool {
+        false
+    }
+}
+
+/// The default reporter, forwarding messages to the [`log`] crate
+#[derive(Debug, Default, Copy, Clone)]
+pub struct LogReporter;
+
+impl Reporter for LogReporter {
+    fn report(&self, level: Level, message: &str) {
+        let level = match level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+        };
+
+        log::log!(level, "{}", message);
+    }
+
+    fn colors(&self) -> bool {
+        detect_colors()
+    }
+}
+
+/// Send all subsequent messages to `reporter`, colors are enabled according to [`Reporter::colors`]
+pub fn set_reporter(reporter: impl Reporter + 'static) {
+    set_colors(reporter.colors());
+    let mut current = REPORTER.write().unwrap_or_else(|err| err.into_inner());
+    *current = Some(Box::new(reporter));
+}
+
+/// Enable or disable ANSI colors in messages and command output
+pub fn set_colors(enabled: bool) {
+    colored::control::set_override(enabled);
+}
+
+/// Whether the terminal cog runs in should get colors: `NO_COLOR` disables them,
+/// `CLICOLOR_FORCE` forces them, otherwise both stdout and stderr must be terminals
+pub fn detect_colors() -> bool {
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+
+    if var("NO_COLOR").is_some() {
+        false
+    } else if var("CLICOLOR_FORCE").is_some_and(|value| value != "0") {
+        true
+    } else {
+        std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
+    }
+}
+
+#[doc(hidden)]
+pub fn report(level: Level, args: fmt::Arguments) {
+    let message = args.to_string();
+    with_reporter(|reporter| reporter.report(level, &message));
+}
+
+fn with_reporter(f: impl FnOnce(&dyn Reporter)) {
+    let reporter = REPORTER.read().unwrap_or_else(|err| err.into_inner());
+    match reporter.as_ref() {
+        Some(reporter) => f(reporter.as_ref()),
+        None => f(&LogReporter),
+    }
+}
+
+/// Reports the progress of a task, finished when dropped
+pub(crate) struct Progress {
+    task: Task,
+    done: u64,
+    total: Option<u64>,
+}
+
+impl Progress {
+    pub(crate) fn start(task: Task, total: Option<usize>) -> Self {
+        let total = total.map(|total| total as u64);
+        with_reporter(|reporter| reporter.progress(task, 0, total));
+        Progress {
+            task,
+            done: 0,
+            total,
+        }
+    }
+
+    pub(crate) fn tick(&mut self) {
+        self.done += 1;
+        with_reporter(|reporter| reporter.progress(self.task, self.done, self.total));
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        with_reporter(|reporter| reporter.finish(self.task));
+    }
+}
+
+macro_rules! report_error {
+    ($($arg:tt)+) => {
+        $crate::reporter::report($crate::reporter::Level::Error, format_args!($($arg)+))
+    };
+}
+
+macro_rules! report_warn {
+    ($($arg:tt)+) => {
+        $crate::reporter::report($crate::reporter::Level::Warn, format_args!($($arg)+))
+    };
+}
+
+macro_rules! report_info {
+    ($($arg:tt)+) => {
+        $crate::reporter::report($crate::reporter::Level::Info, format_args!($($arg)+))
+    };
+}
+
+macro_rules! report_debug {
+    ($($arg:tt)+) => {
+        $crate::reporter::report($crate::reporter::Level::Debug, format_args!($($arg)+))
+    };
+}
+
+pub(crate) use report_debug as debug;
+pub(crate) use report_error as error;
+pub(crate) use report_info as info;
+pub(crate) use report_warn as warn;
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use colored::Colorize;
+    use sealed_test::prelude::*;
+    use speculoos::prelude::*;
+
+    use crate::reporter::{self, Level, Progress, Reporter, Task};
+
+    #[derive(Default, Clone)]
+    struct Collect(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl Reporter for Collect {
+        fn report(&self, level: Level, message: &str) {
+            self.0.lock().unwrap().push((level, message.to_string()));
+        }
+
+        fn progress(&self, task: Task, done: u64, total: Option<u64>) {
+            let message = format!("{} {}/{}", task, done, total.unwrap_or_default());
+            self.0.lock().unwrap().push((Level::Debug, message));
+        }
+
+        fn finish(&self, task: Task) {
+            let message = format!("{} done", task);
+            self.0.lock().unwrap().push((Level::Debug, message));
+        }
+    }
+
+    #[sealed_test]
+    fn custom_reporter_receives_plain_messages() {
+        // Arrange
+        let collect = Collect::default();
+        reporter::set_reporter(collect.clone());
+
+        // Act
+        reporter::info!("Found feature commit {}", "abc1234".blue());
+        reporter::warn!("careful");
+
+        // Assert
+        assert_that!(*collect.0.lock().unwrap()).is_equal_to(vec![
+            (Level::Info, "Found feature commit abc1234".to_string()),
+            (Level::Warn, "careful".to_string()),
+        ]);
+    }
+
+    #[sealed_test]
+    fn no_color_wins_over_clicolor_force() {
+        std::env::set_var("NO_COLOR", "1");
+        std::env::set_var("CLICOLOR_FORCE", "1");
+
+        assert_that!(reporter::detect_colors()).is_false();
+    }
+
+    #[sealed_test]
+    fn clicolor_force_enables_colors() {
+        std::env::remove_var("NO_COLOR");
+        std::env::set_var("CLICOLOR_FORCE", "1");
+
+        assert_that!(reporter::detect_colors()).is_true();
+    }
+
+    #[sealed_test]
+    fn progress_is_finished_on_drop() {
+        // Arrange
+        let collect = Collect::default();
+        reporter::set_reporter(collect.clone());
+
+        // Act
+        {
+            let mut progress = Progress::start(Task::CheckCommits, Some(2));
+            progress.tick();
+            progress.tick();
+        }
+
+        // Assert
+        let messages: Vec<String> = collect
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, message)| message.clone())
+            .collect();
+
+        assert_that!(messages).is_equal_to(vec![
+            "Checking commits 0/2".to_string(),
+            "Checking commits 1/2".to_string(),
+            "Checking commits 2/2".to_string(),
+            "Checking commits done".to_string(),
+        ]);
+    }
+}
@@ -0,0 +1,371 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+use crate::conventional::commit::CommitConfig;
+use crate::settings::Settings;
+
+/// Release tools whose configuration `cog init --from <tool>` converts to cog settings
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ImportSource {
+    /// `cliff.toml`: tag pattern and commit parser groups
+    GitCliff,
+    /// `.versionrc` or `.versionrc.json`: tag prefix and commit type sections
+    StandardVersion,
+    /// `.releaserc` or `.releaserc.json`: tag format and the conventional commits preset types,
+    /// YAML configurations are not supported
+    SemanticRelease,
+}
+
+// The tag prefix and changelog titles of the commit types found in a foreign configuration
+#[derive(Debug, Default, Eq, PartialEq)]
+struct Imported {
+    tag_prefix: Option<String>,
+    commit_types: Vec<(String, String)>,
+}
+
+impl ImportSource {
+    pub const ALL: [ImportSource; 3] = [
+        ImportSource::GitCliff,
+        ImportSource::StandardVersion,
+        ImportSource::SemanticRelease,
+    ];
+
+    fn config_files(&self) -> &'static [&'static str] {
+        match self {
+            ImportSource::GitCliff => &["cliff.toml"],
+            ImportSource::StandardVersion => &[".versionrc", ".versionrc.json"],
+            ImportSource::SemanticRelease => &[".releaserc", ".releaserc.json"],
+        }
+    }
+
+    /// Override the tag prefix and commit types of `settings` with the ones configured for
+    /// this tool in `dir`. Types hidden from the changelog or skipped are left out.
+    pub fn import(&self, dir: &Path, settings: &mut Settings) -> Result<()> {
+        let path = self
+            .config_files()
+            .iter()
+            .map(|file| dir.join(file))
+            .find(|path| path.exists())
+            .ok_or_else(|| {
+                anyhow!(
+                    "no {} configuration found in {:?}, expected one of {:?}",
+                    self,
+                    dir,
+                    self.config_files()
+                )
+            })?;
+
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("failed to read {:?}", path))?;
+        let imported = match self {
+            ImportSource::GitCliff => from_git_cliff(&content),
+            ImportSource::StandardVersion => from_standard_version(&content),
+            ImportSource::SemanticRelease => from_semantic_release(&content),
+        }
+        .with_context(|| format!("failed to import {:?}", path))?;
+
+        if imported.tag_prefix.is_some() {
+            settings.tag_prefix = imported.tag_prefix;
+        }
+
+        for (commit_type, title) in imported.commit_types {
+            settings
+                .commit_types
+                .insert(commit_type, CommitConfig::new(&title));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct CliffConfig {
+    #[serde(default)]
+    git: CliffGit,
+}
+
+#[derive(Deserialize, Default)]
+struct CliffGit {
+    tag_pattern: Option<String>,
+    #[serde(default)]
+    commit_parsers: Vec<CliffParser>,
+}
+
+#[derive(Deserialize)]
+struct CliffParser {
+    message: Option<String>,
+    group: Option<String>,
+    #[serde(default)]
+    skip: bool,
+}
+
+fn from_git_cliff(content: &str) -> Result<Imported> {
+    let config: CliffConfig = toml::from_str(content)?;
+    let tag_prefix = config
+        .git
+        .tag_pattern
+        .as_deref()
+        .map(|pattern| {
+            pattern
+                .trim_start_matches('^')
+                .chars()
+                .take_while(|char| !"[](){}.*+?|\\$".contains(*char))
+                .collect::<String>()
+        })
+        .filter(|prefix| !prefix.is_empty());
+
+    let mut commit_types = vec![];
+    for parser in config
+        .git
+        .commit_parsers
+        .iter()
+        .filter(|parser| !parser.skip)
+    {
+        let (Some(message), Some(group)) = (&parser.message, &parser.group) else {
+            continue;
+        };
+
+        for commit_type in regex_commit_types(message) {
+            commit_types.push((commit_type, strip_html_comments(group)));
+        }
+    }
+
+    Ok(Imported {
+        tag_prefix,
+        commit_types,
+    })
+}
+
+#[derive(Deserialize)]
+struct ConventionalType {
+    #[serde(rename = "type")]
+    commit_type: String,
+    section: Option<String>,
+    #[serde(default)]
+    hidden: bool,
+}
+
+fn conventional_types(types: Vec<ConventionalType>) -> Vec<(String, String)> {
+    types
+        .into_iter()
+        .filter(|commit_type| !commit_type.hidden)
+        .filter_map(|commit_type| Some((commit_type.commit_type, commit_type.section?)))
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionRc {
+    tag_prefix: Option<String>,
+    #[serde(default)]
+    types: Vec<ConventionalType>,
+}
+
+fn from_standard_version(content: &str) -> Result<Imported> {
+    let config: VersionRc = serde_json::from_str(content)?;
+    Ok(Imported {
+        tag_prefix: config.tag_prefix.filter(|prefix| !prefix.is_empty()),
+        commit_types: conventional_types(config.types),
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseRc {
+    tag_format: Option<String>,
+    #[serde(default)]
+    plugins: Vec<serde_json::Value>,
+}
+
+fn from_semantic_release(content: &str) -> Result<Imported> {
+    let config: ReleaseRc = match serde_json::from_str(content) {
+        Ok(config) => config,
+        Err(_) if !content.trim_start().starts_with('{') => {
+            bail!("only JSON semantic-release configurations can be imported")
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let tag_prefix = config
+        .tag_format
+        .as_deref()
+        .and_then(|format| format.split_once("${version}"))
+        .map(|(prefix, _)| prefix.to_string())
+        .filter(|prefix| !prefix.is_empty());
+
+    // Types are declared in the options of the changelog plugins: `["plugin", { options }]`
+    let mut commit_types = vec![];
+    for plugin in &config.plugins {
+        let types = plugin
+            .get(1)
+            .and_then(|options| options.get("presetConfig"))
+            .and_then(|preset| preset.get("types"));
+
+        if let Some(types) = types {
+            let types: Vec<ConventionalType> = serde_json::from_value(types.clone())?;
+            commit_types.extend(conventional_types(types));
+        }
+    }
+
+    Ok(Imported {
+        tag_prefix,
+        commit_types,
+    })
+}
+
+// The types matched by a git-cliff commit parser regex: `^feat`, `^(build|ci)`, `^doc`.
+// Prefixes of a default commit type are completed, `^doc` matches `docs` commits.
+fn regex_commit_types(regex: &str) -> Vec<String> {
+    let default_types = Settings::default().commit_types();
+    let regex = regex.trim_start_matches('^');
+    let alternatives = regex
+        .strip_prefix('(')
+        .and_then(|group| group.split_once(')'))
+        .map_or(regex, |(alternatives, _)| alternatives);
+
+    alternatives
+        .split('|')
+        .map(|alternative| {
+            alternative
+                .trim_start_matches('^')
+                .chars()
+                .take_while(char::is_ascii_alphanumeric)
+                .collect::<String>()
+        })
+        .filter(|prefix| !prefix.is_empty())
+        .map(|prefix| {
+            let completions: Vec<_> = default_types
+                .keys()
+                .map(|commit_type| commit_type.as_ref())
+                .filter(|commit_type| commit_type.starts_with(&prefix))
+                .collect();
+
+            match completions.as_slice() {
+                [commit_type] => commit_type.to_string(),
+                _ => prefix,
+            }
+        })
+        .collect()
+}
+
+// git-cliff groups are often prefixed with an html comment to sort them: `<!-- 0 -->Features`
+fn strip_html_comments(group: &str) -> String {
+    let mut group = group.to_string();
+    while let Some(start) = group.find("<!--") {
+        match group[start..].find("-->") {
+            Some(end) => group.replace_range(start..start + end + 3, ""),
+            None => break,
+        }
+    }
+
+    group.trim().to_string()
+}
+
+impl FromStr for ImportSource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "git-cliff" => Ok(ImportSource::GitCliff),
+            "standard-version" => Ok(ImportSource::StandardVersion),
+            "semantic-release" => Ok(ImportSource::SemanticRelease),
+            other => Err(format!("unknown import source '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            ImportSource::GitCliff => "git-cliff",
+            ImportSource::StandardVersion => "standard-version",
+            ImportSource::SemanticRelease => "semantic-release",
+        };
+
+        f.write_str(source)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::settings::import::{
+        from_git_cliff, from_semantic_release, from_standard_version, Imported,
+    };
+    use anyhow::Result;
+    use indoc::indoc;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn import_git_cliff_config() -> Result<()> {
+        // Arrange
+        let config = indoc! {r#"
+            [git]
+            tag_pattern = "v[0-9].*"
+            commit_parsers = [
+                { message = "^feat", group = "<!-- 0 -->Features" },
+                { message = "^doc", group = "Documentation" },
+                { message = "^(build|ci)", group = "Build" },
+                { message = "^chore\\(release\\)", skip = true },
+                { body = ".*security", group = "Security" },
+            ]
+        "#};
+
+        // Act
+        let imported = from_git_cliff(config)?;
+
+        // Assert
+        assert_that!(imported).is_equal_to(Imported {
+            tag_prefix: Some("v".to_string()),
+            commit_types: vec![
+                ("feat".to_string(), "Features".to_string()),
+                ("docs".to_string(), "Documentation".to_string()),
+                ("build".to_string(), "Build".to_string()),
+                ("ci".to_string(), "Build".to_string()),
+            ],
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn import_standard_version_and_semantic_release_configs() -> Result<()> {
+        // Arrange
+        let versionrc = r#"{
+            "tagPrefix": "release-",
+            "types": [
+                { "type": "feat", "section": "New features" },
+                { "type": "chore", "hidden": true }
+            ]
+        }"#;
+        let releaserc = r#"{
+            "tagFormat": "v${version}",
+            "plugins": [
+                "@semantic-release/commit-analyzer",
+                ["@semantic-release/release-notes-generator", {
+                    "preset": "conventionalcommits",
+                    "presetConfig": { "types": [{ "type": "perf", "section": "Speed" }] }
+                }]
+            ]
+        }"#;
+
+        // Act
+        let standard_version = from_standard_version(versionrc)?;
+        let semantic_release = from_semantic_release(releaserc)?;
+
+        // Assert
+        assert_that!(standard_version).is_equal_to(Imported {
+            tag_prefix: Some("release-".to_string()),
+            commit_types: vec![("feat".to_string(), "New features".to_string())],
+        });
+        assert_that!(semantic_release).is_equal_to(Imported {
+            tag_prefix: Some("v".to_string()),
+            commit_types: vec![("perf".to_string(), "Speed".to_string())],
+        });
+        assert_that!(from_semantic_release("branches: [main]")).is_err();
+        Ok(())
+    }
+}
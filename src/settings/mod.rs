@@ -1,122 +1,972 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 use crate::conventional::commit::CommitConfig;
-use crate::git::repository::Repository;
-use crate::{CommitsMetadata, CONFIG_PATH, SETTINGS};
+use crate::CommitsMetadata;
 
-use crate::conventional::changelog::error::ChangelogError;
-use crate::conventional::changelog::template::{RemoteContext, Template};
+use crate::conventional::version::Increment;
 use crate::settings::error::SettingError;
-use config::{Config, File};
+use config::ConfigError;
 use conventional_commit_parser::commit::CommitType;
+use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{RootSchema, Schema};
+use schemars::{schema_for, JsonSchema};
+use semver::Version;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "git")]
+use {
+    crate::conventional::changelog::error::ChangelogError,
+    crate::conventional::changelog::template::{RemoteContext, Template},
+    crate::conventional::lint::RULES,
+    crate::git::identity::Identity,
+    crate::git::repository::Repository,
+    crate::hook::Hook,
+    crate::CONFIG_PATH,
+    config::{Config, Environment, File, FileFormat, Map},
+    itertools::Itertools,
+    std::fs,
+};
 
 type CommitsMetadataSettings = HashMap<String, CommitConfig>;
 pub(crate) type AuthorSettings = Vec<AuthorSetting>;
 
-mod error;
+pub mod error;
+pub mod import;
+pub mod preset;
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum HookType {
     PreBump,
     PostBump,
+    PreCommit,
+    PostCommit,
 }
 
-#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Default)]
+impl fmt::Display for HookType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hook_type = match self {
+            HookType::PreBump => "pre-bump",
+            HookType::PostBump => "post-bump",
+            HookType::PreCommit => "pre-commit",
+            HookType::PostCommit => "post-commit",
+        };
+
+        f.write_str(hook_type)
+    }
+}
+
+/// The shell used to interpret hook commands, `none` executes the command directly
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Eq, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HookShell {
+    #[default]
+    Sh,
+    Bash,
+    Cmd,
+    Powershell,
+    None,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Settings {
     #[serde(default)]
     pub from_latest_tag: bool,
     #[serde(default)]
     pub ignore_merge_commits: bool,
+    /// Branches allowed to bump from, as glob patterns or `regex:<expression>` regular expressions
     #[serde(default)]
     pub branch_whitelist: Vec<String>,
+    /// Branches never bumped from, written like `branch_whitelist` patterns
+    #[serde(default)]
+    pub branch_denylist: Vec<String>,
     pub tag_prefix: Option<String>,
     #[serde(default)]
-    pub pre_bump_hooks: Vec<String>,
+    pub legacy_tag_prefixes: Vec<String>,
+    pub version_source: Option<PathBuf>,
+    #[serde(default)]
+    pub unshallow: bool,
+    #[serde(default)]
+    pub cache: bool,
+    #[serde(default)]
+    pub hook_shell: HookShell,
     #[serde(default)]
-    pub post_bump_hooks: Vec<String>,
+    pub pre_bump_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub post_bump_hooks: Vec<HookConfig>,
+    /// Hooks run before `cog commit` creates the commit. `{{message}}` is replaced by the quoted
+    /// message with `sh`, `bash` or no shell, the `COG_COMMIT_MESSAGE` environment variable
+    /// holds it whatever the shell.
+    #[serde(default)]
+    pub pre_commit_hooks: Vec<HookConfig>,
+    /// Hooks run once `cog commit` created the commit, they get the message like `pre_commit_hooks`
+    #[serde(default)]
+    pub post_commit_hooks: Vec<HookConfig>,
     #[serde(default)]
     pub commit_types: CommitsMetadataSettings,
+    /// Alternative spellings of commit types, e.g. `feature = "feat"`. Commits are read, and
+    /// created by `cog commit`, with the canonical type.
+    #[serde(default)]
+    pub commit_type_aliases: HashMap<String, String>,
     #[serde(default)]
     pub changelog: Changelog,
     #[serde(default)]
     pub bump_profiles: HashMap<String, BumpProfile>,
+    #[serde(default)]
+    pub notifications: Notifications,
+    #[serde(default)]
+    pub integrations: Integrations,
+    #[serde(default)]
+    pub lint: LintSettings,
+    #[serde(default)]
+    pub tag: TagSettings,
+    #[serde(default)]
+    pub bump: BumpSettings,
+    #[serde(default)]
+    pub publish: PublishSettings,
+    /// Pre-release identifier appended by bumps on branches matching a glob pattern:
+    /// `develop = "beta"` bumps `1.1.0-beta.1`, then `1.1.0-beta.2` from `develop`
+    #[serde(default)]
+    pub channels: HashMap<String, String>,
+    /// Exclusive version ceiling of the branches matching a glob pattern: with `"1.x" = "2.0.0"`
+    /// bumps from `1.x` only read the tags lower than `2.0.0` and never reach it
+    #[serde(default)]
+    pub maintenance_branches: HashMap<String, String>,
+    #[serde(default)]
+    pub commit: CommitSettings,
+    #[serde(default)]
+    pub import_history: ImportHistorySettings,
+    /// Author and committer of the release commits, and committer of the commits
+    /// rewritten by `cog edit`, written `Name <email>`
+    pub release_author: Option<String>,
+    /// Named overlays of these settings, selected with `cog bump --profile <name>`
+    #[serde(default)]
+    pub profile: HashMap<String, serde_json::Value>,
+}
+
+// The prefix of the environment variables overriding settings
+#[cfg(feature = "git")]
+const ENV_PREFIX: &str = "COG_CONFIG_";
+
+// Settings embedded in manifests, looked up in this order when there is no `cog.toml`
+#[cfg(feature = "git")]
+const EMBEDDED_CONFIGS: [(&str, &[&str]); 4] = [
+    ("Cargo.toml", &["package", "metadata", "cocogitto"]),
+    ("Cargo.toml", &["workspace", "metadata", "cocogitto"]),
+    ("pyproject.toml", &["tool", "cocogitto"]),
+    ("package.json", &["cocogitto"]),
+];
+
+fn branch_setting<'a>(settings: &'a HashMap<String, String>, branch: &str) -> Option<&'a str> {
+    settings
+        .iter()
+        .filter(|(pattern, _)| {
+            Glob::new(pattern).is_ok_and(|glob| glob.compile_matcher().is_match(branch))
+        })
+        .max_by_key(|(pattern, _)| pattern.len())
+        .map(|(_, value)| value.as_str())
+}
+
+/// A branch pattern of `branch_whitelist` and `branch_denylist`: a glob,
+/// or a regular expression matching the whole branch name when written `regex:<expression>`
+pub(crate) enum BranchPattern {
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl BranchPattern {
+    pub(crate) fn new(pattern: &str) -> Result<Self, String> {
+        match pattern.strip_prefix("regex:") {
+            Some(expression) => Regex::new(&format!("^(?:{})$", expression))
+                .map(BranchPattern::Regex)
+                .map_err(|err| err.to_string()),
+            None => Glob::new(pattern)
+                .map(|glob| BranchPattern::Glob(glob.compile_matcher()))
+                .map_err(|err| err.to_string()),
+        }
+    }
+
+    pub(crate) fn is_match(&self, branch: &str) -> bool {
+        match self {
+            BranchPattern::Glob(glob) => glob.is_match(branch),
+            BranchPattern::Regex(regex) => regex.is_match(branch),
+        }
+    }
+}
+
+fn merge_json(base: &mut serde_json::Value, overlay: &serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(base) => merge_json(base, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
+#[cfg(feature = "git")]
+fn user_config_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("cog").join(CONFIG_PATH))
+}
+
+/// Options of the commits created by `cog commit` and `cog bump`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct CommitSettings {
+    /// Append a `Signed-off-by` trailer, as `--signoff` does
+    pub sign_off: bool,
+    /// Refuse `cog commit` on the branches `branch_whitelist` and `branch_denylist` forbid
+    pub check_branch: bool,
+}
+
+/// Rules of `cog import-history`, converting a legacy commit message convention
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct ImportHistorySettings {
+    /// Tried in order, the first rule matching a non compliant message rewrites it
+    pub rules: Vec<RewriteRule>,
+}
+
+/// Rewrite the part of a commit message matching `pattern` with `template`,
+/// e.g. `{ pattern = '^\[FIX\] ', template = "fix: " }`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RewriteRule {
+    /// A regular expression searched in the whole commit message
+    pub pattern: String,
+    /// The replacement of the match, `$1` or `${name}` insert the groups of `pattern`
+    pub template: String,
+}
+
+/// Tags never used as the current version, like nightly builds or deployment markers
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct TagSettings {
+    /// Glob patterns matched against the full tag name
+    pub ignore: Vec<String>,
+    /// Also refuse to create a tag that already exists on the default remote, checked without
+    /// prompting for credentials
+    pub check_remote: bool,
+}
+
+/// Options of `cog bump`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct BumpSettings {
+    /// What to do with the changes found in the working tree before bumping
+    pub dirty: DirtyPolicy,
+    /// Glob patterns of the paths the release commit is limited to, relative to the repository
+    /// root. The changelog files are always committed, every change is when not set.
+    pub commit_paths: Option<Vec<String>>,
+    /// Fetch the remote the branch tracks and refuse to bump when the branch is behind it
+    pub require_up_to_date: bool,
+    /// Write a JSON record of each release once the post-bump hooks ran
+    pub manifest: Option<ManifestSettings>,
+}
+
+/// Package registries the released version is published to by `cog publish`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct PublishSettings {
+    /// Publish once `cog bump` released a version
+    pub on_bump: bool,
+    /// Run in order, the first failure stops the publication
+    pub providers: Vec<PublishProvider>,
+}
+
+/// A registry the release is published to, e.g. `{ provider = "cargo", args = ["--locked"] }`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(tag = "provider", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum PublishProvider {
+    /// `cargo publish`, with `--dry-run` on dry runs
+    Cargo {
+        /// Additional arguments, e.g. `["--package", "cocogitto"]`
+        #[serde(default)]
+        args: Vec<String>,
+        /// Relative to the repository root
+        cwd: Option<PathBuf>,
+    },
+    /// `npm publish`, with `--dry-run` on dry runs
+    Npm {
+        #[serde(default)]
+        args: Vec<String>,
+        cwd: Option<PathBuf>,
+    },
+    /// Tag a locally built container image with the released version and push it.
+    /// Nothing is run on dry runs.
+    Container {
+        /// The repository the image is pushed to, e.g. `ghcr.io/cocogitto/cog`
+        image: String,
+        /// The local image to tag, defaults to `image`
+        source: Option<String>,
+        /// `{{version}}` and `{{tag}}` are replaced with the released version and tag
+        #[serde(default = "default_container_tags")]
+        tags: Vec<String>,
+        /// The container engine program
+        #[serde(default = "default_container_engine")]
+        engine: String,
+    },
+}
+
+fn default_container_tags() -> Vec<String> {
+    vec!["{{version}}".to_string()]
+}
+
+fn default_container_engine() -> String {
+    "docker".to_string()
+}
+
+/// The release record written by `cog bump`, e.g. to be archived by compliance teams
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct ManifestSettings {
+    /// Relative to the repository root. The record is written after the release commit,
+    /// so the path should be ignored by git.
+    pub path: PathBuf,
+    /// Glob patterns of the files built by the bump hooks, relative to the repository root.
+    /// Matching files are recorded with their size and git blob id.
+    pub artifacts: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+impl ManifestSettings {
+    pub(crate) fn artifacts(&self) -> Result<GlobSet, globset::Error> {
+        let mut artifacts = GlobSetBuilder::new();
+        for pattern in &self.artifacts {
+            artifacts.add(Glob::new(pattern)?);
+        }
+
+        artifacts.build()
+    }
+}
+
+impl Default for ManifestSettings {
+    fn default() -> Self {
+        ManifestSettings {
+            path: PathBuf::from("release-manifest.json"),
+            artifacts: vec![],
+        }
+    }
+}
+
+/// How `cog bump` handles a working tree that is not clean
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DirtyPolicy {
+    /// Refuse to bump
+    #[default]
+    Error,
+    /// Stash the changes, untracked files included, and restore them after the release
+    Stash,
+    /// Bump despite untracked files, they are left out of the release commit.
+    /// Uncommitted changes to tracked files still fail the bump.
+    AllowUntracked,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields, default)]
 pub struct Changelog {
+    /// Write the release notes to the changelog file on bump
+    pub enabled: bool,
     pub template: Option<String>,
     pub remote: Option<String>,
+    /// Guessed from `remote` when not set
+    pub provider: Option<RemoteProvider>,
     pub path: PathBuf,
     pub owner: Option<String>,
     pub repository: Option<String>,
+    /// Rendered above the commits of the 1.0.0 release created by `cog bump --stabilize`
+    pub stabilize_note: Option<String>,
     pub authors: AuthorSettings,
+    /// Additional changelogs written on bump, each with its own template and commit types
+    pub files: Vec<ChangelogFile>,
+    pub trailers: ChangelogTrailers,
+    /// Detect commits applied more than once, e.g. fixes cherry-picked to a maintenance branch
+    pub dedupe: Option<Dedupe>,
 }
 
 impl Default for Changelog {
     fn default() -> Self {
         Changelog {
+            enabled: true,
             template: None,
             remote: None,
+            provider: None,
             path: PathBuf::from("CHANGELOG.md"),
             owner: None,
             repository: None,
             authors: vec![],
+            files: vec![],
+            stabilize_note: None,
+            trailers: ChangelogTrailers::default(),
+            dedupe: None,
+        }
+    }
+}
+
+/// Commit footers changing how a commit is rendered in the changelog, e.g. `Changelog: hidden`.
+/// Footer tokens and values are case insensitive.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct ChangelogTrailers {
+    /// The footer moving a commit to another changelog section, e.g. `Changelog`
+    pub category: Option<String>,
+    /// Values of the `category` footer leaving the commit out of the changelog
+    pub hidden: Vec<String>,
+    /// Footers attached to the commit as notes, e.g. `Note`
+    pub notes: Vec<String>,
+    /// Section titles of the `category` footer values, e.g. `security = "Security Fixes"`.
+    /// Other values are used as section titles as is.
+    pub categories: HashMap<String, String>,
+}
+
+/// How duplicated commits of a changelog are detected and rendered
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct Dedupe {
+    pub by: DuplicateKey,
+    pub mode: DuplicateMode,
+}
+
+/// What two commits must share to be duplicates, the oldest one is kept
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateKey {
+    /// The commit they were cherry-picked from, as recorded by `git cherry-pick -x`
+    /// or an `X-Original-Sha` footer
+    #[default]
+    OriginalSha,
+    /// The same changes, as computed by `git patch-id`
+    PatchId,
+    /// The same type, scope and summary
+    Summary,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicateMode {
+    /// Keep the duplicates, templates read the commit they duplicate as `commit.duplicate_of`
+    #[default]
+    Mark,
+    /// Leave the duplicates out of the changelog
+    Collapse,
+}
+
+impl Default for ChangelogTrailers {
+    fn default() -> Self {
+        ChangelogTrailers {
+            category: None,
+            hidden: vec!["hidden".to_string()],
+            notes: vec![],
+            categories: HashMap::new(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq)]
+/// A changelog written on bump along the main one, e.g. a user facing changelog
+/// with features and fixes only
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChangelogFile {
+    pub path: PathBuf,
+    /// Falls back to the changelog template
+    pub template: Option<String>,
+    /// Only these commit types are included, all of them when empty
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Commit types left out of this changelog
+    #[serde(default)]
+    pub omit: Vec<String>,
+    /// Applied on top of the deduplication of the main changelog
+    #[serde(default)]
+    pub dedupe: Option<Dedupe>,
+}
+
+impl ChangelogFile {
+    pub(crate) fn includes(&self, commit_type: &str) -> bool {
+        (self.types.is_empty() || self.types.iter().any(|typ| typ == commit_type))
+            && !self.omit.iter().any(|typ| typ == commit_type)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct AuthorSetting {
     pub signature: String,
     pub username: String,
 }
 
-pub fn commit_username(author: &str) -> Option<&'static str> {
-    SETTINGS
-        .changelog
-        .authors
-        .iter()
-        .find(|author_map| author_map.signature == author)
-        .map(|author| author.username.as_str())
+/// Release announcements sent once a version is bumped
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct Notifications {
+    pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Webhook {
+    pub url: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+    /// A tera template rendering the request body, overrides the payload for `kind`
+    pub template: Option<String>,
+}
+
+/// The payload format expected by the webhook endpoint
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    #[default]
+    Generic,
+    Slack,
+    Discord,
+}
+
+/// Rules checked on top of the conventional commit format by `cog check`, `cog verify` and
+/// `cog commit`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct LintSettings {
+    /// Reject the `commit_type_aliases`, commits must use the canonical types
+    pub canonical_types: bool,
+    /// Reject common misspellings in commit summaries, see `misspellings` for additional ones
+    pub spellcheck: bool,
+    /// Words rejected in commit summaries, e.g. `["wip", "tmp"]`, regardless of their case
+    pub banned_words: Vec<String>,
+    /// Reject summaries starting with a past tense or gerund verb, e.g. `added` or `adding`
+    pub imperative_mood: bool,
+    /// Words rejected at the start of summaries by `imperative_mood`, on top of the built-in verbs
+    pub non_imperative_verbs: Vec<String>,
+    /// Require a blank line between the summary and the body
+    pub blank_line_after_summary: bool,
+    /// The maximum length of the body and footer lines, lines containing an url are exempted
+    pub body_max_line_length: Option<usize>,
+    /// Commands the message is piped to, run with `hook_shell`. A command exiting with a
+    /// non-zero status reports its stderr as a violation of the `external` rule.
+    pub external: Vec<String>,
+    pub ticket_reference: Option<TicketReferenceRule>,
+    /// Misspellings checked on top of the built-in ones, with their correction:
+    /// `recieve = "receive"`
+    pub misspellings: HashMap<String, String>,
+    /// The severity of rules by id, e.g. `spelling = "warning"`. Rules are errors by default.
+    pub severities: HashMap<String, Severity>,
+    /// The files of each scope as glob patterns, e.g. `api = ["services/api/**"]`. With
+    /// `cog check --staged`, the scope of the message must have staged files.
+    pub scope_paths: HashMap<String, Vec<String>>,
+}
+
+/// How a violated lint rule is reported
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Fail the check
+    #[default]
+    Error,
+    /// Report the violation without failing the check
+    Warning,
+    /// Disable the rule
+    Off,
+}
+
+/// Require a ticket reference in the scope, body or footers of the commits
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TicketReferenceRule {
+    /// A regex matching ticket references, e.g. `[A-Z]+-\\d+`
+    pub pattern: String,
+    /// The commit types requiring a reference, all of them when empty
+    #[serde(default)]
+    pub types: Vec<String>,
+}
+
+/// Third-party services updated once a version is bumped
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct Integrations {
+    pub issue_tracker: Option<IssueTracker>,
+    pub milestones: Option<MilestoneSettings>,
+}
+
+/// The tracker of the issues referenced by the released commits, commented on and
+/// transitioned after a bump
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IssueTracker {
+    pub kind: IssueTrackerKind,
+    /// The Jira site, e.g. `https://example.atlassian.net`, or the GitHub API, `https://api.github.com`
+    /// by default
+    pub url: Option<String>,
+    /// The GitHub `owner/repository`, defaults to `changelog.owner` and `changelog.repository`
+    pub repository: Option<String>,
+    /// A regex matching issue keys in the commit messages, its first group being the key when
+    /// it has one. Defaults to `[A-Z][A-Z0-9_]+-\d+` for Jira and `#(\d+)` for GitHub
+    pub pattern: Option<String>,
+    /// The environment variable holding the API token, `JIRA_TOKEN` or `GITHUB_TOKEN` by default
+    pub token_env: Option<String>,
+    /// The environment variable holding the Jira user, the token is sent as a bearer token without it
+    pub user_env: Option<String>,
+    /// A tera template rendering the comment, with the `version` and `previous_version` variables.
+    /// No comment is posted when empty
+    #[serde(default = "default_issue_comment")]
+    pub comment: String,
+    /// The Jira transition id, or the GitHub issue state, e.g. `closed`, the issues are moved to
+    pub transition: Option<String>,
+}
+
+fn default_issue_comment() -> String {
+    "Released in {{ version }}".to_string()
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueTrackerKind {
+    Jira,
+    Github,
+}
+
+/// Milestones named after versions, on the GitHub or GitLab repository of the changelog remote
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct MilestoneSettings {
+    /// Close the milestone of the released version
+    pub close: bool,
+    /// Create the milestone of the next version unless it exists
+    pub create_next: bool,
+    /// The increment from the released version to the next milestone
+    pub next: Increment,
+    /// A tera template rendering milestone titles, with the `version` and `tag` variables
+    pub title: String,
+    /// The REST API of the remote, e.g. a GitHub Enterprise server. Defaults to
+    /// `https://api.github.com` and `https://<remote>/api/v4` for GitLab
+    pub api_url: Option<String>,
+    /// The environment variable holding the API token, `GITHUB_TOKEN` or `GITLAB_TOKEN` by default
+    pub token_env: Option<String>,
+}
+
+impl Default for MilestoneSettings {
+    fn default() -> Self {
+        MilestoneSettings {
+            close: true,
+            create_next: true,
+            next: Increment::Minor,
+            title: "{{ version }}".to_string(),
+            api_url: None,
+            token_env: None,
+        }
+    }
+}
+
+/// The hosting service of the changelog remote, deciding how commit and compare urls are built
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteProvider {
+    #[default]
+    Github,
+    Gitlab,
+    Bitbucket,
 }
 
-pub fn changelog_path() -> &'static PathBuf {
-    &SETTINGS.changelog.path
+impl RemoteProvider {
+    /// Guess the provider from the remote host, unknown hosts use GitHub urls
+    pub fn from_remote(remote: &str) -> Self {
+        if remote.contains("gitlab") {
+            RemoteProvider::Gitlab
+        } else if remote.contains("bitbucket") {
+            RemoteProvider::Bitbucket
+        } else {
+            RemoteProvider::Github
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize, Default, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Default, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct BumpProfile {
+    /// The profile whose hooks this one builds on, `default` being the top-level hooks
+    /// unless a profile has this name
+    pub extends: Option<String>,
+    /// Run after the hooks of the extended profile
+    #[serde(default)]
+    pub pre_bump_hooks: Vec<HookConfig>,
+    #[serde(default)]
+    pub post_bump_hooks: Vec<HookConfig>,
+    /// Run before the hooks of the extended profile
     #[serde(default)]
-    pub pre_bump_hooks: Vec<String>,
+    pub prepend_pre_bump_hooks: Vec<HookConfig>,
     #[serde(default)]
-    pub post_bump_hooks: Vec<String>,
+    pub prepend_post_bump_hooks: Vec<HookConfig>,
+}
+
+/// A bump hook, either declared as a plain command string or as a table:
+/// `{ command = "./scripts/release.sh", cwd = "scripts", only_branches = ["main"] }`
+#[derive(Debug, Deserialize, Serialize, Clone, Eq, PartialEq, Default)]
+#[serde(from = "HookSetting", into = "HookSetting")]
+pub struct HookConfig {
+    pub command: String,
+    /// Shell used to run the command, overrides the global `hook_shell` setting
+    pub shell: Option<HookShell>,
+    /// Name used to prefix the hook output, defaults to the hook program name
+    pub name: Option<String>,
+    /// Working directory of the hook, relative to the repository root
+    pub cwd: Option<PathBuf>,
+    /// Only run the hook when bumping from a branch matching one of these glob patterns
+    pub only_branches: Vec<String>,
+    /// Only run the hook for the given version increments
+    pub only_increments: Vec<Increment>,
+    /// Kill the hook if it is still running after the given number of seconds
+    pub timeout: Option<u64>,
+    /// Number of additional attempts when the hook fails
+    pub retries: u32,
+    /// Log a warning instead of failing the bump when the hook fails
+    pub allow_failure: bool,
+    /// Capture the hook stdout and stderr, they are reported on stderr with a `[name]` prefix
+    pub capture_output: bool,
+}
+
+// Hooks are documented by the schema of their two accepted forms
+impl JsonSchema for HookConfig {
+    fn schema_name() -> String {
+        "HookConfig".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        HookSetting::json_schema(gen)
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum HookSetting {
+    Command(String),
+    Config(HookTable),
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct HookTable {
+    command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shell: Option<HookShell>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cwd: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    only_branches: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    only_increments: Vec<Increment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    retries: u32,
+    #[serde(default, skip_serializing_if = "is_false")]
+    allow_failure: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    capture_output: bool,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl HookConfig {
+    pub fn new(command: &str) -> Self {
+        HookConfig {
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn cwd(&self) -> Option<&Path> {
+        self.cwd.as_deref()
+    }
+
+    /// Evaluate the hook conditions, increment conditions only apply to bump hooks
+    pub fn should_run(&self, branch: Option<&str>, increment: Option<Increment>) -> bool {
+        let branch_match = self.only_branches.is_empty()
+            || branch.is_some_and(|branch| {
+                self.only_branches.iter().any(|pattern| {
+                    Glob::new(pattern)
+                        .map(|glob| glob.compile_matcher().is_match(branch))
+                        .unwrap_or(false)
+                })
+            });
+
+        let increment_match = match increment {
+            Some(increment) => {
+                self.only_increments.is_empty() || self.only_increments.contains(&increment)
+            }
+            None => true,
+        };
+
+        branch_match && increment_match
+    }
+}
+
+impl From<HookSetting> for HookConfig {
+    fn from(setting: HookSetting) -> Self {
+        match setting {
+            HookSetting::Command(command) => HookConfig::new(&command),
+            HookSetting::Config(table) => HookConfig {
+                command: table.command,
+                shell: table.shell,
+                name: table.name,
+                cwd: table.cwd,
+                only_branches: table.only_branches,
+                only_increments: table.only_increments,
+                timeout: table.timeout,
+                retries: table.retries,
+                allow_failure: table.allow_failure,
+                capture_output: table.capture_output,
+            },
+        }
+    }
+}
+
+impl From<HookConfig> for HookSetting {
+    fn from(config: HookConfig) -> Self {
+        if config == HookConfig::new(&config.command) {
+            return HookSetting::Command(config.command);
+        }
+
+        HookSetting::Config(HookTable {
+            command: config.command,
+            shell: config.shell,
+            name: config.name,
+            cwd: config.cwd,
+            only_branches: config.only_branches,
+            only_increments: config.only_increments,
+            timeout: config.timeout,
+            retries: config.retries,
+            allow_failure: config.allow_failure,
+            capture_output: config.capture_output,
+        })
+    }
 }
 
 impl Settings {
-    // Fails only if config exists and is malformed
+    /// Load the settings by merging, from lowest to highest precedence:
+    /// - the user config, `$XDG_CONFIG_HOME/cog/cog.toml` or `~/.config/cog/cog.toml`
+    /// - the repository config, read from the first of these sources that exists:
+    ///   1. `cog.toml`
+    ///   2. `[package.metadata.cocogitto]` then `[workspace.metadata.cocogitto]` in `Cargo.toml`
+    ///   3. `[tool.cocogitto]` in `pyproject.toml`
+    ///   4. the `cocogitto` key in `package.json`
+    /// - `COG_CONFIG_*` environment variables, see [`Settings::env_overrides`]
+    ///
+    /// Fails only if config exists and is malformed.
+    #[cfg(feature = "git")]
     pub(crate) fn get(repository: &Repository) -> Result<Self, SettingError> {
-        match repository.get_repo_dir() {
-            Some(repo_path) => {
-                let settings_path = repo_path.join(CONFIG_PATH);
-                if settings_path.exists() {
-                    Config::builder()
-                        .add_source(File::from(settings_path))
-                        .build()
-                        .map_err(SettingError::from)?
-                        .try_deserialize()
-                        .map_err(SettingError::from)
-                } else {
-                    Ok(Settings::default())
-                }
+        let mut config = Config::builder();
+
+        if let Some(user_config) = user_config_path() {
+            config = config.add_source(File::from(user_config).required(false));
+        }
+
+        if let Some(repository_config) = Settings::repository_config(repository)? {
+            config = config.add_source(File::from_str(&repository_config, FileFormat::Toml));
+        }
+
+        config
+            .add_source(Settings::env_overrides())
+            .build()
+            .map_err(SettingError::from)?
+            .try_deserialize()
+            .map_err(SettingError::from)
+    }
+
+    // The repository settings as a TOML document
+    #[cfg(feature = "git")]
+    fn repository_config(repository: &Repository) -> Result<Option<String>, SettingError> {
+        let read = |path: &str| match repository.get_repo_dir() {
+            Some(repo_path) => fs::read_to_string(repo_path.join(path)).ok(),
+            // Bare repositories have no working tree, read the committed config instead
+            None => repository.get_head_file_content(path),
+        };
+
+        if let Some(content) = read(CONFIG_PATH) {
+            return Ok(Some(content));
+        }
+
+        for (path, keys) in EMBEDDED_CONFIGS {
+            if let Some(config) = read(path)
+                .map(|content| Settings::manifest_config(path, &content, keys))
+                .transpose()?
+                .flatten()
+            {
+                return Ok(Some(config));
             }
-            None => Ok(Settings::default()),
         }
+
+        Ok(None)
+    }
+
+    #[cfg(feature = "git")]
+    fn manifest_config(
+        path: &str,
+        content: &str,
+        keys: &[&str],
+    ) -> Result<Option<String>, SettingError> {
+        let invalid = |err: &dyn std::fmt::Display| {
+            SettingError::from(ConfigError::Message(format!("{}: {}", path, err)))
+        };
+
+        let manifest: toml::Value = if path.ends_with(".json") {
+            let manifest: serde_json::Value =
+                serde_json::from_str(content).map_err(|err| invalid(&err))?;
+            toml::Value::try_from(manifest).map_err(|err| invalid(&err))?
+        } else {
+            toml::from_str(content).map_err(|err| invalid(&err))?
+        };
+
+        keys.iter()
+            .try_fold(&manifest, |value, key| value.get(key))
+            .map(|settings| toml::to_string(settings).map_err(|err| invalid(&err)))
+            .transpose()
+    }
+
+    /// Settings overridden by `COG_CONFIG_*` environment variables: `COG_CONFIG_TAG_PREFIX=v`
+    /// sets `tag_prefix` and `__` separates nested keys, as in `COG_CONFIG_CHANGELOG__PATH`.
+    /// The prefix keeps the variables exported to hooks, like `COG_TAG`, from being read as
+    /// settings. Variables not naming a setting are ignored.
+    #[cfg(feature = "git")]
+    fn env_overrides() -> Environment {
+        let fields = serde_json::to_value(Settings::default())
+            .ok()
+            .and_then(|settings| settings.as_object().cloned())
+            .unwrap_or_default();
+
+        let overrides: Map<String, String> = std::env::vars()
+            .filter_map(|(key, value)| {
+                let key = key.strip_prefix(ENV_PREFIX)?;
+                let field = key.split("__").next()?.to_lowercase();
+                fields
+                    .contains_key(&field)
+                    .then(|| (key.to_string(), value))
+            })
+            .collect();
+
+        Environment::default()
+            .separator("__")
+            .try_parsing(true)
+            .source(Some(overrides))
     }
 
     pub fn commit_types(&self) -> CommitsMetadata {
@@ -131,9 +981,36 @@ impl Settings {
 
         default_types.extend(custom_types);
 
+        // Aliases of unknown types are left out, their commits are rejected
+        let aliases: Vec<_> = self
+            .commit_type_aliases
+            .iter()
+            .filter_map(|(alias, canonical)| {
+                let canonical = CommitType::from(canonical.as_str());
+                let config = default_types.get(&canonical)?;
+                Some((
+                    CommitType::from(alias.as_str()),
+                    CommitConfig {
+                        alias_of: Some(canonical),
+                        ..config.clone()
+                    },
+                ))
+            })
+            .collect();
+
+        default_types.extend(aliases);
+
         default_types
     }
 
+    /// The canonical type of `commit_type`, itself unless it is one of the `commit_type_aliases`
+    pub fn canonical_commit_type(&self, commit_type: &str) -> String {
+        match self.commit_type_aliases.get(commit_type) {
+            Some(canonical) => canonical.clone(),
+            None => commit_type.to_string(),
+        }
+    }
+
     fn default_commit_config() -> CommitsMetadata {
         let mut default_types = HashMap::new();
         default_types.insert(CommitType::Feature, CommitConfig::new("Features"));
@@ -156,24 +1033,303 @@ impl Settings {
         default_types
     }
 
-    pub fn get_hooks(&self, hook_type: HookType) -> &Vec<String> {
+    /// The changelog username mapped to a commit author signature
+    pub fn commit_username(&self, author: &str) -> Option<&str> {
+        self.changelog
+            .authors
+            .iter()
+            .find(|author_map| author_map.signature == author)
+            .map(|author| author.username.as_str())
+    }
+
+    /// Report the problems that would otherwise only surface while running a command:
+    /// invalid glob patterns, hooks and changelog template
+    #[cfg(feature = "git")]
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = vec![];
+
+        for pattern in &self.branch_whitelist {
+            if let Err(err) = BranchPattern::new(pattern) {
+                problems.push(format!("branch_whitelist: {}", err));
+            }
+        }
+
+        for pattern in &self.branch_denylist {
+            if let Err(err) = BranchPattern::new(pattern) {
+                problems.push(format!("branch_denylist: {}", err));
+            }
+        }
+
+        for pattern in self.channels.keys().sorted() {
+            if let Err(err) = Glob::new(pattern) {
+                problems.push(format!("channels: {}", err));
+            }
+        }
+
+        for (pattern, ceiling) in self.maintenance_branches.iter().sorted() {
+            if let Err(err) = Glob::new(pattern) {
+                problems.push(format!("maintenance_branches: {}", err));
+            }
+
+            if let Err(err) = Version::parse(ceiling) {
+                problems.push(format!("maintenance_branches.{}: {}", pattern, err));
+            }
+        }
+
+        if let Err(err) = self.ignored_tags() {
+            problems.push(format!("tag.ignore: {}", err));
+        }
+
+        if let Err(err) = self.commit_paths() {
+            problems.push(format!("bump.commit_paths: {}", err));
+        }
+
+        if let Some(Err(err)) = self.bump.manifest.as_ref().map(ManifestSettings::artifacts) {
+            problems.push(format!("bump.manifest.artifacts: {}", err));
+        }
+
+        if let Some(Err(err)) = self.release_author() {
+            problems.push(format!("release_author: {}", err));
+        }
+
+        for rule in self.lint.severities.keys().sorted() {
+            if !RULES.contains(&rule.as_str()) {
+                problems.push(format!(
+                    "lint.severities.{}: unknown lint rule, expected one of {}",
+                    rule,
+                    RULES.join(", ")
+                ));
+            }
+        }
+
+        let mut hooks = vec![
+            ("pre_bump_hooks".to_string(), &self.pre_bump_hooks, true),
+            ("post_bump_hooks".to_string(), &self.post_bump_hooks, true),
+            (
+                "pre_commit_hooks".to_string(),
+                &self.pre_commit_hooks,
+                false,
+            ),
+            (
+                "post_commit_hooks".to_string(),
+                &self.post_commit_hooks,
+                false,
+            ),
+        ];
+
+        for (name, profile) in self.bump_profiles.iter().sorted_by_key(|(name, _)| *name) {
+            let key = format!("bump_profiles.{}", name);
+            if let Err(err) = self.resolve_profile_hooks(name, HookType::PreBump, &mut vec![]) {
+                problems.push(format!("{}.extends: {}", key, err));
+            }
+
+            hooks.push((
+                format!("{}.pre_bump_hooks", key),
+                &profile.pre_bump_hooks,
+                true,
+            ));
+            hooks.push((
+                format!("{}.post_bump_hooks", key),
+                &profile.post_bump_hooks,
+                true,
+            ));
+            hooks.push((
+                format!("{}.prepend_pre_bump_hooks", key),
+                &profile.prepend_pre_bump_hooks,
+                true,
+            ));
+            hooks.push((
+                format!("{}.prepend_post_bump_hooks", key),
+                &profile.prepend_post_bump_hooks,
+                true,
+            ));
+        }
+
+        for (key, hooks, bump) in hooks {
+            for (idx, hook) in hooks.iter().enumerate() {
+                if let Err(err) = Hook::validate(hook, self.hook_shell, bump) {
+                    let cause = err.to_string().split_whitespace().join(" ");
+                    problems.push(format!("{}[{}] `{}`: {}", key, idx, hook.command, cause));
+                }
+            }
+        }
+
+        if let Some(template) = &self.changelog.template {
+            if let Err(err) = Template::from_arg(template, None) {
+                let cause = err.to_string().split_whitespace().join(" ");
+                problems.push(format!("changelog.template: {}", cause));
+            }
+        }
+
+        for (idx, file) in self.changelog.files.iter().enumerate() {
+            if let Some(Err(err)) = file
+                .template
+                .as_deref()
+                .map(|t| Template::from_arg(t, None))
+            {
+                let cause = err.to_string().split_whitespace().join(" ");
+                problems.push(format!("changelog.files[{}].template: {}", idx, cause));
+            }
+        }
+
+        // Only report the problems a profile introduces
+        let inherited = problems.clone();
+        for name in self.profile.keys().sorted() {
+            match self.apply_profile(name) {
+                Ok(mut profile) => {
+                    profile.profile.clear();
+                    problems.extend(
+                        profile
+                            .validate()
+                            .into_iter()
+                            .filter(|problem| !inherited.contains(problem))
+                            .map(|problem| format!("profile.{}: {}", name, problem)),
+                    )
+                }
+                Err(err) => problems.push(err),
+            }
+        }
+
+        problems
+    }
+
+    /// The value of a single setting, nested keys are separated by dots: `changelog.path`
+    pub fn value_at(&self, key: &str) -> Option<serde_json::Value> {
+        let settings = serde_json::to_value(self).ok()?;
+        key.split('.')
+            .try_fold(&settings, |value, key| value.get(key))
+            .cloned()
+    }
+
+    /// The pre-release channel of a branch, the longest matching pattern wins
+    pub(crate) fn channel(&self, branch: &str) -> Option<&str> {
+        branch_setting(&self.channels, branch)
+    }
+
+    /// The exclusive version ceiling of a maintenance branch, the longest matching pattern wins
+    pub(crate) fn maintenance_ceiling(&self, branch: &str) -> Option<Result<Version, String>> {
+        branch_setting(&self.maintenance_branches, branch).map(|ceiling| {
+            Version::parse(ceiling)
+                .map_err(|err| format!("invalid maintenance ceiling '{}': {}", ceiling, err))
+        })
+    }
+
+    /// The identity of the release commits, `None` to use the git config identity
+    #[cfg(feature = "git")]
+    pub fn release_author(&self) -> Option<Result<Identity, String>> {
+        self.release_author.as_deref().map(str::parse)
+    }
+
+    /// These settings overridden by the `[profile.<name>]` table: tables are merged key by key,
+    /// any other value, arrays included, replaces the original one
+    pub fn with_profile(&self, name: &str) -> Result<Settings, SettingError> {
+        self.apply_profile(name)
+            .map_err(|message| SettingError::from(ConfigError::Message(message)))
+    }
+
+    fn apply_profile(&self, name: &str) -> Result<Settings, String> {
+        let overlay = self
+            .profile
+            .get(name)
+            .ok_or_else(|| format!("unknown settings profile '{}'", name))?;
+
+        let mut settings = serde_json::to_value(self).map_err(|err| err.to_string())?;
+        merge_json(&mut settings, overlay);
+
+        serde_json::from_value(settings).map_err(|err| format!("profile.{}: {}", name, err))
+    }
+
+    /// The JSON schema of `cog.toml`, editors use it to complete and validate the settings
+    pub fn schema() -> RootSchema {
+        schema_for!(Settings)
+    }
+
+    pub(crate) fn ignored_tags(&self) -> Result<GlobSet, globset::Error> {
+        let mut ignored_tags = GlobSetBuilder::new();
+        for pattern in &self.tag.ignore {
+            ignored_tags.add(Glob::new(pattern)?);
+        }
+
+        ignored_tags.build()
+    }
+
+    pub(crate) fn commit_paths(&self) -> Result<Option<GlobSet>, globset::Error> {
+        let Some(patterns) = &self.bump.commit_paths else {
+            return Ok(None);
+        };
+
+        let mut commit_paths = GlobSetBuilder::new();
+        for pattern in patterns {
+            commit_paths.add(Glob::new(pattern)?);
+        }
+
+        commit_paths.build().map(Some)
+    }
+
+    pub fn get_hooks(&self, hook_type: HookType) -> &Vec<HookConfig> {
         match hook_type {
             HookType::PreBump => &self.pre_bump_hooks,
             HookType::PostBump => &self.post_bump_hooks,
+            HookType::PreCommit => &self.pre_commit_hooks,
+            HookType::PostCommit => &self.post_commit_hooks,
         }
     }
 
-    pub fn get_profile_hook(&self, profile: &str, hook_type: HookType) -> &Vec<String> {
-        let profile = self
-            .bump_profiles
-            .get(profile)
-            .expect("Bump profile not found");
-        match hook_type {
-            HookType::PreBump => &profile.pre_bump_hooks,
-            HookType::PostBump => &profile.post_bump_hooks,
+    /// The hooks of a bump profile, the hooks of the profiles it extends included
+    pub fn get_profile_hook(
+        &self,
+        profile: &str,
+        hook_type: HookType,
+    ) -> Result<Vec<HookConfig>, SettingError> {
+        self.resolve_profile_hooks(profile, hook_type, &mut vec![])
+            .map_err(|message| SettingError::from(ConfigError::Message(message)))
+    }
+
+    // `extending` holds the profiles extending this one, to detect cycles
+    fn resolve_profile_hooks(
+        &self,
+        name: &str,
+        hook_type: HookType,
+        extending: &mut Vec<String>,
+    ) -> Result<Vec<HookConfig>, String> {
+        let profile = match self.bump_profiles.get(name) {
+            Some(profile) => profile,
+            None if name == "default" => return Ok(self.get_hooks(hook_type).clone()),
+            None => return Err(format!("unknown bump profile '{}'", name)),
+        };
+
+        let (prepended, appended) = match hook_type {
+            HookType::PreBump => (&profile.prepend_pre_bump_hooks, &profile.pre_bump_hooks),
+            HookType::PostBump => (&profile.prepend_post_bump_hooks, &profile.post_bump_hooks),
+            // Bump profiles only override bump hooks
+            HookType::PreCommit | HookType::PostCommit => {
+                return Ok(self.get_hooks(hook_type).clone())
+            }
+        };
+
+        if extending.iter().any(|extending| extending == name) {
+            extending.push(name.to_string());
+            return Err(format!(
+                "bump profiles extend each other: {}",
+                extending.join(" -> ")
+            ));
         }
+
+        extending.push(name.to_string());
+        let base = match &profile.extends {
+            Some(base) => self.resolve_profile_hooks(base, hook_type, extending)?,
+            None => vec![],
+        };
+
+        Ok(prepended
+            .iter()
+            .chain(&base)
+            .chain(appended)
+            .cloned()
+            .collect())
     }
 
+    #[cfg(feature = "git")]
     pub fn get_template_context(&self) -> Option<RemoteContext> {
         let remote = self.changelog.remote.as_ref().cloned();
 
@@ -181,9 +1337,26 @@ impl Settings {
 
         let owner = self.changelog.owner.as_ref().cloned();
 
-        RemoteContext::try_new(remote, repository, owner)
+        let context = RemoteContext::try_new(remote, repository, owner)?;
+        match self.changelog.provider {
+            Some(provider) => Some(context.with_provider(provider)),
+            None => Some(context),
+        }
+    }
+
+    /// The template of an additional changelog file, the changelog template when it has none
+    #[cfg(feature = "git")]
+    pub fn get_changelog_file_template(
+        &self,
+        file: &ChangelogFile,
+    ) -> Result<Template, ChangelogError> {
+        match &file.template {
+            Some(template) => Template::from_arg(template, self.get_template_context()),
+            None => self.get_changelog_template(),
+        }
     }
 
+    #[cfg(feature = "git")]
     pub fn get_changelog_template(&self) -> Result<Template, ChangelogError> {
         let context = self.get_template_context();
         let template = self.changelog.template.as_deref().unwrap_or("default");
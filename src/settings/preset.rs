@@ -0,0 +1,72 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::settings::{HookConfig, Settings};
+
+/// Settings tailored to a project ecosystem, written by `cog init --template <preset>`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Preset {
+    /// Versioned in `Cargo.toml` with `cargo set-version` from cargo-edit
+    Rust,
+    /// Versioned in `package.json` with `npm version`
+    Node,
+    /// Versioned in `pyproject.toml` with `poetry version`
+    Python,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 3] = [Preset::Rust, Preset::Node, Preset::Python];
+
+    pub fn settings(&self) -> Settings {
+        let (manifest, pre_bump_hooks) = match self {
+            Preset::Rust => (
+                "Cargo.toml",
+                vec!["cargo test", "cargo set-version {{version}}"],
+            ),
+            Preset::Node => (
+                "package.json",
+                vec!["npm test", "npm version {{version}} --no-git-tag-version"],
+            ),
+            Preset::Python => ("pyproject.toml", vec!["poetry version {{version}}"]),
+        };
+
+        let hook = |command: &str| HookConfig {
+            command: command.to_string(),
+            ..Default::default()
+        };
+
+        Settings {
+            tag_prefix: Some("v".to_string()),
+            version_source: Some(PathBuf::from(manifest)),
+            pre_bump_hooks: pre_bump_hooks.into_iter().map(hook).collect(),
+            post_bump_hooks: vec![hook("git push"), hook("git push --tags")],
+            ..Default::default()
+        }
+    }
+}
+
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(preset: &str) -> Result<Self, Self::Err> {
+        match preset {
+            "rust" => Ok(Preset::Rust),
+            "node" => Ok(Preset::Node),
+            "python" => Ok(Preset::Python),
+            other => Err(format!("unknown preset '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let preset = match self {
+            Preset::Rust => "rust",
+            Preset::Node => "node",
+            Preset::Python => "python",
+        };
+
+        f.write_str(preset)
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Formatter;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::conventional::commit::Commit;
+
+/// Commit counts over a range, as returned by [`CocoGitto::stats`]
+///
+/// [`CocoGitto::stats`]: crate::CocoGitto::stats
+#[derive(Debug, Default, Serialize, Eq, PartialEq)]
+pub struct CommitStats {
+    pub total: usize,
+    pub non_compliant: usize,
+    pub breaking_changes: usize,
+    pub types: BTreeMap<String, usize>,
+    pub scopes: BTreeMap<String, usize>,
+    pub authors: BTreeMap<String, usize>,
+}
+
+impl CommitStats {
+    // Non compliant commits are only counted in the total and by author
+    pub(crate) fn add(&mut self, commit: Option<&Commit>, author: String) {
+        self.total += 1;
+        *self.authors.entry(author).or_default() += 1;
+
+        let Some(commit) = commit else {
+            self.non_compliant += 1;
+            return;
+        };
+
+        let message = &commit.message;
+        *self
+            .types
+            .entry(message.commit_type.to_string())
+            .or_default() += 1;
+
+        if let Some(scope) = &message.scope {
+            *self.scopes.entry(scope.clone()).or_default() += 1;
+        }
+
+        if message.is_breaking_change {
+            self.breaking_changes += 1;
+        }
+    }
+}
+
+impl fmt::Display for CommitStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {} ({} non compliant, {} breaking changes)",
+            "Commits:".green().bold(),
+            self.total,
+            self.non_compliant,
+            self.breaking_changes
+        )?;
+
+        for (title, counts) in [
+            ("Types", &self.types),
+            ("Scopes", &self.scopes),
+            ("Authors", &self.authors),
+        ] {
+            if counts.is_empty() {
+                continue;
+            }
+
+            writeln!(f, "\n{}", title.green().bold())?;
+            let width = counts
+                .keys()
+                .map(|key| key.chars().count())
+                .max()
+                .unwrap_or(0);
+            // Most frequent first, ties in alphabetical order
+            let mut counts: Vec<_> = counts.iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(a.1));
+            for (key, count) in counts {
+                writeln!(f, "  {:width$}  {}", key, count, width = width)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stats::CommitStats;
+    use anyhow::Result;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn display_stats_sorted_by_count() -> Result<()> {
+        // Arrange
+        let stats = CommitStats {
+            total: 4,
+            non_compliant: 1,
+            breaking_changes: 1,
+            types: [("feat".to_string(), 1), ("fix".to_string(), 2)].into(),
+            scopes: Default::default(),
+            authors: [("Tom".to_string(), 4)].into(),
+        };
+
+        // Act
+        let display = stats.to_string();
+
+        // Assert
+        assert_that!(display.as_str()).contains("4 (1 non compliant, 1 breaking changes)");
+        assert_that!(display.as_str()).contains("\n  fix   2\n  feat  1\n");
+        assert_that!(display.as_str()).contains("\n  Tom  4\n");
+        assert_that!(display.as_str()).does_not_contain("Scopes");
+        Ok(())
+    }
+}
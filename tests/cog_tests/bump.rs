@@ -4,10 +4,15 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
-use indoc::indoc;
+use cmd_lib::{run_cmd, run_fun};
+use indoc::{formatdoc, indoc};
+use predicates::prelude::PredicateBooleanExt;
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
 use std::path::Path;
+use std::thread;
 
 #[sealed_test]
 fn auto_bump_from_start_ok() -> Result<()> {
@@ -27,6 +32,112 @@ fn auto_bump_from_start_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn auto_bump_runs_cog_from_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let cog = assert_cmd::cargo::cargo_bin("cog");
+    let config = format!(
+        "post_bump_hooks = [\"{} changelog --at {{{{version}}}} > notes.md\"]",
+        cog.display()
+    );
+    git_add(&config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.0")?;
+    let notes = std::fs::read_to_string("notes.md")?;
+    assert_that!(notes.as_str()).contains("feature");
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_from_version_source_ok() -> Result<()> {
+    git_init()?;
+    git_add(
+        "[package]\nname = \"app\"\nversion = \"1.2.0\"",
+        "Cargo.toml",
+    )?;
+    git_add("version_source = \"Cargo.toml\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat(taef): feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("1.3.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_with_type_alias() -> Result<()> {
+    git_init()?;
+    git_add("[commit_type_aliases]\nfeature = \"feat\"\n", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feature: refunds")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_from_legacy_tag_prefix() -> Result<()> {
+    git_init()?;
+    git_add(
+        "tag_prefix = \"v\"\nlegacy_tag_prefixes = [\"release-\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("release-1.0.0")?;
+    git_commit("feat(taef): feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("v1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_skips_ignored_tags() -> Result<()> {
+    git_init()?;
+    git_add("[tag]\nignore = [\"*-nightly*\"]", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat(taef): feature")?;
+    git_tag("2.0.0-nightly.1")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .assert()
+        .success();
+
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 fn auto_bump_minor_from_latest_tag() -> Result<()> {
     git_init()?;
@@ -73,6 +184,129 @@ fn auto_bump_dry_run_from_latest_tag() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn bump_profile_extends_another_profile() -> Result<()> {
+    git_init()?;
+
+    let config = indoc! {
+        r#"pre_bump_hooks = ["cargo test"]
+        post_bump_hooks = ["git push"]
+
+        [bump_profiles.release]
+        extends = "default"
+        prepend_pre_bump_hooks = ["cargo fmt --check"]
+        pre_bump_hooks = ["cargo set-version {{version}}"]
+
+        [bump_profiles.publish]
+        extends = "release"
+        post_bump_hooks = ["cargo publish"]
+        "#
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--dry-run", "--show-hooks"])
+        .args(["--hook-profile", "publish"])
+        .assert()
+        .success()
+        .stdout(indoc! {
+            "1.1.0
+            pre-bump: cargo fmt --check
+            pre-bump: cargo test
+            pre-bump: cargo set-version 1.1.0
+            post-bump: git push
+            post-bump: cargo publish
+            "
+        });
+
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_profiles_extending_each_other_are_invalid() -> Result<()> {
+    git_init()?;
+    git_add(
+        "[bump_profiles.a]\nextends = \"b\"\n[bump_profiles.b]\nextends = \"a\"",
+        "cog.toml",
+    )?;
+
+    Command::cargo_bin("cog")?
+        .args(["config", "validate"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "bump_profiles.a.extends: bump profiles extend each other: a -> b -> a",
+        ));
+
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_dry_run_show_hooks() -> Result<()> {
+    git_init()?;
+
+    let config = indoc! {
+        "pre_bump_hooks = [\"cargo set-version {{version}}\"]
+        post_bump_hooks = [\"git push origin {{version}}\"]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        .arg("--dry-run")
+        .arg("--show-hooks")
+        .assert()
+        .success()
+        .stdout(indoc! {
+            "1.1.0
+            pre-bump: cargo set-version 1.1.0
+            post-bump: git push origin 1.1.0
+            "
+        });
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn auto_bump_dry_run_show_hooks_json() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "pre_bump_hooks = [\"cargo set-version {{version}}\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    let output = Command::cargo_bin("cog")?
+        .args(["--output-format", "json", "bump", "--auto"])
+        .args(["--dry-run", "--show-hooks"])
+        .output()?;
+
+    // Assert
+    assert_that!(output.status.success()).is_true();
+    let outcome: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_that!(outcome["new_tag"]).is_equal_to(serde_json::json!("1.1.0"));
+    assert_that!(outcome["hooks"]).is_equal_to(serde_json::json!([
+        { "type": "pre-bump", "command": "cargo set-version 1.1.0" },
+    ]));
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 fn auto_bump_major_from_latest_tag() -> Result<()> {
     git_init()?;
@@ -301,3 +535,1276 @@ fn bump_with_profile_hook() -> Result<()> {
     assert_tag_exists("1.0.1")?;
     Ok(())
 }
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_hook_table_cwd_and_env() -> Result<()> {
+    // Arrange
+    git_init()?;
+    std::fs::create_dir("scripts")?;
+    git_add("", "scripts/.keep")?;
+
+    let config = indoc! {
+        "pre_bump_hooks = [
+            { command = \"echo $COG_NEXT_VERSION > next_version\", cwd = \"scripts\" },
+        ]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--minor")
+        // Assert
+        .assert()
+        .success();
+
+    let next_version = std::fs::read_to_string("scripts/next_version")?;
+    assert_that!(next_version.as_str()).is_equal_to("1.1.0\n");
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_conditional_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    let config = indoc! {
+        "pre_bump_hooks = [
+            { command = \"touch on_master\", only_branches = [\"master\"] },
+            { command = \"touch on_release\", only_branches = [\"release/*\"] },
+            { command = \"touch on_minor\", only_increments = [\"minor\"] },
+            { command = \"touch on_major\", only_increments = [\"major\"] },
+        ]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("on_master")).exists();
+    assert_that!(Path::new("on_minor")).exists();
+    assert_that!(Path::new("on_release")).does_not_exist();
+    assert_that!(Path::new("on_major")).does_not_exist();
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_allowed_hook_failure() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    let config = indoc! {
+        "pre_bump_hooks = [
+            { command = \"exit 1\", allow_failure = true },
+        ]
+        post_bump_hooks = [
+            { command = \"exit 1\", retries = 1, allow_failure = true },
+        ]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_with_captured_hook_output() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    let config = indoc! {
+        "pre_bump_hooks = [
+            { command = \"echo {{version}}\", capture_output = true },
+            { command = \"echo done\", name = \"notify\", capture_output = true },
+        ]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("[echo] 1.1.0"))
+        .stderr(predicates::str::contains("[notify] done"));
+
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn failed_hook_reports_policy() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    let config = indoc! {
+        "pre_bump_hooks = [
+            { command = \"exit 1\", retries = 1, timeout = 10 },
+        ]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "prehook run failed: hook `exit 1` failed: hook failed with status",
+        ))
+        .stderr(predicates::str::contains(
+            "hook policy: timeout: 10s, retries: 1, allow_failure: false",
+        ));
+
+    assert_tag_does_not_exist("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_sends_webhook_notification() -> Result<()> {
+    // Arrange
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/release", listener.local_addr()?);
+
+    let server = serve_requests(listener, vec![""]);
+
+    git_init()?;
+    let config = format!(
+        "[[notifications.webhooks]]\nurl = \"{}\"\ntemplate = \"{{{{ previous_version }}}} -> {{{{ version }}}}\"\n",
+        url
+    );
+    git_add(&config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    let requests = server.join().expect("webhook server panicked")?;
+    assert_that!(requests[0].1.as_str()).is_equal_to("1.0.0 -> 1.1.0");
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+// Answer a request with each of the response bodies and return their request line and body
+fn serve_requests(
+    listener: TcpListener,
+    responses: Vec<&'static str>,
+) -> thread::JoinHandle<Result<Vec<(String, String)>>> {
+    thread::spawn(move || -> Result<Vec<(String, String)>> {
+        let mut requests = vec![];
+        for response in responses {
+            let (stream, _) = listener.accept()?;
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line)?;
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line)?;
+                if line.trim().is_empty() {
+                    break;
+                }
+
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse()?;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body)?;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            );
+            reader.get_mut().write_all(response.as_bytes())?;
+            requests.push((request_line.trim().to_string(), String::from_utf8(body)?));
+        }
+
+        Ok(requests)
+    })
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn failed_notification_does_not_abort_bump() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[[notifications.webhooks]]\nurl = \"http://127.0.0.1:1/release\"\nkind = \"slack\"\n",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "Failed to send release notification",
+        ));
+
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_updates_referenced_jira_issues() -> Result<()> {
+    // Arrange
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let server = serve_requests(listener, vec![""; 4]);
+
+    git_init()?;
+    let config = format!(
+        "[integrations.issue_tracker]\nkind = \"jira\"\nurl = \"{}\"\ntransition = \"31\"\n",
+        url
+    );
+    git_add(&config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: PAY-12 add refunds")?;
+    git_commit("fix: rounding\n\nRefs: PAY-13")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("JIRA_TOKEN", "secret")
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Failed").not());
+
+    let requests = server.join().expect("issue tracker server panicked")?;
+    assert_that!(requests).is_equal_to(vec![
+        (
+            "POST /rest/api/2/issue/PAY-13/comment HTTP/1.1".to_string(),
+            r#"{"body":"Released in 1.1.0"}"#.to_string(),
+        ),
+        (
+            "POST /rest/api/2/issue/PAY-13/transitions HTTP/1.1".to_string(),
+            r#"{"transition":{"id":"31"}}"#.to_string(),
+        ),
+        (
+            "POST /rest/api/2/issue/PAY-12/comment HTTP/1.1".to_string(),
+            r#"{"body":"Released in 1.1.0"}"#.to_string(),
+        ),
+        (
+            "POST /rest/api/2/issue/PAY-12/transitions HTTP/1.1".to_string(),
+            r#"{"transition":{"id":"31"}}"#.to_string(),
+        ),
+    ]);
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn bump_closes_and_creates_milestones() -> Result<()> {
+    // Arrange
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let server = serve_requests(
+        listener,
+        vec![
+            r#"[{"number":3,"title":"1.1.0"},{"number":4,"title":"2.0.0"}]"#,
+            r#"{"number":3}"#,
+            r#"{"number":5}"#,
+        ],
+    );
+
+    git_init()?;
+    let config = formatdoc!(
+        r#"
+        [changelog]
+        remote = "github.com"
+        owner = "cocogitto"
+        repository = "cog"
+
+        [integrations.milestones]
+        api_url = "{}"
+        "#,
+        url
+    );
+    git_add(&config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env("GITHUB_TOKEN", "secret")
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Created milestone 1.2.0"));
+
+    let requests = server.join().expect("milestone server panicked")?;
+    assert_that!(requests).is_equal_to(vec![
+        (
+            "GET /repos/cocogitto/cog/milestones?state=open&per_page=100 HTTP/1.1".to_string(),
+            "".to_string(),
+        ),
+        (
+            "PATCH /repos/cocogitto/cog/milestones/3 HTTP/1.1".to_string(),
+            r#"{"state":"closed"}"#.to_string(),
+        ),
+        (
+            "POST /repos/cocogitto/cog/milestones HTTP/1.1".to_string(),
+            r#"{"title":"1.2.0"}"#.to_string(),
+        ),
+    ]);
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_from_subdirectory_writes_changelog_at_root() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    std::fs::create_dir("subdir")?;
+    std::env::set_current_dir("subdir")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("../CHANGELOG.md")).exists();
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_in_linked_worktree() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git worktree add -q -b release ../linked)?;
+    std::env::set_current_dir("../linked")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("CHANGELOG.md")).exists();
+    assert_that!(Path::new("../repo/CHANGELOG.md")).does_not_exist();
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+// Create an `origin` repository with a tag behind two commits and a shallow `clone` of it
+fn shallow_clone(config: &str) -> Result<()> {
+    git_init_and_set_current_path("origin")?;
+    git_add(config, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    git_commit("fix: bug fix")?;
+    std::env::set_current_dir("..")?;
+
+    let origin = format!("file://{}/origin", std::env::current_dir()?.display());
+    run_cmd!(
+        git clone -q --depth 1 $origin clone;
+        cd clone;
+        git config --local user.name "Tom";
+        git config --local user.email "toml.bombadil@themail.org";
+    )?;
+
+    std::env::set_current_dir("clone")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_in_shallow_clone_fails_without_tag() -> Result<()> {
+    // Arrange
+    shallow_clone("")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "no tag found in this shallow clone",
+        ));
+
+    assert_tag_does_not_exist("0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_in_shallow_clone_with_unshallow() -> Result<()> {
+    // Arrange
+    shallow_clone("unshallow = true")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("bump")
+        .arg("--auto")
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("1.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_settings_profile() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            branch_whitelist = ["main"]
+
+            [profile.hotfix]
+            branch_whitelist = ["hotfix/*"]
+
+            [profile.hotfix.changelog]
+            path = "HOTFIX.md"
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git checkout -q -b hotfix/1.0)?;
+    git_commit("fix: bug fix")?;
+
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        .assert()
+        .failure();
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--profile", "hotfix"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("HOTFIX.md")).exists();
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_tag_exists("1.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_on_channel_branch_increments_pre_release() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[channels]\ndevelop = \"beta\"\n\"release/*\" = \"rc\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git checkout -q -b develop)?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        .assert()
+        .success();
+    assert_tag_exists("1.1.0-beta.1")?;
+    git_commit("fix: bug fix")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("1.1.0-beta.2")?;
+
+    run_cmd!(git checkout -q -b release/1.1)?;
+    git_commit("fix: another bug fix")?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        .assert()
+        .success();
+    assert_tag_exists("1.1.0-rc.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_on_maintenance_branch_stays_below_ceiling() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[maintenance_branches]\n\"1.x\" = \"2.0.0\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git branch 1.x)?;
+    git_commit("feat!: breaking change")?;
+    git_tag("2.0.0")?;
+    run_cmd!(git checkout -q 1.x)?;
+    git_commit("fix: backported fix")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("1.0.1")?;
+
+    git_commit("feat!: breaking change")?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "version 2.0.0 is out of the maintenance line of branch '1.x'",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_writes_github_outputs() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    let outputs = std::env::current_dir()?.join("github_output");
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["--output-format", "github", "bump", "--auto"])
+        .env("GITHUB_OUTPUT", &outputs)
+        // Assert
+        .assert()
+        .success();
+
+    let outputs = std::fs::read_to_string(outputs)?;
+    assert_that!(outputs.as_str()).starts_with("version=1.1.0\nprevious_version=1.0.0\n");
+    assert_that!(outputs.as_str()).contains("changelog<<cog_changelog_");
+    assert_that!(outputs.as_str()).contains("feature");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_dry_run_json() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    let output = Command::cargo_bin("cog")?
+        .args(["--output-format", "json", "bump", "--auto", "--dry-run"])
+        .output()?;
+
+    // Assert
+    assert_that!(output.status.success()).is_true();
+    let outcome: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_that!(outcome["new_tag"]).is_equal_to(serde_json::json!("1.1.0"));
+    assert_that!(outcome["increment"]).is_equal_to(serde_json::json!("minor"));
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_json_output_is_not_mixed_with_hooks_output() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc! {r#"
+            pre_bump_hooks = [
+                { command = "echo captured", capture_output = true },
+                "echo inherited",
+            ]
+        "#},
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    let output = Command::cargo_bin("cog")?
+        .args(["--output-format", "json", "bump", "--auto"])
+        .output()?;
+
+    // Assert
+    assert_that!(output.status.success()).is_true();
+    let outcome: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_that!(outcome["new_tag"]).is_equal_to(serde_json::json!("1.1.0"));
+    let stderr = String::from_utf8(output.stderr)?;
+    assert_that!(stderr.as_str()).contains("[echo] captured");
+    assert_that!(stderr.as_str()).contains("inherited");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_release_author() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        r#"release_author = "release-bot <bot@example.org>""#,
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    let identities = cmd_lib::run_fun!(git log -1 --format="%an <%ae>|%cn <%ce>")?;
+    assert_that!(identities)
+        .is_equal_to("release-bot <bot@example.org>|release-bot <bot@example.org>".to_string());
+
+    // Act
+    git_commit("fix: bug fix")?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--author", "Jane Doe <jane@example.org>"])
+        // Assert
+        .assert()
+        .success();
+
+    let author = cmd_lib::run_fun!(git log -1 --format="%an <%ae>")?;
+    assert_that!(author).is_equal_to("Jane Doe <jane@example.org>".to_string());
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_sign_off_setting() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            release_author = "release-bot <bot@example.org>"
+
+            [commit]
+            sign_off = true
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(git_log_head()?).is_equal_to(
+        "chore(version): 0.1.0\n\nSigned-off-by: release-bot <bot@example.org>".to_string(),
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_allow_empty_on_clean_tree() -> Result<()> {
+    // Arrange
+    git_init()?;
+    // The changelog is ignored, the bump changes no tracked file
+    git_add("CHANGELOG.md", ".gitignore")?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        .assert()
+        .failure();
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--allow-empty"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.0")?;
+    assert_that!(git_log_head()?).is_equal_to("chore(version): 0.1.0".to_string());
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_changelog_disabled() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            [changelog]
+            enabled = false
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--allow-empty"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.0")?;
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_changelog_path() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--changelog-path", "docs/CHANGES.md"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(Path::new("docs/CHANGES.md")).exists();
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_writes_additional_changelog_files() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc! {r#"
+            [[changelog.files]]
+            path = "CHANGELOG_USER.md"
+            types = ["feat", "fix"]
+
+            [[changelog.files]]
+            path = "CHANGELOG_FULL.md"
+            template = "full_hash"
+            omit = ["docs"]
+        "#},
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    git_commit("chore: a chore")?;
+    git_commit("docs: some docs")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    let user = std::fs::read_to_string("CHANGELOG_USER.md")?;
+    let full = std::fs::read_to_string("CHANGELOG_FULL.md")?;
+    let main = std::fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(user).contains("a feature");
+    assert_that!(user).does_not_contain("a chore");
+    assert_that!(full).contains("a chore");
+    assert_that!(full).does_not_contain("some docs");
+    assert_that!(main).contains("some docs");
+    assert_that!(git_status()?).contains("nothing to commit");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_refuses_tag_existing_on_remote() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    run_cmd!(
+        git clone -q --bare . ../remote.git;
+        git remote add origin ../remote.git;
+        git tag 0.1.0;
+        git push -q origin 0.1.0;
+        git tag -d 0.1.0;
+    )?;
+    git_add("[tag]\ncheck_remote = true", "cog.toml")?;
+    git_commit("chore: check remote tags")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "tag 0.1.0 already exists on remote origin",
+        ));
+
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    assert_tag_does_not_exist("0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_does_not_check_remote_tags_by_default() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    run_cmd!(
+        git clone -q --bare . ../remote.git;
+        git remote add origin ../remote.git;
+        git tag 0.1.0 HEAD~1;
+        git push -q origin 0.1.0;
+        git tag -d 0.1.0;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_force_tag_ignores_existing_tag() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    run_cmd!(
+        git clone -q --bare . ../remote.git;
+        git remote add origin ../remote.git;
+        git tag 0.1.0 HEAD~1;
+        git push -q origin 0.1.0;
+        git tag -d 0.1.0;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto", "--force-tag"])
+        // Assert
+        .assert()
+        .success();
+
+    let tagged = run_fun!(git rev-list -n 1 0.1.0)?;
+    assert_that!(tagged).is_equal_to(run_fun!(git rev-parse HEAD)?);
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_untracked_files_allowed() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[bump]\ndirty = \"allow-untracked\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    run_cmd!(
+        mkdir build;
+        echo "artifact" > build/artifact;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.0")?;
+    assert_that!(Path::new("build/artifact")).exists();
+    let committed = run_fun!(git show --name-only --format= HEAD)?;
+    assert_that!(committed).is_equal_to("CHANGELOG.md".to_string());
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_untracked_files_allowed_refuses_uncommitted_changes() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[bump]\ndirty = \"allow-untracked\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    git_add("tracked", "tracked")?;
+    git_commit("chore: add a file")?;
+    run_cmd!(
+        echo "changed" > tracked;
+        echo "untracked" > untracked;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("modified: tracked"))
+        .stderr(PredicateBooleanExt::not(predicates::str::contains(
+            "new: untracked",
+        )));
+
+    assert_tag_does_not_exist("0.1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_stashes_and_restores_dirty_changes() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[bump]\ndirty = \"stash\"", "cog.toml")?;
+    git_add("tracked", "tracked")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    run_cmd!(
+        echo "changed" > tracked;
+        echo "untracked" > untracked;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_tag_exists("0.1.0")?;
+    let committed = run_fun!(git show --name-only --format= HEAD)?;
+    assert_that!(committed).is_equal_to("CHANGELOG.md".to_string());
+    assert_that!(std::fs::read_to_string("tracked")?).is_equal_to("changed\n".to_string());
+    assert_that!(Path::new("untracked")).exists();
+    assert_that!(run_fun!(git stash list)?).is_empty();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_failed_hook_restores_dirty_changes_once() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "pre_bump_hooks = [\"exit 1\"]\n[bump]\ndirty = \"stash\"",
+        "cog.toml",
+    )?;
+    git_add("tracked", "tracked")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    run_cmd!(
+        echo "changed" > tracked;
+        echo "untracked" > untracked;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("git stash apply stash@0"));
+
+    assert_tag_does_not_exist("0.1.0")?;
+    assert_that!(std::fs::read_to_string("tracked")?).is_equal_to("changed\n".to_string());
+    assert_that!(Path::new("untracked")).exists();
+    assert_that!(Path::new("CHANGELOG.md")).does_not_exist();
+    let stashes = run_fun!(git stash list --format=%s)?;
+    assert_that!(stashes).is_equal_to("On master: cog_bump_0.1.0".to_string());
+    run_cmd!(git stash apply "stash@{0}")?;
+    assert_that!(Path::new("CHANGELOG.md")).exists();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_only_commits_configured_paths() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc! {r#"
+            pre_bump_hooks = ["echo {{version}} > VERSION", "echo {{version}} > build.log"]
+
+            [bump]
+            commit_paths = ["VERSION"]
+        "#},
+        "cog.toml",
+    )?;
+    git_add("0.0.0", "VERSION")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    let committed = run_fun!(git show --name-only --format= HEAD)?;
+    assert_that!(committed).is_equal_to("CHANGELOG.md\nVERSION".to_string());
+    assert_that!(git_status()?).contains("build.log");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_refuses_branch_behind_its_upstream() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add("[bump]\nrequire_up_to_date = true", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("feat: a feature")?;
+    let branch = run_fun!(git branch --show-current)?;
+    run_cmd!(
+        git clone -q --bare . ../remote.git;
+        git remote add origin ../remote.git;
+        git fetch -q origin;
+        git branch -q -u origin/$branch;
+        git clone -q ../remote.git ../other;
+        cd ../other;
+        git -c user.name=Tom -c user.email=tom@example.org commit -q --allow-empty -m "fix: a remote fix";
+        git push -q;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(format!(
+            "branch '{branch}' is 1 commit(s) behind 'origin/{branch}'"
+        )));
+
+    run_cmd!(git pull -q --ff-only)?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_stabilize_requires_acknowledged_breaking_changes() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add(
+        "[changelog]\nstabilize_note = \"The public API is now stable.\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("0.3.0")?;
+    git_commit("feat!: drop the legacy API")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--stabilize"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("drop the legacy API"))
+        .stderr(predicates::str::contains("--acknowledge-breaking-changes"));
+
+    Command::cargo_bin("cog")?
+        .args(["bump", "--stabilize", "--acknowledge-breaking-changes"])
+        .assert()
+        .success();
+
+    assert_latest_tag("1.0.0")?;
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    assert_that!(changelog.as_str()).contains("The public API is now stable.");
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_stabilize_without_tag_checks_the_first_commit() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("feat!: the first API")?;
+    git_commit("fix: a fix")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--stabilize"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("the first API"));
+
+    assert_tag_does_not_exist("1.0.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_stabilize_fails_on_stable_version() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("chore: init")?;
+    git_tag("1.2.0")?;
+    git_commit("feat: a feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--stabilize"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "1.2.0 is already a stable version",
+        ));
+
+    assert_latest_tag("1.2.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_hooks_read_snapshot_version() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add(
+        "pre_bump_hooks = [\"echo $COG_SNAPSHOT_VERSION > snapshot\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("0.1.0")?;
+    let head = git_commit("feat: a feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    let snapshot = std::fs::read_to_string("snapshot")?;
+    assert_that!(snapshot.trim()).is_equal_to(format!("0.1.0-dev.1+g{}", &head[..7]).as_str());
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_writes_release_manifest() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add(
+        indoc!(
+            "post_bump_hooks = [\"mkdir -p dist\", \"echo built > dist/app.tar.gz\"]
+
+            [bump.manifest]
+            artifacts = [\"dist/*.tar.gz\"]
+            "
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    let from = run_fun!(git rev-parse HEAD)?;
+    git_commit("feat: feature 1")?;
+    git_commit("feat: feature 2")?;
+    git_commit("fix: bug fix")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    let release_commit = run_fun!(git rev-parse HEAD)?;
+    let manifest = std::fs::read_to_string("release-manifest.json")?;
+    let manifest: serde_json::Value = serde_json::from_str(&manifest)?;
+    assert_that!(manifest["version"]).is_equal_to(serde_json::json!("1.1.0"));
+    assert_that!(manifest["previous_tag"]).is_equal_to(serde_json::json!("1.0.0"));
+    assert_that!(manifest["commit"]).is_equal_to(serde_json::json!(release_commit));
+    assert_that!(manifest["range"]["from"]).is_equal_to(serde_json::json!(from));
+    assert_that!(manifest["commit_counts"]).is_equal_to(serde_json::json!({ "feat": 2, "fix": 1 }));
+    assert_that!(manifest["contributors"]).is_equal_to(serde_json::json!(["Tom"]));
+    assert_that!(manifest["artifacts"][0]["path"])
+        .is_equal_to(serde_json::json!("dist/app.tar.gz"));
+    assert_that!(manifest["post_bump_hooks"].as_array().map(Vec::len)).is_equal_to(Some(2));
+    Ok(())
+}
@@ -431,3 +431,365 @@ fn get_changelog_whith_custom_template() -> Result<()> {
     );
     Ok(())
 }
+
+#[sealed_test]
+fn changelog_from_bare_repository() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add("[changelog]\ntemplate = \"full_hash\"\n", "cog.toml")?;
+    git_commit("chore: init")?;
+    let fix = git_commit("fix: a fix")?;
+    let feature = git_commit("feat: a feature")?;
+    std::env::set_current_dir("..")?;
+    run_cmd!(git clone -q --bare repo bare.git)?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("--repository-path")
+        .arg("bare.git")
+        .arg("changelog")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    assert!(changelog.contains(&feature));
+    assert!(changelog.contains(&fix));
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_with_repository_metadata() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[changelog]\nremote = \"github.com\"\nrepository = \"test\"\nowner = \"test\"\n",
+        "cog.toml",
+    )?;
+    let remote_url = "https://github.com/test/test.git";
+    run_cmd!(
+        git remote add origin $remote_url;
+        git config init.defaultBranch main;
+    )?;
+
+    let template = indoc!(
+        "{{ version.tag }} ({{ release_index }}/{{ repository.tags | length }}) on {{ repository.default_branch }}
+        Full diff: {{ previous_compare_url }}{% if next_compare_url %}, next: {{ next_compare_url }}{% endif %}
+        {{ repository.remote_url }} has {{ repository.commit_count }} commits
+        "
+    );
+    fs::write("template.md", template)?;
+
+    let init = git_commit("chore: init")?;
+    git_commit("feat: feature 1")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: bug fix 1")?;
+    git_tag("1.0.1")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        .arg("-t")
+        .arg("template.md")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "1.0.1 (2/2) on main
+            Full diff: https://github.com/test/test/compare/1.0.0..1.0.1
+            https://github.com/test/test.git has 3 commits
+
+            - - -
+
+            1.0.0 (1/2) on main
+            Full diff: https://github.com/test/test/compare/{init}..1.0.0, next: https://github.com/test/test/compare/1.0.0..1.0.1
+            https://github.com/test/test.git has 3 commits
+
+            ",
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_with_template_extending_default() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let template = indoc!(
+        "{% extends \"default\" %}
+        {% block header -%}
+        ### Release {{ version.tag }}
+        {% endblock header %}
+        {% block footer %}Thanks!{% endblock footer %}"
+    );
+    fs::write("template.md", template)?;
+
+    git_commit("chore: init")?;
+    let feature = git_commit("feat: feature 1")?;
+    git_tag("1.0.0")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .args(["changelog", "--at", "1.0.0", "-t", "template.md"])
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "### Release 1.0.0
+            #### Features
+            - feature 1 - ({feature}) - Tom
+            Thanks!
+            ",
+            feature = &feature[0..7],
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_with_trailers() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            "[changelog.trailers]
+            category = \"Changelog\"
+            notes = [\"Note\"]
+
+            [changelog.trailers.categories]
+            security = \"Security Fixes\"
+            "
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feat: internal feature\n\nChangelog: hidden")?;
+    let fix = git_commit(
+        "fix: sanitize inputs\n\nChangelog: security\nNote: upgrade as soon as possible",
+    )?;
+    let feat = git_commit("feat: feature 1")?;
+    git_tag("1.0.0")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    let fix = &fix[0..7];
+    let feat = &feat[0..7];
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "## 1.0.0 - {today}
+            #### Features
+            - feature 1 - ({feat}) - Tom
+            #### Security Fixes
+            - sanitize inputs - ({fix}) - Tom
+              - upgrade as soon as possible
+
+            ",
+            today = Utc::now().date_naive()
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_with_overrides() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    let excluded = git_commit("feat: wip")?;
+    let feat = git_commit("feat: featur 1")?;
+    git_tag("1.0.0")?;
+    fs::create_dir(".cog")?;
+    fs::write(
+        ".cog/changelog-overrides.toml",
+        formatdoc!(
+            "[{}]
+            summary = \"feature 1\"
+            notes = [\"thanks to the early testers\"]
+
+            [{}]
+            exclude = true
+            ",
+            &feat[0..7],
+            excluded
+        ),
+    )?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    let feat = &feat[0..7];
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "## 1.0.0 - {today}
+            #### Features
+            - feature 1 - ({feat}) - Tom
+              - thanks to the early testers
+
+            ",
+            today = Utc::now().date_naive()
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_merges_aliased_types() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[commit_type_aliases]\nfeature = \"feat\"\n", "cog.toml")?;
+    git_commit("chore: init")?;
+    let feature = git_commit("feature: feature 1")?;
+    let feat = git_commit("feat: feature 2")?;
+    git_tag("1.0.0")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "## 1.0.0 - {today}
+            #### Features
+            - feature 2 - ({feat}) - Tom
+            - feature 1 - ({feature}) - Tom
+
+            ",
+            feat = &feat[0..7],
+            feature = &feature[0..7],
+            today = Utc::now().date_naive()
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_marks_cherry_picked_duplicates() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[changelog.dedupe]\nby = \"original-sha\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    let fix = git_commit("fix: bug fix 1")?;
+    git_tag("1.0.0")?;
+    let cherry_pick = git_commit(&format!(
+        "fix: bug fix 1\n\n(cherry picked from commit {})",
+        fix
+    ))?;
+    git_tag("1.0.1")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    let fix = &fix[0..7];
+    let cherry_pick = &cherry_pick[0..7];
+    assert_eq!(
+        changelog,
+        formatdoc!(
+            "## 1.0.1 - {today}
+            #### Bug Fixes
+            - bug fix 1 - ({cherry_pick}) - Tom - duplicate of {fix}
+
+            - - -
+
+            ## 1.0.0 - {today}
+            #### Bug Fixes
+            - bug fix 1 - ({fix}) - Tom
+
+            ",
+            today = Utc::now().date_naive()
+        )
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_collapses_duplicated_patches() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[changelog.dedupe]\nby = \"patch-id\"\nmode = \"collapse\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_add("fixed", "file")?;
+    let fix = git_commit("fix: bug fix 1")?;
+    git_tag("1.0.0")?;
+    run_cmd!(git rm -q file)?;
+    git_commit("revert: bug fix 1")?;
+    git_add("fixed", "file")?;
+    git_commit("fix: bug fix 1, again")?;
+    git_add("feature", "feature")?;
+    let feat = git_commit("feat: feature 1")?;
+    git_tag("1.1.0")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .arg("changelog")
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    assert!(!changelog.contains("again"));
+    assert!(changelog.contains(&fix[0..7]));
+    assert!(changelog.contains(&feat[0..7]));
+    Ok(())
+}
+
+#[sealed_test]
+fn get_changelog_of_paths() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    run_cmd!(mkdir -p src/parser/lexer)?;
+    git_add("parser", "src/parser/lib.rs")?;
+    let parser = git_commit("feat(parser): add parser")?;
+    git_add("readme", "README.md")?;
+    let readme = git_commit("docs: add readme")?;
+    git_tag("1.0.0")?;
+    git_add("lexer", "src/parser/lexer/mod.rs")?;
+    let lexer = git_commit("fix(parser): handle empty input")?;
+
+    // Act
+    let changelog = Command::cargo_bin("cog")?
+        .args(["changelog", "--path", "src/parser"])
+        // Assert
+        .assert()
+        .success();
+
+    let changelog = String::from_utf8(changelog.get_output().stdout.clone())?;
+    assert!(changelog.contains(&parser[0..7]));
+    assert!(changelog.contains(&lexer[0..7]));
+    assert!(!changelog.contains(&readme[0..7]));
+    Ok(())
+}
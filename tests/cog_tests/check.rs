@@ -2,7 +2,8 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::Command;
-use predicates::prelude::predicate;
+use cmd_lib::run_cmd;
+use predicates::prelude::{predicate, PredicateBooleanExt};
 use sealed_test::prelude::*;
 
 #[sealed_test]
@@ -23,6 +24,48 @@ fn cog_check_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn cog_check_quiet() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("-q")
+        .arg("check")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_colors() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .env("CLICOLOR_FORCE", "1")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("\u{1b}["));
+
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .env("CLICOLOR_FORCE", "1")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("\u{1b}[").not());
+    Ok(())
+}
+
 #[sealed_test]
 fn cog_check_failure() -> Result<()> {
     // Arrange
@@ -83,3 +126,317 @@ fn cog_check_from_latest_tag_failure() -> Result<()> {
         .stderr(predicate::str::contains("Found 1 non compliant commits"));
     Ok(())
 }
+
+#[sealed_test]
+fn check_with_repository_option() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add(
+        "[commit_types]\nwip = { changelog_title = \"WIP\" }\n",
+        "cog.toml",
+    )?;
+    git_commit("wip: a custom commit type")?;
+    std::env::set_current_dir("..")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("--repository-path")
+        .arg("repo")
+        .arg("check")
+        // Assert
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn check_from_latest_tag_in_shallow_clone_fails() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("origin")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    git_commit("fix: bug fix")?;
+    std::env::set_current_dir("..")?;
+    let origin = format!("file://{}/origin", std::env::current_dir()?.display());
+    run_cmd!(git clone -q --depth 1 $origin clone)?;
+    std::env::set_current_dir("clone")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .arg("--from-latest-tag")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("shallow clone"));
+
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_with_commit_cache() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("cache = true", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("toto: errored commit")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?.arg("check").assert().failure();
+
+    // Assert
+    let cache = std::fs::read_to_string(".git/cog-cache/commits.json")?;
+    assert!(cache.contains("feature"));
+    assert!(!cache.contains("errored commit"));
+
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Found 1 non compliant commits"));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_github_annotations() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("not conventional")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        .env("GITHUB_ACTIONS", "true")
+        // Assert
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with(
+            "::error title=Non compliant commit ",
+        ))
+        .stdout(predicate::str::contains("%3A not conventional::"));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_junit_report() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("not conventional")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["check", "--output", "junit"])
+        // Assert
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            r#"<testsuite name="cog check" tests="2" failures="1">"#,
+        ))
+        .stdout(predicate::str::contains("chore: init\"/>"))
+        .stdout(predicate::str::contains(" not conventional\">"));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_codequality_report() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("not conventional")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["check", "--output", "codequality"])
+        // Assert
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            r#""check_name": "conventional-commit""#,
+        ))
+        .stdout(predicate::str::contains(
+            r#""description": "not conventional: "#,
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_json() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("not conventional")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["check", "--output-format", "json"])
+        // Assert
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""errors": 1"#))
+        .stdout(predicate::str::contains(r#""summary": "chore: init""#))
+        .stdout(predicate::str::contains(r#""error": null"#));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_requires_ticket_references() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint.ticket_reference]\npattern = \"[A-Z]+-\\\\d+\"\ntypes = [\"feat\", \"fix\"]\n",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feat(PAY-12): refunds")?;
+    git_commit("fix: rounding")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'fix: rounding'"))
+        .stderr(predicate::str::contains(
+            "No ticket reference matching `[A-Z]+-\\d+` in the scope, body or footers (ticket-reference)",
+        ))
+        .stderr(predicate::str::contains("refunds").not());
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_flags_type_aliases() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[commit_type_aliases]\nfeature = \"feat\"\n\n[lint]\ncanonical_types = true\n",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("feature: refunds")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Commit type `feature` is an alias of `feat` (canonical-type)",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_reports_misspellings() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[lint]\nspellcheck = true\n", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_commit("fix: recieve webhooks")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Misspelled word `recieve` in the summary, did you mean `receive`? (spelling)",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_succeeds_with_warnings() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint]\nspellcheck = true\nbanned_words = [\"wip\"]\n\n[lint.severities]\nspelling = \"warning\"\nbanned-word = \"off\"\n",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_commit("fix: recieve webhooks, wip")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Found 1 commits with warnings"))
+        .stderr(predicate::str::contains("Banned word").not());
+
+    Command::cargo_bin("cog")?
+        .args(["check", "--output-format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""errors": 0"#))
+        .stdout(predicate::str::contains(r#""warnings": 1"#))
+        .stdout(predicate::str::contains(r#""severity": "warning""#));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_runs_external_rules() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "grep -q 'Reviewed-by:' || { echo 'Missing Reviewed-by trailer' >&2; exit 1; }\n",
+        "check-commit.sh",
+    )?;
+    git_add("[lint]\nexternal = [\"sh check-commit.sh\"]\n", "cog.toml")?;
+    git_commit("chore: init\n\nReviewed-by: Tom")?;
+    git_commit("feat: refunds")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("check")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Found 1 non compliant commits"))
+        .stderr(predicate::str::contains(
+            "Missing Reviewed-by trailer (external)",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn cog_check_staged_requires_scope_of_staged_files() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint.scope_paths]\napi = [\"services/api/**\"]\nweb = [\"services/web/**\"]\n",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    run_cmd!(
+        mkdir -p services/api;
+        echo refunds > services/api/refunds.rs;
+        git add services;
+        echo "feat(web): refunds" > message;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["check", "--staged", "--file", "message"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No staged file belongs to scope `web`, the staged files belong to `api` (staged-scope)",
+        ));
+
+    run_cmd!(echo "feat(api): refunds" > .git/COMMIT_EDITMSG)?;
+    Command::cargo_bin("cog")?
+        .args(["check", "--staged"])
+        .assert()
+        .success();
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::helpers::*;
 
 use anyhow::Result;
 use assert_cmd::prelude::*;
+use cmd_lib::{run_cmd, run_fun};
 use indoc::{formatdoc, indoc};
 use pretty_assertions::assert_eq;
 use sealed_test::prelude::*;
@@ -27,6 +28,47 @@ fn commit_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn commit_with_repository_option_after_subcommand() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_add(
+        "[commit_types]\nwip = { changelog_title = \"WIP\" }\n",
+        "cog.toml",
+    )?;
+    std::env::set_current_dir("..")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["commit", "wip", "a custom commit type"])
+        .args(["-C", "repo"])
+        // Assert
+        .assert()
+        .success();
+
+    let message = run_fun!(git -C repo log -1 --format=%s)?;
+    assert_eq!(message, "wip: a custom commit type");
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_with_unknown_type_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("content", "test_file")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["commit", "wip", "a custom commit type"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "invalid value 'wip' for '<TYPE>'",
+        ));
+    Ok(())
+}
+
 #[sealed_test]
 fn commit_fail_if_not_a_repository() -> Result<()> {
     // Act
@@ -130,3 +172,295 @@ fn empty_commit_err() -> Result<()> {
 
     Ok(())
 }
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn commit_runs_commit_hooks() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    let config = indoc! {
+        "pre_commit_hooks = [\"echo {{message}} > pre-commit.txt\"]
+        post_commit_hooks = [\"echo $COG_COMMIT_MESSAGE > post-commit.txt\"]
+        "
+    };
+
+    git_add(config, "cog.toml")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        .arg("scope")
+        // Assert
+        .assert()
+        .success();
+
+    let pre_commit = std::fs::read_to_string("pre-commit.txt")?;
+    let post_commit = std::fs::read_to_string("post-commit.txt")?;
+    assert_eq!(pre_commit, "feat(scope): this is a commit message\n");
+    assert_eq!(post_commit, "feat(scope): this is a commit message\n");
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn failed_pre_commit_hook_aborts_commit() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("pre_commit_hooks = [\"exit 1\"]", "cog.toml")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        // Assert
+        .assert()
+        .failure();
+
+    assert!(git_log_head().is_err());
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_no_gpg_sign_overrides_git_config() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("content", "test_file")?;
+    run_cmd!(
+        git config --local commit.gpgSign true;
+        git config --local gpg.program false;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        .arg("--no-gpg-sign")
+        // Assert
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_signed_with_configured_gpg_program() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("content", "test_file")?;
+    run_cmd!(
+        git config --local commit.gpgSign true;
+        git config --local gpg.program false;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("failed to sign git object"));
+
+    Ok(())
+}
+
+#[sealed_test]
+#[cfg(target_os = "linux")]
+fn commit_edit_follows_git_config() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("content", "test_file")?;
+    std::fs::write("editor.sh", "cp \"$1\" edited.txt")?;
+    std::fs::write("template.txt", "Template body\n")?;
+    run_cmd!(
+        git config --local core.editor "sh editor.sh";
+        git config --local commit.template template.txt;
+        git config --local commit.verbose true;
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .env_remove("GIT_EDITOR")
+        .env("EDITOR", "false")
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        .arg("--edit")
+        // Assert
+        .assert()
+        .success();
+
+    let edited = std::fs::read_to_string("edited.txt")?;
+    assert!(edited.contains("Template body"));
+    assert!(edited.contains("+content"));
+
+    let message = git_log_head()?;
+    assert!(message.starts_with("feat: this is a commit message\n\nTemplate body"));
+    assert!(!message.contains("+content"));
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_with_author() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("content", "test_file")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        .arg("--author")
+        .arg("Jane Doe <jane@example.org>")
+        // Assert
+        .assert()
+        .success();
+
+    let author = cmd_lib::run_fun!(git log -1 --format="%an <%ae>")?;
+    assert_eq!(author, "Jane Doe <jane@example.org>");
+
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        .arg("--author")
+        .arg("Jane Doe")
+        .assert()
+        .failure();
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_with_signoff() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("content", "test_file")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("this is a commit message")
+        .arg("--author")
+        .arg("Jane Doe <jane@example.org>")
+        .arg("--signoff")
+        // Assert
+        .assert()
+        .success();
+
+    assert_eq!(
+        git_log_head()?,
+        "feat: this is a commit message\n\nSigned-off-by: Jane Doe <jane@example.org>"
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_allow_empty() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("chore")
+        .arg("trigger the release pipeline")
+        .arg("--allow-empty")
+        // Assert
+        .assert()
+        .success();
+
+    assert_eq!(git_log_head()?, "chore: trigger the release pipeline");
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_on_denied_branch_fails_when_checked() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "branch_denylist = [\"master\"]\n[commit]\ncheck_branch = true",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_add("content", "test_file")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["commit", "chore", "add a file"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "matches the denied pattern 'master', commit is not allowed",
+        ));
+
+    Command::cargo_bin("cog")?
+        .args([
+            "commit",
+            "chore",
+            "add a file",
+            "--ignore-branch-protection",
+        ])
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_requires_ticket_reference() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint.ticket_reference]\npattern = \"[A-Z]+-\\\\d+\"\n",
+        "cog.toml",
+    )?;
+    git_add("content", "test_file")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("add refunds")
+        // Assert
+        .assert()
+        .failure();
+
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feat")
+        .arg("add refunds")
+        .arg("PAY-12")
+        // Assert
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_with_type_alias_writes_canonical_type() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[commit_type_aliases]\nfeature = \"feat\"\n", "cog.toml")?;
+    git_add("content", "test_file")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("commit")
+        .arg("feature")
+        .arg("add refunds")
+        // Assert
+        .assert()
+        .success();
+
+    let message = cmd_lib::run_fun!(git log -1 --format=%s)?;
+    assert_eq!(message, "feat: add refunds");
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::predicate;
+use sealed_test::prelude::*;
+
+#[sealed_test]
+fn complete_scopes_most_used_first() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("feat(cli): feature")?;
+    git_commit("fix(parser): bug fix")?;
+    git_commit("fix(parser): another bug fix")?;
+    git_commit("not conventional")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["__complete", "scopes"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("parser\ncli\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn complete_types_with_prefix() -> Result<()> {
+    // Arrange
+    git_init()?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["__complete", "types", "fe"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("feat\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn complete_tags_in_range() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    git_tag("1.1.0")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["__complete", "tags", "1.0.0..1."])
+        // Assert
+        .assert()
+        .success()
+        .stdout("1.0.0..1.1.0\n1.0.0..1.0.0\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn generated_completions_call_cog() -> Result<()> {
+    Command::cargo_bin("cog")?
+        .args(["generate-completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("complete -F _cog_dynamic"))
+        .stdout(predicate::str::contains("cog __complete"));
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::predicate;
+use sealed_test::prelude::*;
+
+#[sealed_test]
+fn config_show_merged_settings() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "tag_prefix = \"v\"\n[changelog]\npath = \"CHANGES.md\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "show"])
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tag_prefix = \"v\""))
+        .stdout(predicate::str::contains("[changelog]"))
+        .stdout(predicate::str::contains("path = \"CHANGES.md\""));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_show_json() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "show", "--json"])
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"tag_prefix\": \"v\""));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_get_nested_key() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[changelog]\npath = \"CHANGES.md\"", "cog.toml")?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "get", "changelog.path"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("CHANGES.md\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn config_get_unknown_key_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "get", "changelog.unknown"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown setting 'changelog.unknown'",
+        ));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_validate_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "branch_whitelist = [\"release/**\"]\npre_bump_hooks = [\"echo {{version}}\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "validate"])
+        // Assert
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[sealed_test]
+fn config_validate_reports_invalid_settings() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "branch_whitelist = [\"[main\"]\npre_bump_hooks = [\"echo {{unknown}}\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "validate"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("branch_whitelist:"))
+        .stderr(predicate::str::contains(
+            "pre_bump_hooks[0] `echo {{unknown}}`",
+        ))
+        .stderr(predicate::str::contains("found 2 invalid setting(s)"));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_validate_reports_unknown_lint_rules() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint.severities]\nspelling = \"warning\"\nspellcheck = \"off\"",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "validate"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "lint.severities.spellcheck: unknown lint rule",
+        ))
+        .stderr(predicate::str::contains("found 1 invalid setting(s)"));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_validate_reports_unknown_keys() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefx = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "validate"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field `tag_prefx`"));
+    Ok(())
+}
+
+#[sealed_test]
+fn config_schema_outside_repository() -> Result<()> {
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["config", "schema"])
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"title\": \"Settings\""))
+        .stdout(predicate::str::contains("\"bump_profiles\""));
+    Ok(())
+}
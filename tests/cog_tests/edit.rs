@@ -0,0 +1,161 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use cmd_lib::{run_cmd, run_fun};
+use predicates::prelude::{predicate, PredicateBooleanExt};
+use sealed_test::prelude::*;
+use speculoos::prelude::*;
+
+// An editor rewording every edited commit
+const REWORD: &str = "echo 'fix: reworded' > \"$1\"";
+const FAKE_GPG: &str = "#!/bin/sh\ncat > /dev/null\necho FAKE SIGNATURE";
+
+// A non compliant commit followed by a signed one
+fn signed_history() -> Result<()> {
+    git_init()?;
+    git_add(REWORD, "reword.sh")?;
+    git_add(FAKE_GPG, "fake-gpg")?;
+    run_cmd!(
+        chmod +x fake-gpg;
+        git add fake-gpg;
+        git config --local gpg.program ./fake-gpg;
+    )?;
+    git_commit("chore: init")?;
+    git_add("one", "one")?;
+    git_commit("wip one")?;
+    git_add("two", "two")?;
+    Command::cargo_bin("cog")?
+        .args(["commit", "--sign", "chore", "signed"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn edit_only_rewords_commits_of_the_range() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(REWORD, "reword.sh")?;
+    git_commit("chore: init")?;
+    git_add("one", "one")?;
+    git_commit("wip one")?;
+    run_cmd!(git checkout -q -b feature)?;
+    git_add("two", "two")?;
+    git_commit("wip two")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["edit", "master..HEAD"])
+        .env("GIT_EDITOR", "sh reword.sh")
+        // Assert
+        .assert()
+        .success();
+
+    let summaries = run_fun!(git log --format=%s)?;
+    assert_that!(summaries.as_str()).is_equal_to("fix: reworded\nwip one\nchore: init");
+    Ok(())
+}
+
+#[sealed_test]
+fn edit_rejects_ranges_outside_of_head() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    run_cmd!(git checkout -q -b other)?;
+    git_commit("wip")?;
+    run_cmd!(git checkout -q master)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["edit", "master..other"])
+        .env("GIT_EDITOR", "true")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not in the history of HEAD"));
+    Ok(())
+}
+
+#[sealed_test]
+fn edit_warns_about_dropped_signatures() -> Result<()> {
+    // Arrange
+    signed_history()?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("edit")
+        .env("GIT_EDITOR", "sh reword.sh")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "The signature of these rebased commits will be dropped",
+        ))
+        .stderr(predicate::str::contains("chore: signed"));
+
+    let head = run_fun!(git cat-file -p HEAD)?;
+    assert_that!(head.as_str()).does_not_contain("gpgsig");
+    Ok(())
+}
+
+#[sealed_test]
+fn edit_signs_rebased_commits() -> Result<()> {
+    // Arrange
+    signed_history()?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["edit", "--sign"])
+        .env("GIT_EDITOR", "sh reword.sh")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("will be dropped").not());
+
+    let head = run_fun!(git cat-file -p HEAD)?;
+    let reworded = run_fun!(git cat-file -p HEAD~1)?;
+    assert_that!(head.as_str()).contains("gpgsig FAKE SIGNATURE");
+    assert_that!(reworded.as_str()).contains("gpgsig FAKE SIGNATURE");
+    assert_that!(reworded.as_str()).contains("fix: reworded");
+    Ok(())
+}
+
+#[sealed_test]
+fn edit_skips_commits_older_than_a_merge() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(REWORD, "reword.sh")?;
+    git_commit("chore: init")?;
+    git_add("one", "one")?;
+    git_commit("wip old")?;
+    run_cmd!(git checkout -q -b feature)?;
+    git_add("two", "two")?;
+    git_commit("feat: two")?;
+    run_cmd!(
+        git checkout -q master;
+        git merge -q --no-ff feature -m "chore: merge feature";
+    )?;
+    git_add("three", "three")?;
+    git_commit("wip new")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("edit")
+        .env("GIT_EDITOR", "sh reword.sh")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Skipping 1 errored commits older than merge commit",
+        ))
+        .stderr(predicate::str::contains("wip old"));
+
+    let summaries = run_fun!(git log --format=%s)?;
+    let merges = run_fun!(git log --merges --format=%s)?;
+    assert_that!(summaries.as_str()).starts_with("fix: reworded\nchore: merge feature");
+    assert_that!(summaries.as_str()).contains("wip old");
+    assert_that!(merges.as_str()).is_equal_to("chore: merge feature");
+    Ok(())
+}
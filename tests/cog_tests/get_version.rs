@@ -0,0 +1,111 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::predicate;
+use sealed_test::prelude::*;
+
+#[sealed_test]
+fn get_version_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("v1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        // Assert
+        .assert()
+        .success()
+        .stdout("v1.0.0\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn get_next_version_without_prefix() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("v1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["get-version", "--next", "--no-prefix"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("1.1.0\n");
+
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        .assert()
+        .success()
+        .stdout("v1.0.0\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn get_version_without_tag_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("get-version")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unable to get any tag"));
+    Ok(())
+}
+
+#[sealed_test]
+fn get_version_json() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("v1.0.0")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["--output-format", "json", "get-version"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("{\n  \"tag\": \"v1.0.0\",\n  \"version\": \"1.0.0\"\n}\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn get_snapshot_version() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("v1.2.3")?;
+
+    Command::cargo_bin("cog")?
+        .args(["get-version", "--snapshot"])
+        .assert()
+        .success()
+        .stdout("v1.2.3\n");
+
+    git_commit("feat: feature")?;
+    let head = git_commit("fix: bug fix")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["get-version", "--snapshot", "--no-prefix"])
+        // Assert
+        .assert()
+        .success()
+        .stdout(format!("1.2.3-dev.2+g{}\n", &head[..7]));
+    Ok(())
+}
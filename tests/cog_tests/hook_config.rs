@@ -0,0 +1,29 @@
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use predicates::prelude::predicate;
+
+#[test]
+fn generate_pre_commit_framework_config() -> Result<()> {
+    Command::cargo_bin("cog")?
+        .arg("generate-hook-config")
+        .arg("pre-commit")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("entry: cog verify --file"));
+
+    Ok(())
+}
+
+#[test]
+fn generate_husky_script() -> Result<()> {
+    Command::cargo_bin("cog")?
+        .arg("generate-hook-config")
+        .arg("husky")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("cog verify --file \"$1\""));
+
+    Ok(())
+}
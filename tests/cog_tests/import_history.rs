@@ -0,0 +1,102 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use cmd_lib::run_fun;
+use indoc::indoc;
+use predicates::prelude::predicate;
+use sealed_test::prelude::*;
+use speculoos::prelude::*;
+
+const RULES: &str = indoc! {r#"
+    [[import_history.rules]]
+    pattern = '^\[FIX\] '
+    template = "fix: "
+
+    [[import_history.rules]]
+    pattern = '^\[(?P<module>\w+)\] (?P<summary>.*)'
+    template = "feat(${module}): ${summary}"
+"#};
+
+// A history written with the legacy `[MODULE] summary` convention
+fn legacy_history(rules: &str) -> Result<()> {
+    git_init()?;
+    git_add(rules, "cog.toml")?;
+    git_commit("chore: init")?;
+    git_add("one", "one")?;
+    git_commit("[FIX] handle empty input")?;
+    git_add("two", "two")?;
+    git_commit("Update README")?;
+    git_add("three", "three")?;
+    git_commit("[parser] add arrays")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn import_history_rewrites_matching_commits() -> Result<()> {
+    // Arrange
+    legacy_history(RULES)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("import-history")
+        // Assert
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "No rewrite rule matches these commits",
+        ));
+
+    let summaries = run_fun!(git log --format=%s)?;
+    assert_that!(summaries.as_str()).is_equal_to(
+        "feat(parser): add arrays\nUpdate README\nfix: handle empty input\nchore: init",
+    );
+    Ok(())
+}
+
+#[sealed_test]
+fn import_history_dry_run_keeps_history() -> Result<()> {
+    // Arrange
+    legacy_history(RULES)?;
+    let head = run_fun!(git rev-parse HEAD)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["import-history", "--dry-run"])
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[parser] add arrays\n\t-> feat(parser): add arrays",
+        ))
+        .stdout(predicate::str::contains(
+            "[FIX] handle empty input\n\t-> fix: handle empty input",
+        ));
+
+    assert_that!(run_fun!(git rev-parse HEAD)?).is_equal_to(head);
+    Ok(())
+}
+
+#[sealed_test]
+fn import_history_rejects_non_compliant_rewrites() -> Result<()> {
+    // Arrange
+    let rules = indoc! {r#"
+        [[import_history.rules]]
+        pattern = '^\[(?P<module>\w+)\] '
+        template = "${module}: "
+    "#};
+    legacy_history(rules)?;
+    let head = run_fun!(git rev-parse HEAD)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("import-history")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("still not compliant"))
+        .stderr(predicate::str::contains("parser: add arrays"));
+
+    assert_that!(run_fun!(git rev-parse HEAD)?).is_equal_to(head);
+    Ok(())
+}
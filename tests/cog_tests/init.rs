@@ -70,3 +70,61 @@ fn init_current_dir_with_no_arg() -> Result<()> {
         .success();
     Ok(())
 }
+
+#[sealed_test]
+fn init_with_preset() -> Result<()> {
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["init", "--template", "node", "test_repo"])
+        .assert()
+        .success();
+
+    // Assert
+    let settings = std::fs::read_to_string("test_repo/cog.toml")?;
+    assert_that!(settings.as_str()).contains("version_source = \"package.json\"");
+    assert_that!(settings.as_str()).contains("npm version {{version}} --no-git-tag-version");
+    Ok(())
+}
+
+#[sealed_test]
+fn init_interactive() -> Result<()> {
+    // Act
+    assert_cmd::Command::cargo_bin("cog")?
+        .args(["init", "--interactive", "test_repo"])
+        .write_stdin("python\n\ngithub.com\ncocogitto\ncocogitto\ny\ny\n")
+        .assert()
+        .success();
+
+    // Assert
+    let settings = std::fs::read_to_string("test_repo/cog.toml")?;
+    assert_that!(settings.as_str()).contains("tag_prefix = \"v\"");
+    assert_that!(settings.as_str()).contains("version_source = \"pyproject.toml\"");
+    assert_that!(settings.as_str()).contains("template = \"remote\"");
+    assert_that!(settings.as_str()).contains("owner = \"cocogitto\"");
+    assert_that!(Path::new("test_repo/CHANGELOG.md")).exists();
+    assert_that!(Path::new("test_repo/.git/hooks/commit-msg")).exists();
+    assert_that!(Path::new("test_repo/.git/hooks/pre-push")).exists();
+    Ok(())
+}
+
+#[sealed_test]
+fn init_from_git_cliff_config() -> Result<()> {
+    // Arrange
+    std::fs::create_dir("test_repo")?;
+    std::fs::write(
+        "test_repo/cliff.toml",
+        "[git]\ntag_pattern = \"v[0-9]*\"\ncommit_parsers = [{ message = \"^feat\", group = \"New features\" }]",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["init", "--from", "git-cliff", "test_repo"])
+        .assert()
+        .success();
+
+    // Assert
+    let settings = std::fs::read_to_string("test_repo/cog.toml")?;
+    assert_that!(settings.as_str()).contains("tag_prefix = \"v\"");
+    assert_that!(settings.as_str()).contains("changelog_title = \"New features\"");
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use assert_cmd::Command;
+use cmd_lib::run_cmd;
+use sealed_test::prelude::*;
+use std::path::Path;
+
+use crate::helpers::*;
+
+#[sealed_test]
+fn install_hook_from_linked_worktree() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("chore: init")?;
+    run_cmd!(git worktree add -q ../linked)?;
+    std::env::set_current_dir("../linked")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("install-hook")
+        .arg("commit-msg")
+        // Assert
+        .assert()
+        .success();
+
+    assert!(Path::new("../repo/.git/hooks/commit-msg").exists());
+    Ok(())
+}
@@ -2,5 +2,17 @@ mod bump;
 mod changelog;
 mod check;
 mod commit;
+mod completion;
+mod config;
+mod edit;
+mod get_version;
+mod hook_config;
+mod import_history;
 mod init;
+mod install_hook;
+mod publish;
+mod release_notes;
+mod serve;
+mod stats;
+mod tag;
 mod verify;
@@ -0,0 +1,115 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use indoc::indoc;
+use sealed_test::prelude::*;
+use speculoos::prelude::*;
+use std::fs;
+
+const ENGINE: &str = "#!/bin/sh\necho \"$@\" >> published\n";
+
+fn fake_engine() -> Result<()> {
+    fs::write("engine.sh", ENGINE)?;
+    cmd_lib::run_cmd!(chmod +x engine.sh)?;
+    Ok(())
+}
+
+#[sealed_test]
+fn publish_runs_providers_in_order() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            "[[publish.providers]]
+            provider = \"container\"
+            image = \"registry.example.org/app\"
+            source = \"app\"
+            tags = [\"{{version}}\", \"latest\"]
+            engine = \"./engine.sh\"
+            "
+        ),
+        "cog.toml",
+    )?;
+    fake_engine()?;
+    git_add(ENGINE, "engine.sh")?;
+    git_commit("chore: init")?;
+    git_tag("1.2.0")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("publish")
+        // Assert
+        .assert()
+        .success();
+
+    let published = fs::read_to_string("published")?;
+    assert_that!(published.as_str()).is_equal_to(indoc!(
+        "tag app registry.example.org/app:1.2.0
+        push registry.example.org/app:1.2.0
+        tag app registry.example.org/app:latest
+        push registry.example.org/app:latest
+        "
+    ));
+    Ok(())
+}
+
+#[sealed_test]
+fn publish_dry_run_skips_container_images() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[[publish.providers]]\nprovider = \"container\"\nimage = \"app\"\nengine = \"./engine.sh\"",
+        "cog.toml",
+    )?;
+    fake_engine()?;
+    git_commit("chore: init")?;
+    git_tag("1.2.0")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["--output-format", "json", "publish", "--dry-run"])
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("./engine.sh push app:1.2.0"));
+
+    assert_that!(std::path::Path::new("published")).does_not_exist();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_publishes_released_version() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            "[publish]
+            on_bump = true
+
+            [[publish.providers]]
+            provider = \"container\"
+            image = \"app\"
+            engine = \"./engine.sh\"
+            "
+        ),
+        "cog.toml",
+    )?;
+    fake_engine()?;
+    git_add(ENGINE, "engine.sh")?;
+    git_add("published\n", ".gitignore")?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["bump", "--auto"])
+        // Assert
+        .assert()
+        .success();
+
+    let published = fs::read_to_string("published")?;
+    assert_that!(published.as_str()).is_equal_to("tag app app:1.1.0\npush app:1.1.0\n");
+    Ok(())
+}
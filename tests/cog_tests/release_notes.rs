@@ -0,0 +1,95 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use indoc::formatdoc;
+use pretty_assertions::assert_eq;
+use sealed_test::prelude::*;
+use std::process::Command;
+
+#[sealed_test]
+fn release_notes_from_commits() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    let feature = git_commit("feat: feature")?;
+    git_tag("1.0.0")?;
+    let fix = git_commit("fix: bug fix")?;
+    git_tag("1.0.1")?;
+
+    // Act
+    let notes = Command::cargo_bin("cog")?
+        .arg("release-notes")
+        .arg("1.0.0")
+        // Assert
+        .assert()
+        .success();
+
+    let notes = String::from_utf8(notes.get_output().stdout.clone())?;
+    assert_eq!(
+        notes,
+        formatdoc!(
+            "#### Features
+            - feature - ({feature}) - Tom
+            ",
+            feature = &feature[0..7],
+        )
+    );
+    assert!(!notes.contains(&fix[0..7]));
+    Ok(())
+}
+
+#[sealed_test]
+fn release_notes_from_changelog() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--version", "1.0.0"])
+        .assert()
+        .success();
+    git_commit("fix: bug fix")?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--version", "1.0.1"])
+        .assert()
+        .success();
+
+    // Notes edited by hand are kept as is
+    let changelog = std::fs::read_to_string("CHANGELOG.md")?;
+    let changelog = changelog.replace("- bug fix", "- bug fix, edited");
+    std::fs::write("CHANGELOG.md", changelog)?;
+
+    // Act
+    let notes = Command::cargo_bin("cog")?
+        .args(["release-notes", "1.0.1", "--from-changelog"])
+        // Assert
+        .assert()
+        .success();
+
+    let notes = String::from_utf8(notes.get_output().stdout.clone())?;
+    assert!(notes.starts_with("#### Bug Fixes\n- bug fix, edited"));
+    assert!(!notes.contains("feature"));
+    Ok(())
+}
+
+#[sealed_test]
+fn release_notes_of_unknown_version_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature")?;
+    Command::cargo_bin("cog")?
+        .args(["bump", "--version", "1.0.0"])
+        .assert()
+        .success();
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["release-notes", "2.0.0", "--from-changelog"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("no release 2.0.0 found"));
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use indoc::indoc;
+use sealed_test::prelude::*;
+use serde_json::Value;
+use speculoos::prelude::*;
+
+#[sealed_test]
+fn serve_answers_one_request_per_line() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat(parser): a feature")?;
+    let requests = indoc! {r#"
+        {"id": 1, "method": "verify", "params": {"message": "feat(cli): serve"}}
+        {"id": 2, "method": "verify", "params": {"message": "fest: serve"}}
+        {"id": 3, "method": "scopes"}
+        {"id": 4, "method": "preview", "params": {"message": "fix(cli): a fix"}}
+        {"id": 5, "method": "unknown"}
+        {"id": 6, "method": "exit"}
+        {"id": 7, "method": "types"}
+    "#};
+
+    // Act
+    let output = assert_cmd::Command::cargo_bin("cog")?
+        .arg("serve")
+        .write_stdin(requests)
+        // Assert
+        .assert()
+        .success();
+
+    let responses: Vec<Value> = String::from_utf8(output.get_output().stdout.clone())?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+
+    assert_that!(responses).has_length(6);
+    assert_that!(responses[0]["result"]).is_equal_to(Value::Null);
+    assert_that!(responses[1]["result"]["component"]).is_equal_to(Value::from("type"));
+    assert_that!(responses[2]["result"]).is_equal_to(serde_json::json!(["parser"]));
+    let preview = responses[3]["result"].as_str().unwrap_or_default();
+    assert_that!(preview.to_string()).starts_with("#### Bug Fixes\n- **(cli)** a fix");
+    assert_that!(responses[4]["error"]["code"]).is_equal_to(Value::from(-32601));
+    assert_that!(responses[5]["id"]).is_equal_to(Value::from(6));
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use predicates::prelude::predicate;
+use sealed_test::prelude::*;
+use speculoos::prelude::*;
+
+#[sealed_test]
+fn stats_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat(parser): feature")?;
+    git_commit("fix(parser)!: breaking fix")?;
+    git_commit("not conventional")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("stats")
+        // Assert
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "4 (1 non compliant, 1 breaking changes)",
+        ))
+        .stdout(predicate::str::contains("  parser  2"));
+    Ok(())
+}
+
+#[sealed_test]
+fn stats_json_in_range() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat(parser): feature")?;
+    git_commit("fix: bug fix")?;
+
+    // Act
+    let output = Command::cargo_bin("cog")?
+        .args(["stats", "--json", "1.0.0.."])
+        .assert()
+        .success();
+
+    // Assert
+    let stats: serde_json::Value = serde_json::from_slice(&output.get_output().stdout)?;
+    assert_that!(stats["total"]).is_equal_to(serde_json::json!(2));
+    assert_that!(stats["types"]).is_equal_to(serde_json::json!({ "feat": 1, "fix": 1 }));
+    assert_that!(stats["scopes"]).is_equal_to(serde_json::json!({ "parser": 1 }));
+    Ok(())
+}
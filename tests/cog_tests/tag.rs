@@ -0,0 +1,186 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use assert_cmd::Command;
+use cmd_lib::run_fun;
+use predicates::prelude::predicate;
+use sealed_test::prelude::*;
+use speculoos::prelude::*;
+
+#[sealed_test]
+fn tag_list_is_sorted_by_version() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "tag_prefix = \"v\"\nlegacy_tag_prefixes = [\"cog-\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: init")?;
+    git_tag("cog-0.9.0")?;
+    git_commit("feat: feature")?;
+    git_tag("v1.10.0")?;
+    git_commit("feat: another feature")?;
+    git_tag("v1.2.0")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["tag", "list"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("cog-0.9.0\nv1.2.0\nv1.10.0\n");
+
+    Command::cargo_bin("cog")?
+        .args(["tag", "list", "--prefix", "v"])
+        .assert()
+        .success()
+        .stdout("v1.2.0\nv1.10.0\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn tag_latest() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+    git_tag("1.1.0")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["tag", "latest"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("1.1.0\n");
+    Ok(())
+}
+
+#[sealed_test]
+fn tag_create_adds_the_prefix_and_message() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"v\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    git_tag("v1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["tag", "create", "1.1.0", "--message", "Release 1.1.0"])
+        // Assert
+        .assert()
+        .success()
+        .stdout("v1.1.0\n");
+
+    assert_tag_exists("v1.1.0")?;
+    let message = run_fun!(git cat-file -p v1.1.0)?;
+    assert_that!(message).ends_with("\n\nRelease 1.1.0");
+    Ok(())
+}
+
+#[sealed_test]
+fn tag_create_refuses_lower_version() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: feature")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["tag", "create", "0.9.0"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SemVer Error"));
+
+    assert_tag_does_not_exist("0.9.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn tag_create_refuses_invalid_version() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["tag", "create", "1.0"])
+        // Assert
+        .assert()
+        .failure();
+
+    assert_tag_does_not_exist("1.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn tag_backfill_from_manifest_history() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_add(
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"",
+        "Cargo.toml",
+    )?;
+    let first_release = git_commit("chore: release 0.1.0")?;
+    git_commit("feat: feature")?;
+    git_add(
+        "[package]\nname = \"app\"\nversion = \"0.2.0\"",
+        "Cargo.toml",
+    )?;
+    let second_release = git_commit("chore: release 0.2.0")?;
+    git_add(
+        "[package]\nname = \"app\"\nversion = \"0.2.0\"\nedition = \"2021\"",
+        "Cargo.toml",
+    )?;
+    git_commit("chore: set the edition")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["tag", "--backfill", "--manifest", "Cargo.toml"])
+        // Assert
+        .assert()
+        .success();
+
+    assert_that!(run_fun!(git rev-list -n 1 0.1.0)?).starts_with(&first_release);
+    assert_that!(run_fun!(git rev-list -n 1 0.2.0)?).starts_with(&second_release);
+    assert_latest_tag("0.2.0")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn tag_backfill_dry_run_skips_tagged_versions() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("version_source = \"VERSION\"", "cog.toml")?;
+    git_add("1.0.0", "VERSION")?;
+    git_commit("chore: release 1.0.0")?;
+    git_add("1.1.0", "VERSION")?;
+    git_commit("chore: release 1.1.0")?;
+    git_tag("1.1.0")?;
+    git_add("1.2.0", "VERSION")?;
+    git_commit("chore: release 1.2.0")?;
+
+    // Act
+    let output = Command::cargo_bin("cog")?
+        .args(["tag", "--backfill", "--dry-run"])
+        // Assert
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let tags: Vec<String> = String::from_utf8(output)?
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect();
+    assert_that!(tags).is_equal_to(vec!["1.0.0".to_string(), "1.2.0".to_string()]);
+    assert_tag_does_not_exist("1.0.0")?;
+    Ok(())
+}
@@ -148,3 +148,197 @@ fn should_ignore_merge_commit_via_config() -> Result<()> {
 
     Ok(())
 }
+
+#[sealed_test]
+fn verify_from_file_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    let message = indoc!(
+        "feat: a commit message
+
+        # Please enter the commit message for your changes. Lines starting
+        # with '#' will be ignored, and an empty message aborts the commit.
+        "
+    );
+    std::fs::write("COMMIT_EDITMSG", message)?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("--file")
+        .arg("COMMIT_EDITMSG")
+        // Assert
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn verify_from_file_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    std::fs::write("COMMIT_EDITMSG", "invalid message")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("--file")
+        .arg("COMMIT_EDITMSG")
+        // Assert
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn verify_reports_diagnostic() -> Result<()> {
+    // Arrange
+    let message = "feat(my scope): a commit message";
+
+    // Act
+    let output = Command::cargo_bin("cog")?
+        .args(["--output-format", "json", "verify", message])
+        // Assert
+        .assert()
+        .failure();
+
+    let diagnostic: serde_json::Value = serde_json::from_slice(&output.get_output().stdout)?;
+    assert_eq!(diagnostic["component"], "scope");
+    assert_eq!(diagnostic["span"]["start"], 4);
+    assert_eq!(diagnostic["span"]["end"], 14);
+    assert_eq!(
+        diagnostic["suggestion"],
+        "use dashes instead of whitespace: `(my-scope)`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_underlines_the_faulty_part() -> Result<()> {
+    // Act
+    Command::cargo_bin("cog")?
+        .args(["verify", "faet: a commit message"])
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(indoc!(
+            "
+            1 | faet: a commit message
+              | ^^^^ Commit type `faet` not allowed
+              = suggestion: did you mean `feat`?"
+        )));
+
+    Ok(())
+}
+
+#[sealed_test]
+fn verify_requires_ticket_reference() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint.ticket_reference]\npattern = \"#\\\\d+\"\n",
+        "cog.toml",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("fix: rounding")
+        // Assert
+        .assert()
+        .failure();
+
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("fix: rounding\n\nCloses #12")
+        // Assert
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn verify_highlights_banned_words() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[lint]\nbanned_words = [\"wip\"]\n", "cog.toml")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("feat: wip refunds")
+        .env("CLICOLOR_FORCE", "1")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("\u{1b}[1;31mwip\u{1b}[0m"))
+        .stderr(predicates::str::contains(
+            "Banned word `wip` in the summary",
+        ));
+
+    Ok(())
+}
+
+#[sealed_test]
+fn verify_requires_imperative_mood() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("[lint]\nimperative_mood = true\n", "cog.toml")?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("feat: adding refunds")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Summary starts with `adding`, use the imperative mood: `add` (imperative-mood)",
+        ));
+
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("feat: add refunds")
+        // Assert
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn verify_checks_body_layout() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[lint]\nblank_line_after_summary = true\nbody_max_line_length = 20\n",
+        "cog.toml",
+    )?;
+
+    // Act
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("feat: add refunds\nRefunds are partial for now")
+        // Assert
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Missing blank line between the summary and the body (blank-line-after-summary)",
+        ))
+        .stderr(predicates::str::contains(
+            "Line 2 is 27 characters long, the maximum is 20 (body-line-length)",
+        ));
+
+    Command::cargo_bin("cog")?
+        .arg("verify")
+        .arg("feat: add refunds\n\nSee https://example.org/refunds/partial")
+        // Assert
+        .assert()
+        .success();
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use anyhow::Result;
 
-use cmd_lib::run_cmd;
-use cocogitto::{conventional::version::VersionIncrement, CocoGitto};
+use cmd_lib::{run_cmd, run_fun};
+use cocogitto::conventional::version::{Increment, VersionIncrement};
+use cocogitto::error::CocogittoError;
+use cocogitto::settings::HookType;
+use cocogitto::CocoGitto;
 use indoc::indoc;
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
@@ -20,7 +23,7 @@ fn bump_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
 
     // Assert
     assert_that!(result).is_ok();
@@ -28,6 +31,92 @@ fn bump_ok() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn bump_outcome_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            pre_bump_hooks = [
+                { command = "echo pre {{version}}", capture_output = true },
+            ]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: first commit")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let outcome = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None)?;
+
+    // Assert
+    assert_that!(outcome.previous_tag).is_equal_to(Some("1.0.0".to_string()));
+    assert_that!(outcome.new_tag.as_str()).is_equal_to("1.0.1");
+    assert_that!(outcome.increment).is_equal_to(Increment::Patch);
+    assert_that!(outcome.changelog_path).is_some();
+    assert_that!(outcome.pre_bump_hooks[0].stdout).is_equal_to(Some("pre 1.0.1\n".to_string()));
+    assert_that!(outcome.post_bump_hooks).is_empty();
+    assert_that!(outcome.commit).is_equal_to(Some(run_fun!(git rev-parse HEAD)?));
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_outcome_keeps_allowed_hook_failures() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        indoc!(
+            r#"
+            pre_bump_hooks = [
+                { command = "echo oops; exit 3", capture_output = true, allow_failure = true },
+            ]
+            "#
+        ),
+        "cog.toml",
+    )?;
+    git_commit("chore: first commit")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let outcome = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None)?;
+
+    // Assert
+    let hook = &outcome.pre_bump_hooks[0];
+    assert_that!(hook.failed).is_true();
+    assert_that!(hook.status).is_equal_to(Some(3));
+    assert_that!(hook.stdout).is_equal_to(Some("oops\n".to_string()));
+    assert_latest_tag("1.0.1")?;
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_failed_pre_bump_hook_returns_error() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("pre_bump_hooks = [\"exit 1\"]", "cog.toml")?;
+    git_commit("chore: first commit")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: a bug fix")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
+
+    // Assert
+    assert_that!(matches!(result, Err(CocogittoError::PreHook(_)))).is_true();
+    assert_latest_tag("1.0.0")?;
+    Ok(())
+}
+
 #[sealed_test]
 fn should_fallback_to_0_0_0_when_there_is_no_tag() -> Result<()> {
     // Arrange
@@ -38,7 +127,7 @@ fn should_fallback_to_0_0_0_when_there_is_no_tag() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
 
     // Assert
     assert_that!(result).is_ok();
@@ -58,7 +147,7 @@ fn should_fail_when_latest_tag_is_not_semver_compliant() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
     let error = result.unwrap_err().to_string();
     let error = error.as_str();
 
@@ -89,7 +178,7 @@ fn bump_with_whitelisted_branch_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
 
     // Assert
     assert_that!(result).is_ok();
@@ -114,7 +203,7 @@ fn bump_with_whitelisted_branch_fails() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
 
     // Assert
     assert_that!(result.unwrap_err().to_string()).is_equal_to(
@@ -124,6 +213,108 @@ fn bump_with_whitelisted_branch_fails() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn bump_with_regex_whitelisted_branch_ok() -> Result<()> {
+    // Arrange
+    let settings = r#"branch_whitelist = [ "regex:mast(er)?" ]"#;
+
+    git_init()?;
+    run_cmd!(
+        echo $settings > cog.toml;
+        git add .;
+    )?;
+
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
+
+    // Assert
+    assert_that!(result).is_ok();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_denied_branch_fails() -> Result<()> {
+    // Arrange
+    let settings = r#"branch_denylist = [ "regex:ma[a-z]+" ]"#;
+
+    git_init()?;
+    run_cmd!(
+        echo $settings > cog.toml;
+        git add .;
+    )?;
+
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
+
+    // Assert
+    assert_that!(result.unwrap_err().to_string()).starts_with(
+        "Branch 'master' matches the denied pattern 'regex:ma[a-z]+', bump is not allowed",
+    );
+
+    let mut cocogitto = CocoGitto::get()?.with_ignore_branch_protection();
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
+    assert_that!(result).is_ok();
+
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_with_dirty_repository_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_commit("feat: add a feature commit")?;
+    std::fs::write("untracked", "content")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
+
+    // Assert
+    assert_that!(matches!(result, Err(CocogittoError::DirtyRepository(_)))).is_true();
+    Ok(())
+}
+
+#[sealed_test]
+fn bump_to_lower_version_fails() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: add a feature commit")?;
+
+    let mut cocogitto = CocoGitto::get()?;
+
+    // Act
+    let result = cocogitto.create_version(
+        VersionIncrement::Manual("0.1.0".to_string()),
+        None,
+        None,
+        false,
+        None,
+    );
+
+    // Assert
+    assert_that!(matches!(
+        result,
+        Err(CocogittoError::SemverViolation { .. })
+    ))
+    .is_true();
+    Ok(())
+}
+
 #[sealed_test]
 fn bump_with_whitelisted_branch_pattern_ok() -> Result<()> {
     // Arrange
@@ -143,7 +334,7 @@ fn bump_with_whitelisted_branch_pattern_ok() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
 
     // Assert
     assert_that!(result).is_ok();
@@ -168,10 +359,33 @@ fn bump_with_whitelisted_branch_pattern_err() -> Result<()> {
     let mut cocogitto = CocoGitto::get()?;
 
     // Act
-    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false);
+    let result = cocogitto.create_version(VersionIncrement::Auto, None, None, false, None);
 
     // Assert
     assert_that!(result).is_err();
 
     Ok(())
 }
+
+#[sealed_test]
+fn render_bump_hooks_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "pre_bump_hooks = [\"echo {{latest}} {{version}}\"]",
+        "cog.toml",
+    )?;
+    git_commit("chore: first commit")?;
+    git_tag("1.0.0")?;
+    git_commit("fix: the fix")?;
+
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let hooks = cocogitto.render_bump_hooks(VersionIncrement::Auto, None, None)?;
+
+    // Assert
+    assert_that!(hooks).is_equal_to(vec![(HookType::PreBump, "echo 1.0.0 1.0.1".to_string())]);
+    assert_latest_tag("1.0.0")?;
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use crate::helpers::*;
 
 use anyhow::Result;
+use cmd_lib::run_cmd;
+use cocogitto::error::CocogittoError;
+use cocogitto::git::revspec::RevspecPattern;
 use cocogitto::CocoGitto;
 use sealed_test::prelude::*;
 use speculoos::prelude::*;
@@ -32,6 +35,146 @@ fn open_repo_err() -> Result<()> {
     Ok(())
 }
 
+#[sealed_test]
+fn open_repo_at_path_ok() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("repo")?;
+    git_commit("feat: a valid commit")?;
+    std::env::set_current_dir("..")?;
+
+    // Act
+    let cocogitto = CocoGitto::open("repo")?;
+
+    // Assert
+    assert_that!(cocogitto.get_committer()).is_ok();
+    assert_that!(CocoGitto::get()).is_err();
+    Ok(())
+}
+
+#[sealed_test]
+fn open_repos_with_distinct_settings_ok() -> Result<()> {
+    // Arrange
+    git_init_and_set_current_path("custom")?;
+    git_add(
+        "[commit_types]\nwip = { changelog_title = \"Work in progress\" }",
+        "cog.toml",
+    )?;
+    git_commit("wip: a custom commit type")?;
+    std::env::set_current_dir("..")?;
+    run_cmd!(
+        git init default;
+        git -C default config --local user.name Tom;
+        git -C default config --local user.email toml.bombadil@themail.org;
+        git -C default commit --allow-empty -q -m "wip: a custom commit type";
+    )?;
+
+    // Act
+    let custom = CocoGitto::open("custom")?;
+    let default = CocoGitto::open("default")?;
+
+    // Assert
+    assert_that!(custom.check(false, false)).is_ok();
+    assert_that!(default.check(false, false)).is_err();
+    Ok(())
+}
+
+#[sealed_test]
+fn open_repo_with_settings_in_manifest() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        "[package]\nname = \"app\"\n\n[package.metadata.cocogitto]\ntag_prefix = \"v\"",
+        "Cargo.toml",
+    )?;
+    git_add(
+        r#"{ "cocogitto": { "tag_prefix": "npm-" } }"#,
+        "package.json",
+    )?;
+    git_commit("chore: init")?;
+
+    // Act
+    let from_cargo = CocoGitto::get()?.settings().tag_prefix.clone();
+    git_add("tag_prefix = \"cog-\"", "cog.toml")?;
+    let from_cog_toml = CocoGitto::get()?.settings().tag_prefix.clone();
+
+    // Assert
+    assert_that!(from_cargo).is_equal_to(Some("v".to_string()));
+    assert_that!(from_cog_toml).is_equal_to(Some("cog-".to_string()));
+    Ok(())
+}
+
+#[sealed_test]
+fn open_repo_with_settings_in_package_json() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add(
+        r#"{ "name": "app", "cocogitto": { "tag_prefix": "v" } }"#,
+        "package.json",
+    )?;
+    git_commit("chore: init")?;
+
+    // Act
+    let cocogitto = CocoGitto::get()?;
+
+    // Assert
+    assert_that!(cocogitto.settings().tag_prefix).is_equal_to(Some("v".to_string()));
+    Ok(())
+}
+
+#[sealed_test]
+fn open_repo_with_user_config_and_env_overrides() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_add("tag_prefix = \"repo-\"", "cog.toml")?;
+    git_commit("chore: init")?;
+    std::fs::create_dir_all("xdg/cog")?;
+    std::fs::write(
+        "xdg/cog/cog.toml",
+        "tag_prefix = \"user-\"\nfrom_latest_tag = true\n[changelog]\npath = \"USER.md\"",
+    )?;
+    std::env::set_var("XDG_CONFIG_HOME", std::env::current_dir()?.join("xdg"));
+    std::env::set_var("COG_CONFIG_IGNORE_MERGE_COMMITS", "true");
+    std::env::set_var("COG_CONFIG_CHANGELOG__PATH", "ENV.md");
+    // Exported to hooks, not a setting
+    std::env::set_var("COG_CURRENT_VERSION", "1.0.0");
+    std::env::set_var("COG_TAG", "1.0.0");
+
+    // Act
+    let cocogitto = CocoGitto::get()?;
+
+    // Assert
+    let settings = cocogitto.settings();
+    assert_that!(settings.tag_prefix).is_equal_to(Some("repo-".to_string()));
+    assert_that!(settings.from_latest_tag).is_true();
+    assert_that!(settings.ignore_merge_commits).is_true();
+    assert_that!(settings.changelog.path).is_equal_to(std::path::PathBuf::from("ENV.md"));
+    Ok(())
+}
+
+#[sealed_test]
+fn commit_iter_ok() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: first commit")?;
+    git_tag("1.0.0")?;
+    git_commit("feat: a feature")?;
+    git_commit("not conventional")?;
+    git_commit("fix: a bug fix")?;
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let commits: Vec<_> = cocogitto
+        .commit_iter(&RevspecPattern::default())?
+        .take(2)
+        .collect();
+
+    // Assert
+    assert_that!(commits).has_length(2);
+    assert_that!(commits[0]).is_ok();
+    assert_that!(matches!(commits[1], Err(CocogittoError::InvalidCommit(_)))).is_true();
+    Ok(())
+}
+
 #[sealed_test]
 fn check_commit_history_ok() -> Result<()> {
     // Arrange
@@ -93,7 +236,7 @@ fn check_commit_history_err() -> Result<()> {
     let check = cocogitto.check(false, false);
 
     // Assert
-    assert_that!(check).is_err();
+    assert_that!(matches!(check, Err(CocogittoError::NotConventional(_)))).is_true();
     Ok(())
 }
 
@@ -142,7 +285,7 @@ fn long_commit_summary_does_not_panic() -> Result<()> {
 
     let cocogitto = CocoGitto::get()?;
     git_add("Hello", "file")?;
-    cocogitto.conventional_commit("feat", None, message, None, None, false, false)?;
+    cocogitto.conventional_commit("feat", None, message, None, None, false, None)?;
 
     let check = cocogitto.check(false, false);
 
@@ -1,4 +1,6 @@
+use cocogitto::git::revspec::RevspecPattern;
 use cocogitto::log::filter::{CommitFilter, CommitFilters};
+use cocogitto::log::format::{LogFormat, LogOptions};
 use cocogitto::CocoGitto;
 
 use crate::helpers::*;
@@ -48,3 +50,63 @@ fn get_log_with_no_errors() -> Result<()> {
 
     Ok(())
 }
+
+#[sealed_test]
+fn write_oneline_log_with_max_count() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("feat: a commit")?;
+    git_commit("fix(parser): a fix")?;
+    git_commit("I am afraid I can't do that Dave")?;
+    let cocogitto = CocoGitto::get()?;
+    let options = LogOptions {
+        format: LogFormat::Oneline,
+        max_count: Some(2),
+        ..Default::default()
+    };
+
+    // Act
+    let mut logs = vec![];
+    cocogitto.write_log(&mut logs, &CommitFilters(vec![]), &options)?;
+
+    // Assert
+    let logs = String::from_utf8(logs)?;
+    let lines: Vec<&str> = logs.lines().collect();
+    assert_that!(lines).has_length(2);
+    assert_that!(lines[0]).contains("I am afraid I can't do that Dave");
+    assert_that!(lines[1]).contains("fix(parser): a fix");
+    Ok(())
+}
+
+#[sealed_test]
+fn write_json_log_in_range() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("feat: a commit")?;
+    git_tag("1.0.0")?;
+    git_commit("fix(parser)!: a fix")?;
+    git_commit("not conventional")?;
+    let cocogitto = CocoGitto::get()?;
+    let options = LogOptions {
+        pattern: Some(RevspecPattern::from("1.0.0..")),
+        format: LogFormat::Json,
+        max_count: None,
+    };
+
+    // Act
+    let mut logs = vec![];
+    cocogitto.write_log(&mut logs, &CommitFilters(vec![]), &options)?;
+
+    // Assert
+    let logs: Vec<serde_json::Value> = String::from_utf8(logs)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_that!(logs).has_length(2);
+    assert_that!(logs[0]["summary"]).is_equal_to(serde_json::json!("not conventional"));
+    assert_that!(logs[0]["error"].is_string()).is_true();
+    assert_that!(logs[1]["type"]).is_equal_to(serde_json::json!("fix"));
+    assert_that!(logs[1]["scope"]).is_equal_to(serde_json::json!("parser"));
+    assert_that!(logs[1]["breaking_change"]).is_equal_to(serde_json::json!(true));
+    Ok(())
+}
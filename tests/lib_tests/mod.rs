@@ -2,3 +2,4 @@ mod bump;
 mod cocogitto;
 mod init;
 mod log;
+mod release;
@@ -0,0 +1,37 @@
+use crate::helpers::*;
+
+use anyhow::Result;
+use cocogitto::git::revspec::RevspecPattern;
+use cocogitto::CocoGitto;
+use sealed_test::prelude::*;
+use speculoos::prelude::*;
+
+#[sealed_test]
+fn walk_releases_newest_first() -> Result<()> {
+    // Arrange
+    git_init()?;
+    git_commit("chore: init")?;
+    git_commit("feat: feature 1")?;
+    git_tag("1.0.0")?;
+    let fix = git_commit("fix: bug fix 1")?;
+    git_commit("fix: bug fix 2")?;
+    git_tag("1.0.1")?;
+    let cocogitto = CocoGitto::get()?;
+
+    // Act
+    let changelog = cocogitto.get_changelog(RevspecPattern::default(), true)?;
+
+    // Assert
+    let versions: Vec<String> = changelog
+        .iter()
+        .map(|release| release.version.to_string())
+        .collect();
+    assert_that!(versions).is_equal_to(vec!["1.0.1".to_string(), "1.0.0".to_string()]);
+
+    let latest = changelog.iter().next().unwrap();
+    assert_that!(latest.commits).has_length(2);
+    assert_that!(latest.commits[1].commit.oid()).is_equal_to(fix.as_str());
+    assert_that!(latest.commits[1].commit.message().summary.as_str()).is_equal_to("bug fix 1");
+    assert_that!(latest.previous().map(|release| release.commits.len())).is_equal_to(Some(1));
+    Ok(())
+}